@@ -1,23 +1,407 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use crate::project::Project;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Poll;
+use std::time::Duration;
+use crate::offline;
+use crate::project::{ClipLabel, ClipMatch, ConversationTurn, Project};
+use crate::startup::{self, AgentStatus};
 
-const OLLAMA_URL: &str = "http://localhost:11434/api/generate";
-const MODEL: &str = "qwen2.5:3b";
+/// Where to reach Ollama and which model to ask
+#[derive(Clone, Debug)]
+pub struct OllamaEndpoint {
+    /// Model name as listed by `ollama list` (e.g., "qwen2.5:3b")
+    pub model: String,
+    /// How long to wait for a generation before giving up
+    pub timeout: Duration,
+    /// Base URL of the Ollama server (e.g., "http://localhost:11434")
+    pub url: String,
+}
+
+impl OllamaEndpoint {
+    /// URL of the text generation API
+    pub fn generate_url(&self) -> String {
+        format!("{}/api/generate", self.url.trim_end_matches('/'))
+    }
+    
+    /// URL listing the locally available models
+    pub fn tags_url(&self) -> String {
+        format!("{}/api/tags", self.url.trim_end_matches('/'))
+    }
+    
+    /// URL listing the models currently loaded in memory
+    pub fn ps_url(&self) -> String {
+        format!("{}/api/ps", self.url.trim_end_matches('/'))
+    }
+}
+
+/// Which kind of service answers agent requests
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentBackendKind {
+    /// A local Ollama server
+    #[default]
+    Ollama,
+    /// Anything speaking OpenAI's chat completions API (OpenAI, OpenRouter, LM Studio)
+    OpenAiCompatible,
+}
+
+impl AgentBackendKind {
+    /// Short name for status chips
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Ollama => "Ollama",
+            Self::OpenAiCompatible => "OpenAI API",
+        }
+    }
+}
+
+/// Future returned by `AgentBackend::complete`
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// A language model the agent can send its prompts to
+pub trait AgentBackend: Send + Sync {
+    fn kind(&self) -> AgentBackendKind;
+    
+    /// Send the system prompt and a request, returning the whole reply;
+    /// `on_text` gets each piece of it as it is generated
+    fn complete<'a>(
+        &'a self,
+        system: &'a str,
+        prompt: &'a str,
+        on_text: &'a mut (dyn FnMut(&str) + Send),
+    ) -> BoxFuture<'a, Result<String>>;
+    
+    /// Whether the server answers and will use the configured model (blocks on HTTP)
+    fn check(&self) -> AgentStatus;
+}
+
+/// The agent's current behavior: a local Ollama server
+pub struct OllamaBackend {
+    pub endpoint: OllamaEndpoint,
+}
 
 #[derive(Debug, Serialize)]
-struct OllamaRequest {
-    model: String,
-    prompt: String,
+struct OllamaRequest<'a> {
+    model: &'a str,
+    system: &'a str,
+    prompt: &'a str,
     stream: bool,
-    format: String,
+    format: &'a str,
 }
 
+/// One line of Ollama's streamed reply
 #[derive(Debug, Deserialize)]
-struct OllamaResponse {
+struct OllamaChunk {
+    #[serde(default)]
     response: String,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+impl AgentBackend for OllamaBackend {
+    fn kind(&self) -> AgentBackendKind {
+        AgentBackendKind::Ollama
+    }
+    
+    fn complete<'a>(
+        &'a self,
+        system: &'a str,
+        prompt: &'a str,
+        on_text: &'a mut (dyn FnMut(&str) + Send),
+    ) -> BoxFuture<'a, Result<String>> {
+        Box::pin(async move {
+            let mut streamed = false;
+            let first = self
+                .generate(system, prompt, &mut |text| {
+                    streamed = true;
+                    on_text(text);
+                })
+                .await;
+            let error = match first {
+                Ok(reply) => return Ok(reply),
+                Err(e) => e,
+            };
+            
+            // A cold model can outlast the timeout while it loads, and a server
+            // that was just started refuses connections for a moment
+            if streamed || !(is_timeout(&error) || is_unreachable(&error)) {
+                return Err(self.explain(error).await);
+            }
+            tracing::warn!("Ollama request failed ({:#}); retrying once", error);
+            if is_unreachable(&error) {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+            match self.generate(system, prompt, on_text).await {
+                Ok(reply) => Ok(reply),
+                Err(e) => Err(self.explain(e).await),
+            }
+        })
+    }
+    
+    fn check(&self) -> AgentStatus {
+        startup::check_ollama(&self.endpoint)
+    }
+}
+
+impl OllamaBackend {
+    /// A single streamed generation request
+    async fn generate(&self, system: &str, prompt: &str, on_text: &mut (dyn FnMut(&str) + Send)) -> Result<String> {
+        let ollama = &self.endpoint;
+        let request = OllamaRequest {
+            model: &ollama.model,
+            system,
+            prompt,
+            stream: true,
+            format: "json",
+        };
+        let request = reqwest::Client::new().post(ollama.generate_url()).json(&request).send();
+        let response = within(ollama.timeout, request)
+            .await?
+            .map_err(|e| request_error(e, ollama.timeout, "Failed to connect to Ollama. Is it running? (ollama serve)"))?;
+        
+        if !response.status().is_success() {
+            anyhow::bail!("Ollama returned error: {}", response.status());
+        }
+        
+        // One JSON object per line, each carrying the next few tokens
+        let mut reply = String::new();
+        for_each_line(response, ollama.timeout, |line| {
+            let chunk: OllamaChunk = serde_json::from_str(line)
+                .context("Failed to parse Ollama response")?;
+            if let Some(error) = chunk.error {
+                anyhow::bail!("Ollama returned error: {}", error);
+            }
+            on_text(&chunk.response);
+            reply.push_str(&chunk.response);
+            Ok(())
+        })
+        .await?;
+        Ok(reply)
+    }
+    
+    /// Say why a request failed in terms of what to do about it
+    async fn explain(&self, error: anyhow::Error) -> anyhow::Error {
+        let ollama = &self.endpoint;
+        if is_unreachable(&error) {
+            return error.context(format!("Ollama isn't running at {} (start it with `ollama serve`)", ollama.url));
+        }
+        if is_timeout(&error) && !self.is_loaded().await {
+            return error.context(format!("`{}` is still loading into memory (try again in a moment)", ollama.model));
+        }
+        error
+    }
+    
+    /// Whether the server has the configured model in memory right now
+    async fn is_loaded(&self) -> bool {
+        let ollama = &self.endpoint;
+        let Ok(response) = reqwest::Client::new().get(ollama.ps_url()).timeout(Duration::from_secs(2)).send().await else {
+            return false;
+        };
+        response
+            .json::<startup::OllamaTags>()
+            .await
+            .is_ok_and(|loaded| loaded.has_model(&ollama.model))
+    }
+}
+
+/// A hosted or local server speaking OpenAI's chat completions API
+pub struct OpenAiCompatibleBackend {
+    /// Sent as a bearer token; local servers usually don't need one
+    pub api_key: Option<String>,
+    /// API root, e.g. "https://api.openai.com/v1" or "http://localhost:1234/v1"
+    pub base_url: String,
+    pub model: String,
+    /// How long to wait for a reply before giving up
+    pub timeout: Duration,
+}
+
+impl OpenAiCompatibleBackend {
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url.trim_end_matches('/'), path)
+    }
+    
+    fn authorized(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self.api_key.as_deref().filter(|key| !key.is_empty()) {
+            Some(key) => request.bearer_auth(key),
+            None => request,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: [ChatMessage<'a>; 2],
+    stream: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+/// One `data:` event of a streamed chat completion
+#[derive(Debug, Deserialize)]
+struct ChatChunk {
+    #[serde(default)]
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    #[serde(default)]
+    delta: ChatDelta,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ChatDelta {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+impl AgentBackend for OpenAiCompatibleBackend {
+    fn kind(&self) -> AgentBackendKind {
+        AgentBackendKind::OpenAiCompatible
+    }
+    
+    fn complete<'a>(
+        &'a self,
+        system: &'a str,
+        prompt: &'a str,
+        on_text: &'a mut (dyn FnMut(&str) + Send),
+    ) -> BoxFuture<'a, Result<String>> {
+        Box::pin(async move {
+            let request = ChatRequest {
+                model: &self.model,
+                messages: [
+                    ChatMessage { role: "system", content: system },
+                    ChatMessage { role: "user", content: prompt },
+                ],
+                stream: true,
+            };
+            let request = self
+                .authorized(reqwest::Client::new().post(self.url("chat/completions")))
+                .json(&request)
+                .send();
+            let response = within(self.timeout, request)
+                .await?
+                .map_err(|e| request_error(e, self.timeout, &format!("Failed to connect to {}", self.base_url)))?;
+            
+            let status = response.status();
+            if !status.is_success() {
+                let body = response.text().await.unwrap_or_default();
+                anyhow::bail!("The API returned {}: {}", status, body.trim());
+            }
+            
+            // Server-sent events: "data: {json}" lines, ending with "data: [DONE]"
+            let mut reply = String::new();
+            for_each_line(response, self.timeout, |line| {
+                let Some(data) = line.strip_prefix("data:").map(str::trim) else {
+                    return Ok(());
+                };
+                if data == "[DONE]" {
+                    return Ok(());
+                }
+                let chunk: ChatChunk = serde_json::from_str(data)
+                    .context("Failed to parse the API response")?;
+                for content in chunk.choices.into_iter().filter_map(|c| c.delta.content) {
+                    on_text(&content);
+                    reply.push_str(&content);
+                }
+                Ok(())
+            })
+            .await?;
+            Ok(reply)
+        })
+    }
+    
+    fn check(&self) -> AgentStatus {
+        #[derive(Deserialize)]
+        struct Models {
+            #[serde(default)]
+            data: Vec<Model>,
+        }
+        #[derive(Deserialize)]
+        struct Model {
+            id: String,
+        }
+        
+        let client = reqwest::blocking::Client::new();
+        let mut request = client.get(self.url("models")).timeout(Duration::from_secs(5));
+        if let Some(key) = self.api_key.as_deref().filter(|key| !key.is_empty()) {
+            request = request.bearer_auth(key);
+        }
+        
+        match request.send() {
+            Ok(response) if matches!(response.status().as_u16(), 401 | 403) => AgentStatus::Rejected,
+            Ok(response) if response.status().is_success() => {
+                // Not every server lists its models; only trust a non-empty list
+                let models = response.json::<Models>().map(|m| m.data).unwrap_or_default();
+                if models.is_empty() || models.iter().any(|m| m.id == self.model) {
+                    AgentStatus::Ready(self.model.clone())
+                } else {
+                    AgentStatus::NoModel(self.model.clone())
+                }
+            }
+            _ => AgentStatus::NotRunning,
+        }
+    }
 }
 
+/// Describe a failed HTTP call, telling timeouts apart from connection problems
+fn request_error(e: reqwest::Error, timeout: Duration, connect_hint: &str) -> anyhow::Error {
+    if e.is_timeout() {
+        anyhow::Error::new(e).context(format!("The model took longer than {}s to answer", timeout.as_secs()))
+    } else {
+        anyhow::Error::new(e).context(connect_hint.to_string())
+    }
+}
+
+/// Wait at most `timeout` for the next step of a request: the answer
+/// starting, or the next piece of it. A long reply that keeps arriving is
+/// never cut off.
+async fn within<T>(timeout: Duration, step: impl Future<Output = T>) -> Result<T> {
+    tokio::time::timeout(timeout, step).await.map_err(|elapsed| {
+        anyhow::Error::new(elapsed).context(format!("The model took longer than {}s to answer", timeout.as_secs()))
+    })
+}
+
+/// Feed each non-empty line of a streamed response body to `on_line`,
+/// giving up if the server goes quiet for `timeout`
+async fn for_each_line(
+    mut response: reqwest::Response,
+    timeout: Duration,
+    mut on_line: impl FnMut(&str) -> Result<()>,
+) -> Result<()> {
+    let mut pending = Vec::new();
+    while let Some(bytes) = within(timeout, response.chunk())
+        .await?
+        .map_err(|e| request_error(e, timeout, "The connection dropped mid-reply"))?
+    {
+        pending.extend_from_slice(&bytes);
+        while let Some(end) = pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = pending.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            if !line.trim().is_empty() {
+                on_line(line.trim())?;
+            }
+        }
+    }
+    // The last line may come without a newline
+    let rest = String::from_utf8_lossy(&pending);
+    if !rest.trim().is_empty() {
+        on_line(rest.trim())?;
+    }
+    Ok(())
+}
+
+/// Called with the agent's message so far while the reply streams in
+pub type MessageCallback = Box<dyn Fn(String) + Send>;
+
 /// Response from the agent with project modifications
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AgentResponse {
@@ -63,6 +447,50 @@ pub enum Modification {
         new_description: String,
     },
     
+    /// Cut a clip in two at a timeline time (the playhead when missing)
+    SplitClip {
+        #[serde(default)]
+        id: Option<String>,
+        /// Clip to cut (defaults to the one at that time)
+        #[serde(default)]
+        description: Option<String>,
+        #[serde(default)]
+        time_seconds: Option<f64>,
+    },
+    
+    /// Copy a clip (same file, trim and label) right after the original
+    DuplicateClip {
+        #[serde(default)]
+        id: Option<String>,
+        #[serde(default)]
+        description: Option<String>,
+        /// Description for the copy (defaults to the original's plus " (copy)")
+        #[serde(default)]
+        new_description: Option<String>,
+    },
+    
+    /// Color-label or tag clips (one by id/description, or several by `ids`)
+    LabelClip {
+        #[serde(default)]
+        id: Option<String>,
+        #[serde(default)]
+        description: Option<String>,
+        #[serde(default)]
+        ids: Vec<String>,
+        /// A color, any tag, or empty/"none" to clear it
+        #[serde(default)]
+        label: Option<String>,
+    },
+    
+    /// Change how long a clip plays (mainly for stills)
+    SetClipDuration {
+        #[serde(default)]
+        id: Option<String>,
+        #[serde(default)]
+        description: Option<String>,
+        seconds: f64,
+    },
+    
     /// Move a clip to a new position (1-indexed)
     MoveClip {
         /// Clip to move (by description)
@@ -81,17 +509,126 @@ pub enum Modification {
     
     /// Add a marker/note at a timestamp
     AddMarker {
-        description: String,
+        #[serde(alias = "description")]
+        label: String,
+        #[serde(default)]
+        time_seconds: Option<f64>,
+        #[serde(default)]
+        color: Option<String>,
+    },
+    
+    /// Remove a marker by id or label
+    RemoveMarker {
+        #[serde(default)]
+        id: Option<String>,
+        #[serde(default)]
+        label: Option<String>,
+    },
+    
+    /// Rename, move or recolor a marker (found by id or label)
+    UpdateMarker {
+        #[serde(default)]
+        id: Option<String>,
+        #[serde(default)]
+        label: Option<String>,
+        #[serde(default)]
+        new_label: Option<String>,
         #[serde(default)]
         time_seconds: Option<f64>,
+        #[serde(default)]
+        color: Option<String>,
     },
     
     /// Set project description
     SetDescription { description: String },
     
+    /// Set how long newly added images stay on screen
+    SetStillDuration { seconds: f64 },
+    
     /// Set Pexels API key
     SetPexelsKey { key: String },
     
+    /// Switch the Ollama model used by the agent
+    SetModel { model: String },
+    
+    /// Switch the color palette ("dark", "high contrast" or "light")
+    SetTheme { theme: String },
+    
+    /// Render the video, optionally in another format
+    Export {
+        /// "landscape", "vertical", "square" or "WIDTHxHEIGHT"
+        #[serde(default)]
+        preset: Option<String>,
+        /// "letterbox", "crop" or "stretch"
+        #[serde(default)]
+        aspect_mode: Option<String>,
+        /// "none", "sidecar" or "burned"
+        #[serde(default)]
+        subtitles: Option<String>,
+        /// "cut" or "crossfade", optionally with seconds ("crossfade 0.5")
+        #[serde(default)]
+        transition: Option<String>,
+        /// "software", "auto" (hardware if available), "nvenc", "vaapi" or "videotoolbox"
+        #[serde(default)]
+        encoder: Option<String>,
+        /// Only export from this time ("0:10" or seconds)
+        #[serde(default)]
+        from: Option<String>,
+        /// Only export up to this time
+        #[serde(default)]
+        to: Option<String>,
+        /// "720p", "1080p", "1440p" or "4k"
+        #[serde(default)]
+        resolution: Option<String>,
+        /// "low", "medium" or "high"
+        #[serde(default)]
+        quality: Option<String>,
+        /// "mp4", "webm" or "gif"
+        #[serde(default)]
+        format: Option<String>,
+    },
+    
+    /// Render only the audio (voiceover + audio clips) to MP3 or WAV
+    ExportAudio {
+        /// Output file; the user is asked when missing
+        #[serde(default)]
+        path: Option<String>,
+    },
+    
+    /// Write the cut as an EDL for another editor, without rendering
+    ExportEdl {
+        /// Output file; the user is asked when missing
+        #[serde(default)]
+        path: Option<String>,
+    },
+    
+    /// Save the frame in the preview as a PNG
+    SaveFrame {
+        /// Output file; the user is asked when missing
+        #[serde(default)]
+        path: Option<String>,
+    },
+    
+    /// Write the transcript as a subtitle file next to the project
+    ExportSubtitles {
+        /// "srt" (default) or "vtt"
+        #[serde(default)]
+        format: Option<String>,
+    },
+    
+    /// Write credits.txt for the downloaded stock footage
+    ExportCredits,
+    
+    /// List the cached transcript with timestamps
+    ShowTranscript,
+    
+    /// Transcribe the audio again, e.g. in another language
+    Transcribe {
+        /// Spoken language ("fr", "French"...); the configured one when missing
+        #[serde(default)]
+        language: Option<String>,
+    },
+    
     /// Generate video from audio (transcribe + fetch stock footage)
     GenerateFromAudio {
         /// Which audio clip to use (by description)
@@ -99,11 +636,31 @@ pub enum Modification {
         audio_clip: Option<String>,
     },
     
+    /// Cut stock footage on the beat of the music
+    BeatMontage {
+        /// What the footage should show
+        #[serde(default)]
+        query: Option<String>,
+    },
+    
+    /// Leave the voiceover's pauses out of the export
+    RemoveSilences {
+        /// Level in dB below which audio is silent (default -40)
+        #[serde(default)]
+        threshold_db: Option<f64>,
+        /// Shortest pause to cut, in seconds (default 1)
+        #[serde(default)]
+        min_seconds: Option<f64>,
+    },
+    
     /// Search and add stock footage from Pexels
     SearchPexels {
         query: String,
         #[serde(default)]
         count: Option<u32>,
+        /// "videos" (default) or "photos"
+        #[serde(default)]
+        media: Option<String>,
     },
 }
 
@@ -123,7 +680,8 @@ You receive the current project state as JSON and user commands. You respond wit
     { "type": "update_clip", "old_description": "clip1", "new_description": "opening shot" },
     { "type": "move_clip", "description": "intro", "position": "last" },
     { "type": "swap_clips", "clip1": "intro", "clip2": "outro" },
-    { "type": "add_marker", "description": "cut here", "time_seconds": 30.5 },
+    { "type": "add_marker", "label": "cut here", "time_seconds": 30.5 },
+    { "type": "update_marker", "label": "cut here", "new_label": "cut", "time_seconds": 31.0 },
     { "type": "set_description", "description": "My vacation video" }
   ]
 }
@@ -133,73 +691,413 @@ You receive the current project state as JSON and user commands. You respond wit
 - add_clip: Add a new clip (user will attach the file)
 - remove_clip: Remove a clip by id or description
 - update_clip: Change a clip's description
+- split_clip: Cut a clip in two at a time on the timeline, e.g. "split the clip at 1:05" {"type": "split_clip", "time_seconds": 65} (omit time_seconds for the playhead; add "description" to pick the clip)
+- duplicate_clip: Copy a clip right after itself, e.g. for a cutaway reusing the same file {"type": "duplicate_clip", "description": "beach", "new_description": "beach cutaway"} (new_description optional)
+- label_clip: Give clips a color label (red, yellow, green, blue, purple) or any tag, by id or description, or several at once with "ids"; Pexels stock clips have "pexels" in their path. E.g. "mark all pexels clips yellow" {"type": "label_clip", "ids": ["clip_1", "clip_2"], "label": "yellow"}; "label": "none" clears it
+- set_clip_duration: Set how many seconds a clip (usually an image) stays on screen {"type": "set_clip_duration", "description": "title card", "seconds": 3}
 - move_clip: Move a clip to a new position ("first", "last", or a number like "2")
 - swap_clips: Swap the positions of two clips
-- add_marker: Add a timestamp marker/note
+- add_marker: Add a timestamp marker/note (omit time_seconds to use the playhead, optional "color": "#rrggbb")
+- remove_marker: Remove a marker by id or label {"type": "remove_marker", "label": "cut here"}
+- update_marker: Change a marker's label (new_label), time_seconds or color, found by id or label
 - set_description: Set project description
+- set_still_duration: How long images added from now on stay on screen {"type": "set_still_duration", "seconds": 3}
 - set_pexels_key: Set Pexels API key for stock footage {"type": "set_pexels_key", "key": "..."}
+- set_model: Switch the AI model, e.g. "use model llama3.1:8b" {"type": "set_model", "model": "llama3.1:8b"}
+- set_theme: Switch the app's colors, e.g. "use high contrast theme" {"type": "set_theme", "theme": "high_contrast"} (dark, high_contrast or light)
+- export: Export the video, e.g. "export vertical for tiktok" {"type": "export", "preset": "vertical", "aspect_mode": "crop"} (preset: landscape, vertical, square or WIDTHxHEIGHT; aspect_mode: letterbox, crop or stretch; subtitles: none, sidecar or burned; transition: cut or "crossfade 0.5" in seconds; encoder: software or hardware; from/to: "0:10" and "0:40" to export only part of the timeline; resolution: 720p, 1080p, 1440p or 4k; quality: low, medium or high, e.g. "export in 4k high quality" {"type": "export", "resolution": "4k", "quality": "high"}; format: mp4, webm or gif (no sound); all optional)
+- export_audio: Export only the audio as MP3 or WAV, e.g. "export audio to ~/out.mp3" {"type": "export_audio", "path": "~/out.mp3"}
+- export_edl: Write the cut as a CMX3600 EDL for DaVinci Resolve or Premiere, e.g. "export an EDL" {"type": "export_edl", "path": "~/cut.edl"} (path optional)
+- save_frame: Save the frame in the preview as a PNG, e.g. "save current frame to ~/thumb.png" {"type": "save_frame", "path": "~/thumb.png"} (path optional)
+- export_subtitles: Write subtitles from the transcript, e.g. "export subtitles" {"type": "export_subtitles", "format": "srt"}
+- export_credits: Write credits.txt naming the authors of downloaded stock clips, e.g. "export credits" {"type": "export_credits"}
+- show_transcript: Show the transcript with timestamps, e.g. "what does the voiceover say?" {"type": "show_transcript"}
+- transcribe: Transcribe the audio afresh, e.g. "transcribe in French" {"type": "transcribe", "language": "fr"}
 - generate_from_audio: Transcribe audio and auto-fetch matching stock footage {"type": "generate_from_audio"}
-- search_pexels: Search Pexels for stock footage {"type": "search_pexels", "query": "sunset beach", "count": 5}
+- beat_montage: Cut stock footage to the beat of the music, e.g. "make a beat-synced montage of city nights" {"type": "beat_montage", "query": "city night"}
+- remove_silences: Cut dead air from the voiceover on export, e.g. "remove silences longer than 2 seconds" {"type": "remove_silences", "min_seconds": 2} (optional "threshold_db", default -40)
+- search_pexels: Search Pexels for stock footage {"type": "search_pexels", "query": "sunset beach", "count": 5} (add "media": "photos" for still images)
 
 ## Rules
 - Be helpful and conversational in your message
 - Only include modifications that the user actually requested
 - If the user just asks a question, respond with empty modifications: []
 - If adding a clip, just set the description - the user will attach the file
+- To refer to a clip by its position, use "clip 3" as its description, e.g. "remove clip 3" {"type": "remove_clip", "description": "clip 3"}
 - Keep messages concise
+- Follow-ups like "actually make that the intro" refer to the conversation so far
 - Clips are ordered in the sequence they will appear in the final video
-- For generate_from_audio, there must be an audio clip in the project
+- For generate_from_audio and beat_montage, there must be an audio clip in the project
 - For Pexels features, the API key must be set first
+- "Search photos/pictures/images for X" means search_pexels with "media": "photos"; title cards and backdrops suit photos too
+- TikTok, Reels and Shorts are vertical (9:16); Instagram posts are square (1:1)
 
 Return ONLY valid JSON, no other text."#;
 
-/// Process a user command with project context (blocking - runs in thread)
-pub fn process_command_blocking(project: &Project, user_input: &str, has_attachments: bool) -> Result<AgentResponse> {
-    // Serialize project to give context
-    let project_json = serde_json::to_string_pretty(project)
-        .context("Failed to serialize project")?;
+/// Budget for earlier exchanges in the prompt, so small models keep room
+/// for the project and the reply (roughly 1500 tokens)
+const MAX_HISTORY_CHARS: usize = 6000;
+
+/// The most recent turns that fit in `MAX_HISTORY_CHARS`, as a prompt section
+fn format_history(history: &[ConversationTurn]) -> String {
+    let mut budget = MAX_HISTORY_CHARS;
+    let mut lines = Vec::new();
+    for turn in history.iter().rev() {
+        let line = format!("User: {}\nAssistant: {}\n", turn.user, turn.agent);
+        let Some(rest) = budget.checked_sub(line.len()) else {
+            break;
+        };
+        budget = rest;
+        lines.push(line);
+    }
+    if lines.is_empty() {
+        return String::new();
+    }
+    lines.reverse();
+    format!("## Conversation So Far\n{}\n", lines.concat())
+}
+
+/// Tokio runtime driving the async HTTP client (GPUI's executors don't
+/// provide a reactor); requests run on it while GPUI awaits them
+pub struct AgentRuntime {
+    runtime: tokio::runtime::Runtime,
+}
+
+impl AgentRuntime {
+    pub fn new() -> Result<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("montage-agent")
+            .enable_all()
+            .build()
+            .context("Failed to start the agent runtime")?;
+        Ok(Self { runtime })
+    }
+}
+
+/// An in-flight agent request; dropping it aborts the HTTP call
+pub struct AgentRequest {
+    handle: tokio::task::JoinHandle<Result<AgentResponse>>,
+}
+
+impl AgentRequest {
+    /// Send a user command with project context and the earlier
+    /// conversation to the model without blocking; `on_message` sees the
+    /// reply's message as it is generated
+    pub fn spawn(
+        project: &Project,
+        history: &[ConversationTurn],
+        user_input: &str,
+        has_attachments: bool,
+        backend: Arc<dyn AgentBackend>,
+        on_message: MessageCallback,
+        runtime: &AgentRuntime,
+    ) -> Result<Self> {
+        // Serialize project to give context; the conversation goes in its own section
+        let mut context = project.clone();
+        context.conversation.clear();
+        let project_json = serde_json::to_string_pretty(&context)
+            .context("Failed to serialize project")?;
+        
+        let attachment_note = if has_attachments {
+            "\n\n[User has attached file(s) to this message]"
+        } else {
+            ""
+        };
+        
+        let prompt = format!(
+            "## Current Project State\n```json\n{}\n```\n\n{}## User Command\n{}{}\n\n## Your Response (JSON only)",
+            project_json, format_history(history), user_input, attachment_note
+        );
+        
+        let handle = runtime.runtime.spawn(send_command(backend, prompt, user_input.to_string(), on_message));
+        Ok(Self { handle })
+    }
+}
+
+impl Future for AgentRequest {
+    type Output = Result<AgentResponse>;
     
-    let attachment_note = if has_attachments {
-        "\n\n[User has attached file(s) to this message]"
-    } else {
-        ""
+    fn poll(mut self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.handle).poll(cx).map(|joined| {
+            joined.unwrap_or_else(|e| Err(anyhow::anyhow!("Agent request failed: {}", e)))
+        })
+    }
+}
+
+impl Drop for AgentRequest {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+async fn send_command(
+    backend: Arc<dyn AgentBackend>,
+    prompt: String,
+    user_input: String,
+    on_message: MessageCallback,
+) -> Result<AgentResponse> {
+    let mut stream = MessageStream { on_message, so_far: String::new(), shown: String::new() };
+    let raw = match backend.complete(SYSTEM_PROMPT, &prompt, &mut |text| stream.push(text)).await {
+        Ok(raw) => raw,
+        // No server to talk to: basic edits still work without a model
+        Err(e) if is_unreachable(&e) => {
+            tracing::warn!("{} is unreachable, using the offline parser: {:#}", backend.kind().label(), e);
+            return offline::parse(&user_input).ok_or_else(|| {
+                e.context("Only simple edits work offline (rename, add, remove, move or swap clips, set the Pexels key)")
+            });
+        }
+        Err(e) => return Err(e),
+    };
+    
+    tracing::debug!("Agent raw response: {}", raw);
+    
+    let error = match parse_reply(&raw) {
+        Ok(response) => return Ok(response),
+        Err(e) => e,
     };
     
-    let prompt = format!(
-        "{}\n\n## Current Project State\n```json\n{}\n```\n\n## User Command\n{}{}\n\n## Your Response (JSON only)",
-        SYSTEM_PROMPT, project_json, user_input, attachment_note
-    );
+    // Small models often manage on a second try once reminded
+    tracing::warn!("Agent reply is not valid JSON; asking again: {}", error);
+    stream.so_far.clear();
+    let prompt = format!("{}{}", prompt, JSON_REMINDER);
+    let raw = backend.complete(SYSTEM_PROMPT, &prompt, &mut |text| stream.push(text)).await?;
+    
+    tracing::debug!("Agent raw response (retry): {}", raw);
+    
+    parse_reply(&raw)
+}
+
+/// Whether the request failed because nothing answered at the backend's address
+fn is_unreachable(error: &anyhow::Error) -> bool {
+    error
+        .chain()
+        .any(|cause| cause.downcast_ref::<reqwest::Error>().is_some_and(reqwest::Error::is_connect))
+}
+
+/// Whether the request gave up waiting for the backend
+fn is_timeout(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        cause.is::<tokio::time::error::Elapsed>()
+            || cause.downcast_ref::<reqwest::Error>().is_some_and(reqwest::Error::is_timeout)
+    })
+}
 
-    let request = OllamaRequest {
-        model: MODEL.to_string(),
-        prompt,
-        stream: false,
-        format: "json".to_string(),
+/// Added to the prompt when the first reply couldn't be read
+const JSON_REMINDER: &str = "\n\nYour previous reply could not be parsed. Answer with exactly one JSON object \
+    in the response format above: no code fences, no text before or after it.";
+
+/// Forwards a streaming reply's "message" to the UI as it grows
+struct MessageStream {
+    on_message: MessageCallback,
+    so_far: String,
+    shown: String,
+}
+
+impl MessageStream {
+    fn push(&mut self, text: &str) {
+        self.so_far.push_str(text);
+        if let Some(message) = partial_message(&self.so_far)
+            && message != self.shown
+        {
+            (self.on_message)(message.clone());
+            self.shown = message;
+        }
+    }
+}
+
+/// Read a reply as JSON, looking past code fences and chatter around the
+/// object when the model added them; the error quotes what the model said
+fn parse_reply(raw: &str) -> Result<AgentResponse> {
+    let error = match serde_json::from_str(raw.trim()) {
+        Ok(response) => return Ok(response),
+        Err(e) => e,
+    };
+    let parsed = match json_object(raw) {
+        Some(object) => serde_json::from_str(object),
+        None => Err(error),
     };
+    parsed.map_err(|e| anyhow::anyhow!("The model's reply wasn't valid JSON ({}). It said:\n{}", e, raw.trim()))
+}
 
-    // Use blocking client to avoid Tokio runtime conflict with GPUI
-    let client = reqwest::blocking::Client::new();
-    let response = client
-        .post(OLLAMA_URL)
-        .json(&request)
-        .timeout(std::time::Duration::from_secs(60))
-        .send()
-        .context("Failed to connect to Ollama. Is it running? (ollama serve)")?;
+/// The first balanced `{...}` in `text`, skipping braces inside strings
+fn json_object(text: &str) -> Option<&str> {
+    let start = text.find('{')?;
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in text[start..].char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&text[start..start + i + 1]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
 
-    if !response.status().is_success() {
-        anyhow::bail!("Ollama returned error: {}", response.status());
+/// The "message" value of a reply that may still be arriving, decoded as
+/// far as it goes
+fn partial_message(raw: &str) -> Option<String> {
+    let key = raw.find("\"message\"")?;
+    let rest = raw[key + "\"message\"".len()..]
+        .trim_start()
+        .strip_prefix(':')?
+        .trim_start()
+        .strip_prefix('"')?;
+    
+    let mut message = String::new();
+    let mut chars = rest.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => break,
+            '\\' => match chars.next() {
+                Some('n') => message.push('\n'),
+                Some('r') => message.push('\r'),
+                Some('t') => message.push('\t'),
+                Some('u') => match unicode_escape(&mut chars) {
+                    Some(c) => message.push(c),
+                    // Cut off mid-escape; wait for more
+                    None => break,
+                },
+                // \" \\ \/
+                Some(other) => message.push(other),
+                None => break,
+            },
+            c => message.push(c),
+        }
     }
+    Some(message)
+}
 
-    let ollama_response: OllamaResponse = response
-        .json()
-        .context("Failed to parse Ollama response")?;
+/// Decode what follows a `\u`, joining a UTF-16 surrogate pair (which is
+/// how emoji arrive) into one char; `None` while the escape is cut off
+fn unicode_escape(chars: &mut std::str::Chars) -> Option<char> {
+    let code = hex_code(chars)?;
+    if !(0xD800..0xDC00).contains(&code) {
+        return Some(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+    }
+    // A high surrogate takes its low half from the escape right after it
+    let mut ahead = chars.clone();
+    if ahead.next()? != '\\' || ahead.next()? != 'u' {
+        return Some(char::REPLACEMENT_CHARACTER);
+    }
+    let low = hex_code(&mut ahead)?;
+    if !(0xDC00..0xE000).contains(&low) {
+        return Some(char::REPLACEMENT_CHARACTER);
+    }
+    *chars = ahead;
+    char::from_u32(0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00))
+}
 
-    tracing::debug!("Ollama raw response: {}", ollama_response.response);
+/// The next four hex digits as a number
+fn hex_code(chars: &mut std::str::Chars) -> Option<u32> {
+    let digits: String = chars.by_ref().take(4).collect();
+    if digits.len() < 4 {
+        return None;
+    }
+    u32::from_str_radix(&digits, 16).ok()
+}
 
-    let agent_response: AgentResponse = serde_json::from_str(&ollama_response.response)
-        .context("Failed to parse agent response JSON")?;
+/// Look up the clip a modification refers to. When there's no clear
+/// answer, says why in `results` so the user can be more specific.
+fn resolve_clip(
+    project: &Project,
+    id: Option<&str>,
+    description: Option<&str>,
+    results: &mut Vec<String>,
+) -> Option<usize> {
+    let wanted = id.or(description).unwrap_or_default();
+    match project.find_clip(id, description) {
+        ClipMatch::Found(index) => Some(index),
+        ClipMatch::Ambiguous(indices) => {
+            let names: Vec<String> = indices
+                .iter()
+                .map(|&i| format!("'{}'", project.clips[i].description))
+                .collect();
+            results.push(format!(
+                "⚠ {} clips match '{}': {}. Which one?",
+                names.len(),
+                wanted,
+                names.join(", ")
+            ));
+            None
+        }
+        ClipMatch::NotFound => {
+            results.push(format!("⚠ No clip matches '{}'", wanted));
+            None
+        }
+    }
+}
+
+impl Modification {
+    /// Whether a wrong guess at the clip would lose work: removals, splits,
+    /// and renames of a clip the model named only loosely
+    pub fn is_destructive(&self, project: &Project) -> bool {
+        match self {
+            Self::RemoveClip { .. } | Self::SplitClip { .. } => true,
+            Self::UpdateClip { id: None, old_description: Some(old), .. } => {
+                !project.clips.iter().any(|c| c.description.eq_ignore_ascii_case(old.trim()))
+            }
+            _ => false,
+        }
+    }
+}
 
-    Ok(agent_response)
+/// What held-back modifications would do, e.g. "remove 2 clips ('beach',
+/// 'city'), split 'intro' at 3.0s"
+pub fn describe_destructive(project: &Project, modifications: &[Modification]) -> String {
+    let name = |id: &Option<String>, description: &Option<String>| match project.find_clip(id.as_deref(), description.as_deref()) {
+        ClipMatch::Found(index) => format!("'{}'", project.clips[index].description),
+        _ => format!("'{}'", id.as_deref().or(description.as_deref()).unwrap_or_default()),
+    };
+    
+    let mut removals = Vec::new();
+    let mut changes = Vec::new();
+    for modification in modifications {
+        match modification {
+            Modification::RemoveClip { id, description } => removals.push(name(id, description)),
+            Modification::SplitClip { id, description, time_seconds } => {
+                let time = time_seconds.unwrap_or(project.timeline.position);
+                let clip = if id.is_some() || description.is_some() {
+                    name(id, description)
+                } else {
+                    project
+                        .clip_at(time)
+                        .map_or("the clip".to_string(), |i| format!("'{}'", project.clips[i].description))
+                };
+                changes.push(format!("split {} at {:.1}s", clip, time));
+            }
+            Modification::UpdateClip { id, old_description, new_description } => {
+                changes.push(format!("rename {} to '{}'", name(id, old_description), new_description));
+            }
+            _ => {}
+        }
+    }
+    
+    let mut parts = Vec::new();
+    match removals.as_slice() {
+        [] => {}
+        [clip] => parts.push(format!("remove {}", clip)),
+        clips => parts.push(format!("remove {} clips ({})", clips.len(), clips.join(", "))),
+    }
+    parts.extend(changes);
+    parts.join(", ")
 }
 
 /// Apply modifications to a project
@@ -226,90 +1124,141 @@ pub fn apply_modifications(project: &mut Project, modifications: &[Modification]
             }
             
             Modification::RemoveClip { id, description } => {
-                let initial_len = project.clips.len();
-                
-                if let Some(clip_id) = id {
-                    project.clips.retain(|c| c.id != *clip_id);
-                } else if let Some(desc) = description {
-                    let desc_lower = desc.to_lowercase();
-                    project.clips.retain(|c| !c.description.to_lowercase().contains(&desc_lower));
+                if let Some(index) = resolve_clip(project, id.as_deref(), description.as_deref(), &mut results) {
+                    let clip = project.remove_clip(index);
+                    results.push(format!("✓ Removed clip: {}", clip.description));
                 }
-                
-                let removed = initial_len - project.clips.len();
-                if removed > 0 {
-                    results.push(format!("✓ Removed {} clip(s)", removed));
+            }
+            
+            Modification::UpdateClip { id, old_description, new_description } => {
+                if let Some(index) = resolve_clip(project, id.as_deref(), old_description.as_deref(), &mut results) {
+                    project.clips[index].description = new_description.clone();
+                    results.push(format!("✓ Updated clip to: {}", new_description));
+                }
+            }
+            
+            Modification::SplitClip { id, description, time_seconds } => {
+                let time = time_seconds.unwrap_or(project.timeline.position);
+                let index = if id.is_some() || description.is_some() {
+                    resolve_clip(project, id.as_deref(), description.as_deref(), &mut results)
                 } else {
-                    results.push("⚠ No matching clips found to remove".to_string());
+                    let index = project.clip_at(time);
+                    if index.is_none() {
+                        results.push(format!("⚠ No clip at {:.1}s", time));
+                    }
+                    index
+                };
+                if let Some(index) = index {
+                    match project.split_clip(index, time) {
+                        Ok(_) => results.push(format!("✂ Split '{}' at {:.1}s", project.clips[index].description, time)),
+                        Err(e) => results.push(format!("⚠ Can't split: {}", e)),
+                    }
                 }
             }
             
-            Modification::UpdateClip { id, old_description, new_description } => {
-                let mut updated = false;
-                
-                for clip in &mut project.clips {
-                    let matches = id.as_ref().is_some_and(|i| clip.id == *i)
-                        || old_description.as_ref().is_some_and(|d| 
-                            clip.description.to_lowercase().contains(&d.to_lowercase())
-                        );
-                    
-                    if matches {
-                        clip.description = new_description.clone();
-                        updated = true;
-                        break;
+            Modification::DuplicateClip { id, description, new_description } => {
+                if let Some(index) = resolve_clip(project, id.as_deref(), description.as_deref(), &mut results) {
+                    project.duplicate_clip(index);
+                    let copy = &mut project.clips[index + 1];
+                    if let Some(new_description) = new_description {
+                        copy.description = new_description.clone();
                     }
+                    results.push(format!("✓ Duplicated clip as '{}' (position {})", copy.description, index + 2));
                 }
-                
-                if updated {
-                    results.push(format!("✓ Updated clip to: {}", new_description));
+            }
+            
+            Modification::LabelClip { id, description, ids, label } => {
+                let label = label
+                    .as_deref()
+                    .filter(|l| !l.trim().eq_ignore_ascii_case("none"))
+                    .and_then(ClipLabel::parse);
+                let indices: Vec<usize> = if ids.is_empty() {
+                    resolve_clip(project, id.as_deref(), description.as_deref(), &mut results)
+                        .into_iter()
+                        .collect()
                 } else {
-                    results.push("⚠ No matching clip found to update".to_string());
+                    ids.iter()
+                        .filter_map(|id| resolve_clip(project, Some(id), None, &mut results))
+                        .collect()
+                };
+                for &index in &indices {
+                    project.clips[index].label = label.clone();
+                }
+                match (&label, indices.as_slice()) {
+                    (_, []) => {}
+                    (Some(label), [index]) => {
+                        results.push(format!("🏷 Labeled '{}' {}", project.clips[*index].description, label.name()));
+                    }
+                    (Some(label), _) => results.push(format!("🏷 Labeled {} clips {}", indices.len(), label.name())),
+                    (None, _) => results.push(format!("✓ Cleared the label on {} clip(s)", indices.len())),
+                }
+            }
+            
+            Modification::SetClipDuration { id, description, seconds } => {
+                if *seconds <= 0.0 {
+                    results.push(format!("⚠ Duration must be positive, got {}s", seconds));
+                } else if let Some(index) = resolve_clip(project, id.as_deref(), description.as_deref(), &mut results) {
+                    project.clips[index].duration = Some(*seconds);
+                    project.update_start_times();
+                    results.push(format!("✓ '{}' now lasts {}s", project.clips[index].description, seconds));
                 }
             }
             
             Modification::MoveClip { description, position } => {
-                let desc_lower = description.to_lowercase();
-                if let Some(idx) = project.clips.iter().position(|c| 
-                    c.description.to_lowercase().contains(&desc_lower)
-                ) {
-                    let clip = project.clips.remove(idx);
+                if let Some(idx) = resolve_clip(project, None, Some(description), &mut results) {
+                    // Positions count the other clips, as if this one were already taken out
+                    let others = project.clips.len() - 1;
                     let new_pos = match position.to_lowercase().as_str() {
                         "first" | "1" | "start" | "beginning" => 0,
-                        "last" | "end" => project.clips.len(),
-                        s => s.parse::<usize>().unwrap_or(project.clips.len()).saturating_sub(1),
+                        "last" | "end" => others,
+                        s => s.parse::<usize>().unwrap_or(others).saturating_sub(1),
                     };
-                    let new_pos = new_pos.min(project.clips.len());
-                    project.clips.insert(new_pos, clip);
-                    results.push(format!("✓ Moved '{}' to position {}", description, new_pos + 1));
-                } else {
-                    results.push(format!("⚠ Clip '{}' not found", description));
+                    let new_pos = new_pos.min(others);
+                    project.move_clip(idx, new_pos);
+                    results.push(format!("✓ Moved '{}' to position {}", project.clips[new_pos].description, new_pos + 1));
                 }
             }
             
             Modification::SwapClips { clip1, clip2 } => {
-                let clip1_lower = clip1.to_lowercase();
-                let clip2_lower = clip2.to_lowercase();
-                
-                let idx1 = project.clips.iter().position(|c| 
-                    c.description.to_lowercase().contains(&clip1_lower)
-                );
-                let idx2 = project.clips.iter().position(|c| 
-                    c.description.to_lowercase().contains(&clip2_lower)
-                );
+                let idx1 = resolve_clip(project, None, Some(clip1), &mut results);
+                let idx2 = resolve_clip(project, None, Some(clip2), &mut results);
                 
                 if let (Some(i1), Some(i2)) = (idx1, idx2) {
-                    project.clips.swap(i1, i2);
-                    results.push(format!("✓ Swapped '{}' and '{}'", clip1, clip2));
-                } else {
-                    results.push("⚠ Could not find both clips to swap".to_string());
+                    project.swap_clips(i1, i2);
+                    results.push(format!("✓ Swapped '{}' and '{}'", project.clips[i2].description, project.clips[i1].description));
                 }
             }
             
-            Modification::AddMarker { description, time_seconds } => {
-                // TODO: Add proper marker support to project
-                let time_str = time_seconds
-                    .map(|t| format!(" at {:.1}s", t))
-                    .unwrap_or_default();
-                results.push(format!("📍 Marker{}: {}", time_str, description));
+            Modification::AddMarker { label, time_seconds, color } => {
+                // Default to the playhead when the user didn't give a time
+                let time = time_seconds.unwrap_or(project.timeline.position);
+                let marker = project.add_marker(label.clone(), time, color.clone());
+                results.push(format!("📍 Marker at {:.1}s: {}", marker.time_seconds, marker.label));
+            }
+            
+            Modification::RemoveMarker { id, label } => {
+                match project.find_marker(id.as_deref(), label.as_deref()) {
+                    Some(index) => {
+                        let marker = project.remove_marker(index);
+                        results.push(format!("✓ Removed marker '{}'", marker.label));
+                    }
+                    None => results.push("⚠ No matching marker found to remove".to_string()),
+                }
+            }
+            
+            Modification::UpdateMarker { id, label, new_label, time_seconds, color } => {
+                match project.find_marker(id.as_deref(), label.as_deref()) {
+                    Some(index) => {
+                        let marker = project.update_marker(
+                            index,
+                            new_label.clone(),
+                            *time_seconds,
+                            color.clone(),
+                        );
+                        results.push(format!("✓ Marker '{}' at {:.1}s", marker.label, marker.time_seconds));
+                    }
+                    None => results.push("⚠ No matching marker found to update".to_string()),
+                }
             }
             
             Modification::SetDescription { description } => {
@@ -317,22 +1266,217 @@ pub fn apply_modifications(project: &mut Project, modifications: &[Modification]
                 results.push("✓ Project description updated".to_string());
             }
             
+            Modification::SetStillDuration { seconds } => {
+                if *seconds <= 0.0 {
+                    results.push(format!("⚠ Duration must be positive, got {}s", seconds));
+                } else {
+                    project.metadata.still_duration = Some(*seconds);
+                    results.push(format!("✓ New images will last {}s", seconds));
+                }
+            }
+            
             // These are handled by the UI, not here
             Modification::SetPexelsKey { key } => {
                 results.push(format!("🔑 PEXELS_KEY:{}", key));
             }
             
+            Modification::SetModel { model } => {
+                results.push(format!("🧠 SET_MODEL:{}", model));
+            }
+            
+            Modification::SetTheme { theme } => {
+                results.push(format!("🎨 THEME:{}", theme));
+            }
+            
+            Modification::Export { preset, aspect_mode, subtitles, transition, encoder, from, to, resolution, quality, format } => {
+                // '|' separated: presets like "9:16" contain colons
+                results.push(format!(
+                    "📤 EXPORT:{}|{}|{}|{}|{}|{}|{}|{}|{}|{}",
+                    preset.as_deref().unwrap_or(""),
+                    aspect_mode.as_deref().unwrap_or(""),
+                    subtitles.as_deref().unwrap_or(""),
+                    transition.as_deref().unwrap_or(""),
+                    encoder.as_deref().unwrap_or(""),
+                    from.as_deref().unwrap_or(""),
+                    to.as_deref().unwrap_or(""),
+                    resolution.as_deref().unwrap_or(""),
+                    quality.as_deref().unwrap_or(""),
+                    format.as_deref().unwrap_or("")
+                ));
+            }
+            
+            Modification::ExportAudio { path } => {
+                results.push(format!("🔊 EXPORT_AUDIO:{}", path.as_deref().unwrap_or("")));
+            }
+            
+            Modification::ExportEdl { path } => {
+                results.push(format!("🎞 EXPORT_EDL:{}", path.as_deref().unwrap_or("")));
+            }
+            
+            Modification::SaveFrame { path } => {
+                results.push(format!("🖼 SAVE_FRAME:{}", path.as_deref().unwrap_or("")));
+            }
+            
+            Modification::ExportSubtitles { format } => {
+                results.push(format!(
+                    "💬 EXPORT_SUBTITLES:{}",
+                    format.as_deref().unwrap_or("srt")
+                ));
+            }
+            
+            Modification::ExportCredits => {
+                results.push("📜 EXPORT_CREDITS".to_string());
+            }
+            
+            Modification::ShowTranscript => {
+                results.push("📝 SHOW_TRANSCRIPT".to_string());
+            }
+            
+            Modification::Transcribe { language } => {
+                results.push(format!("📝 TRANSCRIBE:{}", language.as_deref().unwrap_or("")));
+            }
+            
             Modification::GenerateFromAudio { audio_clip } => {
                 let clip_info = audio_clip.as_deref().unwrap_or("default");
                 results.push(format!("🎬 GENERATE_FROM_AUDIO:{}", clip_info));
             }
             
-            Modification::SearchPexels { query, count } => {
+            Modification::BeatMontage { query } => {
+                let query = query.as_deref().filter(|q| !q.trim().is_empty()).unwrap_or("abstract background");
+                results.push(format!("🥁 BEAT_MONTAGE:{}", query));
+            }
+            
+            Modification::RemoveSilences { threshold_db, min_seconds } => {
+                results.push(format!(
+                    "✂ CUT_SILENCES:{}|{}",
+                    threshold_db.map(|db| db.to_string()).unwrap_or_default(),
+                    min_seconds.map(|s| s.to_string()).unwrap_or_default()
+                ));
+            }
+            
+            Modification::SearchPexels { query, count, media } => {
                 let n = count.unwrap_or(5);
-                results.push(format!("🔍 SEARCH_PEXELS:{}:{}", query, n));
+                let media = media.as_deref().unwrap_or("videos");
+                results.push(format!("🔍 SEARCH_PEXELS:{}:{}:{}", query, n, media));
             }
         }
     }
     
     results
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    
+    fn project(names: &[&str]) -> Project {
+        let mut project = Project::new("Matching");
+        for name in names {
+            project.add_clip(name.to_string(), PathBuf::from(format!("{}.mp4", name)));
+        }
+        project
+    }
+    
+    fn descriptions(project: &Project) -> Vec<&str> {
+        project.clips.iter().map(|c| c.description.as_str()).collect()
+    }
+    
+    fn remove(description: &str) -> Modification {
+        Modification::RemoveClip { id: None, description: Some(description.to_string()) }
+    }
+    
+    #[test]
+    fn test_remove_prefers_exact_and_whole_words() {
+        let mut project = project(&["intro", "introduction to the park", "outro"]);
+        apply_modifications(&mut project, &[remove("intro")]);
+        assert_eq!(descriptions(&project), ["introduction to the park", "outro"]);
+        
+        let mut project = project(&["intro music", "introduction to the park"]);
+        apply_modifications(&mut project, &[remove("Intro")]);
+        assert_eq!(descriptions(&project), ["introduction to the park"]);
+    }
+    
+    #[test]
+    fn test_ambiguous_match_changes_nothing() {
+        let mut project = project(&["drone shot 1", "drone shot 2", "beach"]);
+        let results = apply_modifications(&mut project, &[
+            remove("drone shot"),
+            Modification::UpdateClip {
+                id: None,
+                old_description: Some("drone".to_string()),
+                new_description: "aerial".to_string(),
+            },
+            Modification::MoveClip { description: "drone".to_string(), position: "last".to_string() },
+            Modification::SwapClips { clip1: "drone".to_string(), clip2: "beach".to_string() },
+        ]);
+        
+        assert_eq!(descriptions(&project), ["drone shot 1", "drone shot 2", "beach"]);
+        assert_eq!(results[0], "⚠ 2 clips match 'drone shot': 'drone shot 1', 'drone shot 2'. Which one?");
+        assert_eq!(results.len(), 4);
+    }
+    
+    #[test]
+    fn test_clips_picked_by_position() {
+        let mut project = project(&["a", "b", "c", "d"]);
+        let results = apply_modifications(&mut project, &[
+            remove("clip 3"),
+            Modification::MoveClip { description: "#1".to_string(), position: "last".to_string() },
+            Modification::SwapClips { clip1: "clip 1".to_string(), clip2: "clip 2".to_string() },
+            remove("clip 9"),
+        ]);
+        
+        assert_eq!(descriptions(&project), ["d", "b", "a"]);
+        assert_eq!(results[1], "✓ Moved 'a' to position 3");
+        assert_eq!(results[2], "✓ Swapped 'b' and 'd'");
+        assert_eq!(results[3], "⚠ No clip matches 'clip 9'");
+    }
+    
+    #[test]
+    fn test_reply_found_inside_fences_and_chatter() {
+        let fenced = "```json\n{\"message\": \"Done\", \"modifications\": []}\n```";
+        assert_eq!(parse_reply(fenced).unwrap().message, "Done");
+        
+        let chatty = "Sure! Here it is:\n{\"message\": \"Renamed\", \"modifications\": [{\"type\": \"set_name\", \"name\": \"Trip\"}]}\nAnything else?";
+        let reply = parse_reply(chatty).unwrap();
+        assert_eq!(reply.message, "Renamed");
+        assert_eq!(reply.modifications.len(), 1);
+    }
+    
+    #[test]
+    fn test_braces_inside_strings_are_skipped() {
+        let raw = r#"Ok: {"message": "Use {curly} and \"}\" braces"} trailing }"#;
+        assert_eq!(json_object(raw), Some(r#"{"message": "Use {curly} and \"}\" braces"}"#));
+        assert_eq!(parse_reply(raw).unwrap().message, "Use {curly} and \"}\" braces");
+    }
+    
+    #[test]
+    fn test_broken_reply_error_quotes_it() {
+        for raw in ["{\"message\": \"Half a rep", "Here: {\"message\": \"Hi\"", "no json at all"] {
+            assert_eq!(json_object(raw), None);
+            let error = parse_reply(raw).unwrap_err().to_string();
+            assert!(error.contains(raw), "{}", error);
+        }
+    }
+    
+    #[test]
+    fn test_partial_message_grows_with_the_reply() {
+        assert_eq!(partial_message(r#"{"mess"#), None);
+        assert_eq!(partial_message(r#"{"modifications": [], "message": "#), None);
+        assert_eq!(partial_message(r#"{"message": "Adding cl"#).as_deref(), Some("Adding cl"));
+        assert_eq!(
+            partial_message(r#"{"message" : "Say \"hi\"\nthen! go", "modif"#).as_deref(),
+            Some("Say \"hi\"\nthen! go")
+        );
+    }
+    
+    #[test]
+    fn test_partial_message_joins_surrogate_pairs() {
+        assert_eq!(partial_message(r#"{"message": "\u2705 Done \ud83c\udfac!"#).as_deref(), Some("✅ Done 🎬!"));
+        // Half a pair waits for the rest
+        assert_eq!(partial_message(r#"{"message": "Done \ud83c"#).as_deref(), Some("Done "));
+        assert_eq!(partial_message(r#"{"message": "Done \ud83c\udf"#).as_deref(), Some("Done "));
+        // A lone half can't be shown, but doesn't stop the rest
+        assert_eq!(partial_message(r#"{"message": "a\ud83cb\udc00c"#).as_deref(), Some("a\u{FFFD}b\u{FFFD}c"));
+    }
+}
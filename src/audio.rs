@@ -1,6 +1,11 @@
 use anyhow::{Context, Result};
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use std::collections::hash_map::DefaultHasher;
 use std::fs::File;
-use std::path::Path;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use symphonia::core::audio::SampleBuffer;
 use symphonia::core::codecs::DecoderOptions;
 use symphonia::core::formats::FormatOptions;
@@ -20,12 +25,259 @@ pub struct AudioData {
     /// Original sample rate
     #[allow(dead_code)]
     pub sample_rate: u32,
-    /// Samples normalized to -1.0 to 1.0 range (mono, downsampled for waveform)
-    pub samples: Vec<f32>,
+    /// Channels in the source file
+    pub channels: usize,
+    /// Waveform peaks at every zoom level (channels mixed down)
+    pub peaks: Peaks,
+    /// Left and right peaks, for files with two or more channels
+    pub stereo: Option<[Peaks; 2]>,
+}
+
+/// Peak amplitudes as a pyramid: level 0 holds one peak per
+/// `1 / PEAKS_PER_SECOND` seconds, each level above halves the resolution
+#[derive(Clone)]
+pub struct Peaks {
+    levels: Arc<Vec<Vec<f32>>>,
+}
+
+impl Peaks {
+    /// Finest resolution kept (10 ms); plenty for the deepest zoom
+    pub const PEAKS_PER_SECOND: f64 = 100.0;
+    
+    /// Build the pyramid from mono samples
+    pub fn from_samples(samples: &[f32], sample_rate: u32) -> Self {
+        let mut builder = PeaksBuilder::new(sample_rate);
+        samples.iter().for_each(|&s| builder.push(s));
+        builder.finish()
+    }
+    
+    /// Build the pyramid from level 0, e.g. as read back from the cache
+    fn from_finest(base: Vec<f32>) -> Self {
+        let mut levels = vec![base];
+        while let Some(last) = levels.last()
+            && last.len() > 1
+        {
+            let next = last.chunks(2).map(|pair| pair.iter().fold(0.0_f32, |a, &b| a.max(b))).collect();
+            levels.push(next);
+        }
+        
+        Self { levels: Arc::new(levels) }
+    }
+    
+    /// Loudest peak between two times (seconds), 0.0 to 1.0
+    ///
+    /// Coarse levels round the range outwards by less than its own length,
+    /// which is invisible at the bar width it's drawn with.
+    pub fn max_in(&self, start: f64, end: f64) -> f32 {
+        // Coarsest level whose buckets are no wider than the range
+        let span = ((end - start) * Self::PEAKS_PER_SECOND).max(1.0);
+        let level = (span.log2().floor() as usize).min(self.levels.len() - 1);
+        let peaks = &self.levels[level];
+        let rate = Self::PEAKS_PER_SECOND / f64::from(1_u32 << level);
+        
+        let first = ((start.max(0.0) * rate) as usize).min(peaks.len());
+        let last = ((end * rate).ceil() as usize).clamp(first, peaks.len());
+        peaks[first..last.max((first + 1).min(peaks.len()))]
+            .iter()
+            .fold(0.0, |a, &b| a.max(b))
+    }
+    
+    /// Peaks at the finest resolution, one per `1 / PEAKS_PER_SECOND` seconds
+    pub fn finest(&self) -> &[f32] {
+        &self.levels[0]
+    }
+}
+
+/// Folds samples into level-0 peaks as they arrive, so a long file never
+/// has to be held in memory whole
+struct PeaksBuilder {
+    /// Samples per level-0 peak
+    bucket: usize,
+    /// Samples folded into `current` so far
+    filled: usize,
+    current: f32,
+    base: Vec<f32>,
+}
+
+impl PeaksBuilder {
+    fn new(sample_rate: u32) -> Self {
+        Self {
+            bucket: ((f64::from(sample_rate) / Peaks::PEAKS_PER_SECOND) as usize).max(1),
+            filled: 0,
+            current: 0.0,
+            base: Vec::new(),
+        }
+    }
+    
+    fn push(&mut self, sample: f32) {
+        self.current = self.current.max(sample.abs());
+        self.filled += 1;
+        if self.filled == self.bucket {
+            self.base.push(self.current);
+            self.current = 0.0;
+            self.filled = 0;
+        }
+    }
+    
+    /// The pyramid, with a last partial bucket if the samples ran out mid-way
+    fn finish(mut self) -> Peaks {
+        if self.filled > 0 {
+            self.base.push(self.current);
+        }
+        Peaks::from_finest(self.base)
+    }
+}
+
+/// First bytes of a peaks cache file; bump the digit when the layout changes
+const PEAKS_CACHE_MAGIC: &[u8; 4] = b"MPK2";
+
+/// Cached peaks location for an audio file
+///
+/// Keyed by path and modification time like thumbnails, so an edited file
+/// is decoded again.
+fn peaks_cache_path(source: &Path) -> Option<PathBuf> {
+    let modified = std::fs::metadata(source).ok()?.modified().ok()?;
+    let mut hasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+    modified.hash(&mut hasher);
+    
+    let dir = dirs::home_dir()?.join(".montage").join("peaks");
+    Some(dir.join(format!("{:016x}.peaks", hasher.finish())))
+}
+
+/// Header, then the finest mixed-down peaks and (for stereo) left and
+/// right, all little-endian after the magic
+fn encode_peaks(audio: &AudioData) -> Vec<u8> {
+    let mut arrays = vec![audio.peaks.finest()];
+    if let Some([left, right]) = &audio.stereo {
+        arrays.extend([left.finest(), right.finest()]);
+    }
+    let mut bytes = Vec::with_capacity(22 + arrays.iter().map(|a| a.len() * 4).sum::<usize>());
+    bytes.extend_from_slice(PEAKS_CACHE_MAGIC);
+    bytes.extend_from_slice(&audio.sample_rate.to_le_bytes());
+    bytes.extend_from_slice(&audio.duration.to_le_bytes());
+    bytes.extend_from_slice(&(audio.channels as u16).to_le_bytes());
+    bytes.extend_from_slice(&(arrays.len() as u32).to_le_bytes());
+    for peak in arrays.into_iter().flatten() {
+        bytes.extend_from_slice(&peak.to_le_bytes());
+    }
+    bytes
+}
+
+/// Inverse of `encode_peaks` (the name isn't stored); None for files from
+/// another version or cut short
+fn decode_peaks(bytes: &[u8], name: String) -> Option<AudioData> {
+    let rest = bytes.strip_prefix(PEAKS_CACHE_MAGIC)?;
+    let (sample_rate, rest) = rest.split_first_chunk::<4>()?;
+    let (duration, rest) = rest.split_first_chunk::<8>()?;
+    let (channels, rest) = rest.split_first_chunk::<2>()?;
+    let (count, rest) = rest.split_first_chunk::<4>()?;
+    let count = u32::from_le_bytes(*count) as usize;
+    if !matches!(count, 1 | 3) || rest.is_empty() || rest.len() % (count * 4) != 0 {
+        return None;
+    }
+    let mut arrays = rest.chunks_exact(rest.len() / count).map(|array| {
+        let finest = array
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect();
+        Peaks::from_finest(finest)
+    });
+    let peaks = arrays.next()?;
+    let stereo = match (arrays.next(), arrays.next()) {
+        (Some(left), Some(right)) => Some([left, right]),
+        _ => None,
+    };
+    Some(AudioData {
+        channels: usize::from(u16::from_le_bytes(*channels)),
+        duration: f64::from_le_bytes(*duration),
+        name,
+        peaks,
+        sample_rate: u32::from_le_bytes(*sample_rate),
+        stereo,
+    })
+}
+
+/// Smallest jump in level (0.0 to 1.0) that can count as a beat
+const MIN_ONSET: f32 = 0.1;
+
+/// How much a jump must stand out from the jumps around it
+const ONSET_RATIO: f32 = 3.0;
+
+/// Seconds either side a jump is compared against
+const ONSET_WINDOW_SECONDS: f64 = 0.5;
+
+/// Beats closer than this are one hit (about 240 bpm)
+const MIN_BEAT_GAP_SECONDS: f64 = 0.25;
+
+/// Times (seconds) of strong onsets, such as drum hits
+///
+/// An onset is a sudden rise in the peak envelope, well above the rises
+/// nearby. Steady or quiet audio has none.
+pub fn detect_beats(audio: &AudioData) -> Vec<f64> {
+    let envelope = audio.peaks.finest();
+    let rate = Peaks::PEAKS_PER_SECOND;
+    let flux: Vec<f32> = std::iter::once(0.0)
+        .chain(envelope.windows(2).map(|pair| (pair[1] - pair[0]).max(0.0)))
+        .collect();
+    let window = (ONSET_WINDOW_SECONDS * rate) as usize;
+    let min_gap = (MIN_BEAT_GAP_SECONDS * rate) as usize;
+    
+    let mut beats = Vec::new();
+    let mut last = None;
+    for i in 1..flux.len().saturating_sub(1) {
+        let rise = flux[i];
+        if rise < MIN_ONSET || rise < flux[i - 1] || rise < flux[i + 1] {
+            continue;
+        }
+        let nearby = &flux[i.saturating_sub(window)..(i + window + 1).min(flux.len())];
+        let mean = nearby.iter().sum::<f32>() / nearby.len() as f32;
+        if rise < mean * ONSET_RATIO || last.is_some_and(|l| i - l < min_gap) {
+            continue;
+        }
+        beats.push(i as f64 / rate);
+        last = Some(i);
+    }
+    beats
+}
+
+/// Level (dBFS) below which audio counts as silence by default
+pub const DEFAULT_SILENCE_DB: f64 = -40.0;
+
+/// Shortest pause worth cutting by default, in seconds
+pub const DEFAULT_MIN_SILENCE_SECONDS: f64 = 1.0;
+
+/// Stretches (start, end seconds) quieter than `threshold_db` for at least
+/// `min_duration` seconds, including any at the very start or end
+///
+/// Works on the finest peaks: each covers 10 ms and holds its loudest
+/// sample, so a stretch is only silent if every sample in it is.
+pub fn detect_silence(audio: &AudioData, threshold_db: f64, min_duration: f64) -> Vec<(f64, f64)> {
+    let threshold = 10_f64.powf(threshold_db / 20.0) as f32;
+    let rate = Peaks::PEAKS_PER_SECOND;
+    let peaks = audio.peaks.finest();
+    
+    let mut silences = Vec::new();
+    let mut quiet_since = None;
+    // A loud sentinel closes a silence that runs to the end
+    for (i, &peak) in peaks.iter().chain(std::iter::once(&f32::INFINITY)).enumerate() {
+        match quiet_since {
+            None if peak < threshold => quiet_since = Some(i),
+            Some(start) if peak >= threshold => {
+                let (start, end) = (start as f64 / rate, (i as f64 / rate).min(audio.duration));
+                if end - start >= min_duration {
+                    silences.push((start, end));
+                }
+                quiet_since = None;
+            }
+            _ => {}
+        }
+    }
+    silences
 }
 
 impl AudioData {
-    /// Load audio from a file path
+    /// Load audio from a file path, reusing cached peaks when the file hasn't changed
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
         let path = path.as_ref();
         let name = path
@@ -33,6 +285,30 @@ impl AudioData {
             .and_then(|n| n.to_str())
             .unwrap_or("audio")
             .to_string();
+        
+        let cache = peaks_cache_path(path);
+        if let Some(ref cache) = cache
+            && let Ok(bytes) = std::fs::read(cache)
+            && let Some(audio) = decode_peaks(&bytes, name.clone())
+        {
+            return Ok(audio);
+        }
+        
+        let audio = Self::decode(path, name)?;
+        if let Some(cache) = cache {
+            let written = cache
+                .parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|_| std::fs::write(&cache, encode_peaks(&audio)));
+            if let Err(e) = written {
+                tracing::warn!("Couldn't cache waveform peaks for {}: {}", path.display(), e);
+            }
+        }
+        Ok(audio)
+    }
+    
+    /// Decode the whole file, folding it into peaks packet by packet
+    fn decode(path: &Path, name: String) -> Result<Self> {
 
         let file = File::open(path).context("Failed to open audio file")?;
         let mss = MediaSourceStream::new(Box::new(file), Default::default());
@@ -73,7 +349,11 @@ impl AudioData {
             .make(&track.codec_params, &decoder_opts)
             .context("Failed to create decoder")?;
 
-        let mut all_samples: Vec<f32> = Vec::new();
+        // Only peaks are kept, so memory grows with duration / 10 ms, not with samples
+        let mut frames: u64 = 0;
+        let mut mixed = PeaksBuilder::new(sample_rate);
+        // The first two channels, kept apart for the stereo view
+        let mut sides = (channels >= 2).then(|| [PeaksBuilder::new(sample_rate), PeaksBuilder::new(sample_rate)]);
 
         loop {
             let packet = match format.next_packet() {
@@ -104,36 +384,225 @@ impl AudioData {
             // Convert to mono by averaging channels
             for chunk in samples.chunks(channels) {
                 let mono: f32 = chunk.iter().sum::<f32>() / channels as f32;
-                all_samples.push(mono);
+                mixed.push(mono);
+                frames += 1;
+                if let Some([left, right]) = &mut sides
+                    && let [l, r, ..] = chunk
+                {
+                    left.push(*l);
+                    right.push(*r);
+                }
             }
         }
 
-        let duration = all_samples.len() as f64 / sample_rate as f64;
-
-        // Downsample for waveform display (target ~4000 samples for visualization)
-        let target_samples = 4000;
-        let samples = if all_samples.len() > target_samples {
-            let chunk_size = all_samples.len() / target_samples;
-            all_samples
-                .chunks(chunk_size)
-                .map(|chunk| {
-                    // Use peak value for better waveform visualization
-                    chunk
-                        .iter()
-                        .map(|s| s.abs())
-                        .max_by(|a, b| a.partial_cmp(b).unwrap())
-                        .unwrap_or(0.0)
-                })
-                .collect()
-        } else {
-            all_samples.iter().map(|s| s.abs()).collect()
-        };
+        let duration = frames as f64 / sample_rate as f64;
+
+        // Keep peaks at full timeline resolution so the waveform can zoom
+        let peaks = mixed.finish();
+        let stereo = sides.map(|sides| sides.map(PeaksBuilder::finish));
 
         Ok(Self {
-            samples,
+            channels,
+            peaks,
             sample_rate,
+            stereo,
             duration,
             name,
         })
     }
 }
+
+/// Plays an audio file through GStreamer's `playbin`
+///
+/// The pipeline is torn down when the player is dropped.
+pub struct AudioPlayer {
+    playbin: gst::Element,
+}
+
+impl AudioPlayer {
+    /// Open a file, paused at the start
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
+        gst::init()?;
+        
+        let uri = format!("file://{}", path.as_ref().canonicalize()?.display());
+        let playbin = gst::ElementFactory::make("playbin")
+            .property("uri", &uri)
+            .build()
+            .context("Failed to create playbin")?;
+        
+        // Cover art shows up as a video stream; don't open a window for it
+        let video_sink = gst::ElementFactory::make("fakesink")
+            .build()
+            .context("Failed to create fakesink")?;
+        playbin.set_property("video-sink", &video_sink);
+        
+        playbin.set_state(gst::State::Paused)?;
+        Ok(Self { playbin })
+    }
+    
+    pub fn pause(&self) {
+        let _ = self.playbin.set_state(gst::State::Paused);
+    }
+    
+    pub fn play(&self) {
+        let _ = self.playbin.set_state(gst::State::Playing);
+    }
+    
+    /// Playback position in seconds, as reported by the pipeline
+    pub fn position(&self) -> Option<f64> {
+        self.playbin
+            .query_position::<gst::ClockTime>()
+            .map(|p| p.nseconds() as f64 / 1_000_000_000.0)
+    }
+    
+    pub fn seek(&self, seconds: f64) {
+        let _ = self.playbin.seek_simple(
+            gst::SeekFlags::FLUSH | gst::SeekFlags::ACCURATE,
+            gst::ClockTime::from_nseconds((seconds.max(0.0) * 1_000_000_000.0) as u64),
+        );
+    }
+    
+    /// Drain the bus; returns whether playback reached the end
+    pub fn take_eos(&self) -> bool {
+        let Some(bus) = self.playbin.bus() else {
+            return false;
+        };
+        
+        let mut reached_end = false;
+        while let Some(msg) = bus.pop() {
+            match msg.view() {
+                gst::MessageView::Eos(..) => reached_end = true,
+                gst::MessageView::Error(err) => {
+                    tracing::error!("Audio playback error: {}", err.error());
+                    reached_end = true;
+                }
+                _ => {}
+            }
+        }
+        reached_end
+    }
+}
+
+impl Drop for AudioPlayer {
+    fn drop(&mut self) {
+        let _ = self.playbin.set_state(gst::State::Null);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peaks_at_any_zoom() {
+        // 4 seconds at 1 kHz: silent except a spike at 2.5s
+        let mut samples = vec![0.1_f32; 4000];
+        samples[2500] = -0.9;
+        let peaks = Peaks::from_samples(&samples, 1000);
+
+        assert_eq!(peaks.max_in(0.0, 4.0), 0.9);
+        assert_eq!(peaks.max_in(2.4, 2.6), 0.9);
+        assert_eq!(peaks.max_in(0.0, 1.0), 0.1);
+        // Narrower than one bucket still finds its bucket
+        assert_eq!(peaks.max_in(2.501, 2.502), 0.9);
+        // Past the end
+        assert_eq!(peaks.max_in(10.0, 11.0), 0.0);
+    }
+
+    fn audio_from(samples: &[f32], sample_rate: u32) -> AudioData {
+        AudioData {
+            channels: 1,
+            duration: samples.len() as f64 / f64::from(sample_rate),
+            name: "test".to_string(),
+            sample_rate,
+            peaks: Peaks::from_samples(samples, sample_rate),
+            stereo: None,
+        }
+    }
+
+    #[test]
+    fn test_peaks_cache_roundtrip() {
+        let mut samples = vec![0.2_f32; 3000];
+        samples[1200] = 0.7;
+        let mono = audio_from(&samples, 1000);
+        
+        let bytes = encode_peaks(&mono);
+        let loaded = decode_peaks(&bytes, "test".to_string()).unwrap();
+        assert_eq!(loaded.sample_rate, 1000);
+        assert_eq!(loaded.duration, 3.0);
+        assert_eq!(loaded.peaks.finest(), mono.peaks.finest());
+        assert_eq!(loaded.peaks.max_in(0.0, 3.0), 0.7);
+        assert!(loaded.stereo.is_none());
+        
+        // Left and right come back on their own sides
+        let quiet = vec![0.1_f32; 3000];
+        let stereo = AudioData {
+            channels: 2,
+            stereo: Some([Peaks::from_samples(&samples, 1000), Peaks::from_samples(&quiet, 1000)]),
+            ..mono
+        };
+        let loaded = decode_peaks(&encode_peaks(&stereo), "test".to_string()).unwrap();
+        assert_eq!(loaded.channels, 2);
+        let [left, right] = loaded.stereo.unwrap();
+        assert_eq!(left.max_in(0.0, 3.0), 0.7);
+        assert_eq!(right.max_in(0.0, 3.0), 0.1);
+        
+        // Truncated or foreign files are ignored rather than misread
+        assert!(decode_peaks(&bytes[..bytes.len() - 1], "test".to_string()).is_none());
+        assert!(decode_peaks(b"RIFF....", "test".to_string()).is_none());
+    }
+
+    #[test]
+    fn test_streamed_peaks_match_whole_buffer() {
+        // 2.5 buckets at 1 kHz: the partial last one still counts
+        let samples: Vec<f32> = (0..25).map(|i| (i as f32 / 25.0) * if i % 2 == 0 { 1.0 } else { -1.0 }).collect();
+        let mut builder = PeaksBuilder::new(1000);
+        samples.iter().for_each(|&s| builder.push(s));
+        let streamed = builder.finish();
+        assert_eq!(streamed.finest(), &[0.36, 0.76, 0.96]);
+        
+        // An hour at 1 kHz keeps one peak per 10 ms and nothing else
+        let mut builder = PeaksBuilder::new(1000);
+        for i in 0..3_600_000_u32 {
+            builder.push(if i == 1_800_000 { 0.9 } else { 0.1 });
+        }
+        let peaks = builder.finish();
+        assert_eq!(peaks.finest().len(), 360_000);
+        assert_eq!(peaks.max_in(1799.5, 1800.5), 0.9);
+        assert_eq!(peaks.max_in(0.0, 60.0), 0.1);
+    }
+    
+    #[test]
+    fn test_silences_found_at_edges_and_long_gaps_only() {
+        // 1 kHz: quiet 0-1.5s, speech, 0.3s pause, speech, quiet 4-6s, speech, quiet 8-9s
+        let mut samples = vec![0.3_f32; 9000];
+        for range in [0..1500, 2500..2800, 4000..6000, 8000..9000] {
+            samples[range].fill(0.001);
+        }
+        let silences = detect_silence(&audio_from(&samples, 1000), DEFAULT_SILENCE_DB, 1.0);
+        assert_eq!(silences, vec![(0.0, 1.5), (4.0, 6.0), (8.0, 9.0)]);
+        
+        // Shorter than the minimum, or louder than the threshold: left alone
+        assert_eq!(detect_silence(&audio_from(&samples, 1000), DEFAULT_SILENCE_DB, 2.5), vec![]);
+        assert_eq!(detect_silence(&audio_from(&samples, 1000), -70.0, 1.0), vec![]);
+        assert_eq!(detect_silence(&audio_from(&[0.3; 2000], 1000), DEFAULT_SILENCE_DB, 1.0), vec![]);
+    }
+    
+    #[test]
+    fn test_beats_found_on_hits_only() {
+        // 4 seconds at 1 kHz: a quiet bed with a 50ms hit every half second
+        let mut samples = vec![0.05_f32; 4000];
+        for hit in 1..8 {
+            for s in &mut samples[hit * 500..hit * 500 + 50] {
+                *s = 0.8;
+            }
+        }
+        assert_eq!(
+            detect_beats(&audio_from(&samples, 1000)),
+            vec![0.5, 1.0, 1.5, 2.0, 2.5, 3.0, 3.5]
+        );
+
+        // A steady tone has no beats
+        assert!(detect_beats(&audio_from(&[0.6; 4000], 1000)).is_empty());
+    }
+}
@@ -1,9 +1,15 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
-use crate::pexels::{self, PexelsVideo};
-use crate::transcription::{self, Transcript, TranscriptSegment};
+use crate::agent::OllamaEndpoint;
+use crate::audio::{self, AudioData};
+use crate::media_probe;
+use crate::pexels::{self, Orientation, PexelsPhoto, PexelsVideo};
+use crate::project::ClipSource;
+use crate::transcription::{self, TranscribeOptions, Transcript, TranscriptSegment};
 
 /// A suggested video clip based on transcript
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,29 +18,132 @@ pub struct SuggestedClip {
     pub query: String,
     /// Segment of the transcript this covers
     pub segment: TranscriptSegment,
+    /// When the clip starts on the timeline (seconds)
+    #[serde(default)]
+    pub start: f64,
+    /// How long the clip stays on screen, up to the next clip (seconds)
+    #[serde(default)]
+    pub duration: f64,
     /// Matched video from Pexels
     pub video: Option<PexelsVideo>,
+    /// Photo used as a still when no video matched
+    #[serde(default)]
+    pub photo: Option<PexelsPhoto>,
     /// Local path if downloaded
     pub local_path: Option<PathBuf>,
+    /// Attribution for the downloaded file
+    #[serde(default)]
+    pub source: Option<ClipSource>,
+}
+
+/// What decided where the cuts go
+#[derive(Debug, Clone)]
+pub enum CutTiming {
+    /// One clip per sentence of this transcript
+    Transcript(Transcript),
+    /// Cut on this many of the music's beats
+    Beats(usize),
+    /// No clear beat, so a cut every this many seconds
+    FixedInterval(f64),
 }
 
 /// Result of auto-video generation
 #[derive(Debug, Clone)]
 pub struct AutoVideoResult {
-    /// The transcript
-    pub transcript: Transcript,
+    /// Where the cuts came from
+    pub timing: CutTiming,
+    /// Length of the audio the clips cover (seconds)
+    pub duration: f64,
     /// Suggested clips for each segment
     pub clips: Vec<SuggestedClip>,
+    /// Problems that left some segments without footage
+    pub warnings: Vec<String>,
+}
+
+/// How far auto-video generation has got
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AutoVideoProgress {
+    /// Whisper is running over the audio (it reports nothing finer)
+    Transcribing,
+    /// Looking for the beat in the music
+    FindingBeats,
+    /// Asking the LLM what footage suits each segment
+    PickingQueries,
+    /// Pexels searches finished out of the total
+    Searching { done: usize, total: usize },
+    /// Clips downloaded out of the total
+    Downloading { done: usize, total: usize },
+}
+
+impl AutoVideoProgress {
+    /// 0.0 to 1.0 through this stage, when it can be counted
+    pub fn fraction(&self) -> Option<f64> {
+        match *self {
+            Self::Searching { done, total } | Self::Downloading { done, total } if total > 0 => {
+                Some(done as f64 / total as f64)
+            }
+            _ => None,
+        }
+    }
+    
+    /// Whether both are the same stage, whatever their counts
+    pub fn same_stage(&self, other: &Self) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+    
+    /// "Searching 3/8", for the progress list
+    pub fn label(&self) -> String {
+        match *self {
+            Self::Transcribing => "Transcribing…".to_string(),
+            Self::FindingBeats => "Finding the beat…".to_string(),
+            Self::PickingQueries => "Picking search terms…".to_string(),
+            Self::Searching { done, total } => format!("Searching {}/{}", done, total),
+            Self::Downloading { done, total } => format!("Downloading {}/{}", done, total),
+        }
+    }
+}
+
+/// Auto-video progress callback
+pub type ProgressCallback = Box<dyn Fn(AutoVideoProgress) + Send>;
+
+/// Generation stopped because its cancel flag was set
+#[derive(Debug, thiserror::Error)]
+#[error("Auto-video generation cancelled")]
+pub struct Cancelled;
+
+fn check_cancelled(cancel: &AtomicBool) -> Result<()> {
+    if cancel.load(Ordering::Relaxed) {
+        return Err(Cancelled.into());
+    }
+    Ok(())
 }
 
+/// Shortest clip in a beat-synced montage; closer beats are skipped
+const MIN_BEAT_CLIP_SECONDS: f64 = 1.5;
+
+/// Clip length when the music has no beat to cut on
+const FALLBACK_CUT_SECONDS: f64 = 3.0;
+
+/// Longest stretch of speech one clip covers before the transcript is split
+const MAX_SEGMENT_SECONDS: f64 = 6.0;
+
+/// Segments per keyword request once a transcript is longer than this;
+/// shorter ones go to the model in a single request
+pub const DEFAULT_KEYWORD_BATCH: usize = 20;
+
+/// Keyword requests sent to the model at once
+const PARALLEL_KEYWORD_REQUESTS: usize = 3;
+
 /// Extract keywords from transcript segments for video search
-/// Uses the LLM to analyze the transcript and suggest search queries
+/// Uses the LLM to analyze the transcript and suggest search queries,
+/// `batch` segments per request (0 for a single request)
 pub fn extract_keywords_with_llm(
     transcript: &Transcript,
-    ollama_available: bool,
+    ollama: Option<&OllamaEndpoint>,
+    batch: usize,
 ) -> Result<Vec<(TranscriptSegment, String)>> {
-    if ollama_available {
-        extract_keywords_ollama(transcript)
+    if let Some(ollama) = ollama {
+        extract_keywords_ollama(transcript, ollama, batch)
     } else {
         // Fallback: simple keyword extraction
         Ok(extract_keywords_simple(transcript))
@@ -42,9 +151,96 @@ pub fn extract_keywords_with_llm(
 }
 
 /// Use Ollama to extract meaningful search queries
-fn extract_keywords_ollama(transcript: &Transcript) -> Result<Vec<(TranscriptSegment, String)>> {
+///
+/// Long transcripts are split into batches asked for concurrently, which
+/// keeps each prompt small enough for small models; a batch that fails
+/// falls back to simple extraction for its segments.
+fn extract_keywords_ollama(
+    transcript: &Transcript,
+    ollama: &OllamaEndpoint,
+    batch: usize,
+) -> Result<Vec<(TranscriptSegment, String)>> {
+    let segments = &transcript.segments;
+    let simple = extract_keywords_simple(transcript);
+    if batch == 0 || segments.len() <= batch {
+        let answer = suggest_queries(segments, ollama);
+        return merge_keyword_batches(simple, vec![(0, segments.len(), answer)]);
+    }
+    
+    // Batches as (index of their first segment, segments)
+    let batches: Vec<(usize, &[TranscriptSegment])> = segments
+        .chunks(batch)
+        .enumerate()
+        .map(|(i, chunk)| (i * batch, chunk))
+        .collect();
+    let mut answers = Vec::new();
+    for wave in batches.chunks(PARALLEL_KEYWORD_REQUESTS) {
+        std::thread::scope(|scope| {
+            let requests: Vec<_> = wave
+                .iter()
+                .map(|&(first, chunk)| (first, chunk.len(), scope.spawn(move || suggest_queries(chunk, ollama))))
+                .collect();
+            for (first, len, request) in requests {
+                let answer = request
+                    .join()
+                    .unwrap_or_else(|_| Err(anyhow::anyhow!("keyword request panicked")));
+                answers.push((first, len, answer));
+            }
+        });
+    }
+    merge_keyword_batches(simple, answers)
+}
+
+/// Merge batch answers, each as (index of its first segment, segment count,
+/// answer), into one query per segment. Segments a batch left out, or whose
+/// batch failed, keep their query from `simple`.
+fn merge_keyword_batches(
+    simple: Vec<(TranscriptSegment, String)>,
+    answers: Vec<(usize, usize, Result<Vec<QuerySuggestion>>)>,
+) -> Result<Vec<(TranscriptSegment, String)>> {
+    let batches = answers.len();
+    let mut queries: Vec<Option<String>> = vec![None; simple.len()];
+    let mut failed = 0;
+    for (first, len, answer) in answers {
+        match answer {
+            Ok(suggestions) => {
+                // Indices in the answer count from the batch's first segment
+                for s in suggestions.into_iter().filter(|s| s.segment_index < len) {
+                    if let Some(slot) = queries.get_mut(first + s.segment_index)
+                        && slot.is_none()
+                    {
+                        *slot = Some(s.query);
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Keyword batch at segment {} failed: {}, using simple extraction", first, e);
+                failed += 1;
+            }
+        }
+    }
+    if failed == batches {
+        anyhow::bail!("Every keyword request failed");
+    }
     
-    let segments_json = serde_json::to_string_pretty(&transcript.segments)?;
+    Ok(simple
+        .into_iter()
+        .zip(queries)
+        .map(|((segment, fallback), query)| (segment, query.unwrap_or(fallback)))
+        .collect())
+}
+
+/// One search query the model suggested, for a segment counted from the
+/// start of the request
+#[derive(Deserialize)]
+struct QuerySuggestion {
+    segment_index: usize,
+    query: String,
+}
+
+/// Ask the model for a search query per segment
+fn suggest_queries(segments: &[TranscriptSegment], ollama: &OllamaEndpoint) -> Result<Vec<QuerySuggestion>> {
+    let segments_json = serde_json::to_string_pretty(segments)?;
     
     let prompt = format!(
         r#"Analyze these transcript segments and suggest a Pexels video search query for each.
@@ -63,15 +259,9 @@ Return ONLY valid JSON array like:
         segments_json
     );
     
-    #[derive(Deserialize)]
-    struct QuerySuggestion {
-        segment_index: usize,
-        query: String,
-    }
-    
     // Call Ollama directly with a simpler request
     let request = serde_json::json!({
-        "model": "qwen2.5:3b",
+        "model": ollama.model,
         "prompt": prompt,
         "stream": false,
         "format": "json"
@@ -79,9 +269,9 @@ Return ONLY valid JSON array like:
     
     let client = reqwest::blocking::Client::new();
     let response = client
-        .post("http://localhost:11434/api/generate")
+        .post(ollama.generate_url())
         .json(&request)
-        .timeout(std::time::Duration::from_secs(60))
+        .timeout(ollama.timeout)
         .send()
         .context("Failed to connect to Ollama")?;
     
@@ -95,17 +285,7 @@ Return ONLY valid JSON array like:
     }
     
     let ollama_resp: OllamaResponse = response.json()?;
-    let suggestions: Vec<QuerySuggestion> = serde_json::from_str(&ollama_resp.response)
-        .context("Failed to parse LLM suggestions")?;
-    
-    let results: Vec<_> = suggestions.into_iter()
-        .filter_map(|s| {
-            transcript.segments.get(s.segment_index)
-                .map(|seg| (seg.clone(), s.query))
-        })
-        .collect();
-    
-    Ok(results)
+    serde_json::from_str(&ollama_resp.response).context("Failed to parse LLM suggestions")
 }
 
 /// Simple keyword extraction without LLM
@@ -142,82 +322,528 @@ fn extract_keywords_simple(transcript: &Transcript) -> Vec<(TranscriptSegment, S
     }).collect()
 }
 
-/// Generate video suggestions from audio
+/// Generate video suggestions from audio, reusing `transcript` when the
+/// project already has one for this file
 pub fn generate_from_audio(
     audio_path: &Path,
+    transcript: Option<Transcript>,
+    whisper: &TranscribeOptions,
+    ollama: &OllamaEndpoint,
+    keyword_batch: usize,
     pexels_api_key: &str,
+    orientation: Orientation,
     output_dir: &Path,
+    on_progress: &ProgressCallback,
+    cancel: &AtomicBool,
 ) -> Result<AutoVideoResult> {
     // Step 1: Transcribe audio
-    tracing::info!("Transcribing audio: {:?}", audio_path);
-    let transcript = transcription::transcribe(audio_path)
-        .context("Failed to transcribe audio")?;
+    let transcript = match transcript {
+        Some(transcript) => {
+            tracing::info!("Using cached transcript of {:?}", audio_path);
+            transcript
+        }
+        None => {
+            tracing::info!("Transcribing audio: {:?}", audio_path);
+            on_progress(AutoVideoProgress::Transcribing);
+            let options = TranscribeOptions {
+                max_segment_seconds: Some(MAX_SEGMENT_SECONDS),
+                word_timestamps: true,
+                ..whisper.clone()
+            };
+            transcription::transcribe(audio_path, &options).context("Failed to transcribe audio")?
+        }
+    };
+    check_cancelled(cancel)?;
     
+    // A cached transcript may still have whisper's long segments
+    let segmented = transcript.resegmented(MAX_SEGMENT_SECONDS);
     tracing::info!("Transcript: {} segments, {:.1}s duration", 
-        transcript.segments.len(), transcript.duration);
+        segmented.segments.len(), segmented.duration);
     
     // Step 2: Extract keywords for each segment
     tracing::info!("Extracting keywords...");
-    let keywords = extract_keywords_with_llm(&transcript, true)
+    on_progress(AutoVideoProgress::PickingQueries);
+    let keywords = extract_keywords_with_llm(&segmented, Some(ollama), keyword_batch)
         .unwrap_or_else(|e| {
             tracing::warn!("LLM keyword extraction failed: {}, using simple extraction", e);
-            extract_keywords_simple(&transcript)
+            extract_keywords_simple(&segmented)
         });
     
     // Step 3: Search Pexels for each keyword
     tracing::info!("Searching Pexels for {} segments...", keywords.len());
     std::fs::create_dir_all(output_dir)?;
     
+    let mut keywords = keywords;
+    keywords.sort_by(|a, b| a.0.start.total_cmp(&b.0.start));
+    let spans = clip_spans(&keywords, transcript.duration);
+    
+    let total = keywords.len();
     let mut clips = Vec::new();
-    for (segment, query) in keywords {
+    // Repeated queries within this run are answered from its cache
+    let pexels = pexels::PexelsClient::new(pexels_api_key);
+    // Once the quota is gone every further search would fail the same way
+    let mut rate_limited: Option<pexels::PexelsError> = None;
+    for (done, ((segment, query), (start, duration))) in keywords.into_iter().zip(spans).enumerate() {
+        check_cancelled(cancel)?;
+        on_progress(AutoVideoProgress::Searching { done, total });
         tracing::info!("Searching for: '{}'", query);
         
-        let video = match pexels::search_videos(pexels_api_key, &query, 3) {
-            Ok(videos) => {
-                // Pick a video that's long enough for the segment
-                let segment_duration = (segment.end - segment.start) as u32;
-                videos.into_iter()
-                    .find(|v| v.duration >= segment_duration.max(3))
+        let video = if rate_limited.is_some() {
+            None
+        } else {
+            match pexels.search_videos(&query, 3, orientation, cancel) {
+                Ok(videos) => {
+                    // Pick a video that's long enough to fill the clip
+                    let needed = seconds_needed(duration).max(3);
+                    videos.into_iter()
+                        .find(|v| v.duration >= needed)
+                }
+                Err(pexels::PexelsError::Auth) => return Err(pexels::PexelsError::Auth.into()),
+                Err(pexels::PexelsError::Cancelled) => return Err(Cancelled.into()),
+                Err(e) => {
+                    tracing::warn!("Pexels search failed for '{}': {}", query, e);
+                    if matches!(e, pexels::PexelsError::RateLimited(_)) {
+                        rate_limited = Some(e);
+                    }
+                    None
+                }
             }
-            Err(e) => {
-                tracing::warn!("Pexels search failed for '{}': {}", query, e);
-                None
+        };
+        
+        // No stock footage: fall back to a still for a slideshow
+        let photo = if video.is_none() && rate_limited.is_none() {
+            match pexels.search_photos(&query, 1, orientation, cancel) {
+                Ok(photos) => photos.into_iter().next(),
+                Err(pexels::PexelsError::Cancelled) => return Err(Cancelled.into()),
+                Err(e) => {
+                    tracing::warn!("Pexels photo search failed for '{}': {}", query, e);
+                    if matches!(e, pexels::PexelsError::RateLimited(_)) {
+                        rate_limited = Some(e);
+                    }
+                    None
+                }
             }
+        } else {
+            None
         };
         
         clips.push(SuggestedClip {
             query,
             segment,
+            start,
+            duration,
             video,
+            photo,
             local_path: None,
+            source: None,
         });
     }
+    on_progress(AutoVideoProgress::Searching { done: total, total });
     
-    Ok(AutoVideoResult { transcript, clips })
+    let duration = transcript.duration;
+    let warnings = rate_limited
+        .map(|e| {
+            let missing = clips.iter().filter(|c| c.video.is_none() && c.photo.is_none()).count();
+            format!("⚠ {} ({} segments have no footage)", e, missing)
+        })
+        .into_iter()
+        .collect();
+    Ok(AutoVideoResult {
+        timing: CutTiming::Transcript(transcript),
+        duration,
+        clips,
+        warnings,
+    })
 }
 
-/// Download all suggested videos
+/// Cut stock footage of `query` to the beat of the music in `audio_path`,
+/// falling back to evenly spaced cuts when no strong beat is found
+pub fn generate_beat_synced(
+    audio_path: &Path,
+    query: &str,
+    pexels_api_key: &str,
+    orientation: Orientation,
+    on_progress: &ProgressCallback,
+    cancel: &AtomicBool,
+) -> Result<AutoVideoResult> {
+    on_progress(AutoVideoProgress::FindingBeats);
+    let audio = AudioData::load(audio_path).context("Failed to load audio")?;
+    let beats = audio::detect_beats(&audio);
+    check_cancelled(cancel)?;
+    tracing::info!("Found {} beats in {:?}", beats.len(), audio_path);
+    
+    let (cuts, timing) = if beats.is_empty() {
+        let cuts: Vec<f64> = (1..)
+            .map(|i| f64::from(i) * FALLBACK_CUT_SECONDS)
+            .take_while(|&t| t < audio.duration)
+            .collect();
+        (cuts, CutTiming::FixedInterval(FALLBACK_CUT_SECONDS))
+    } else {
+        let cuts = beat_cuts(&beats, audio.duration);
+        let count = cuts.len();
+        (cuts, CutTiming::Beats(count))
+    };
+    let spans = cut_spans(&cuts, audio.duration);
+    
+    // One search, reused round-robin; cached downloads make repeats cheap
+    on_progress(AutoVideoProgress::Searching { done: 0, total: 1 });
+    let per_page = (spans.len() as u32).clamp(1, 40);
+    let pexels = pexels::PexelsClient::new(pexels_api_key);
+    let videos = pexels.search_videos(query, per_page, orientation, cancel);
+    check_cancelled(cancel)?;
+    let videos = videos.with_context(|| format!("Pexels search failed for '{}'", query))?;
+    let photos = if videos.is_empty() {
+        let photos = pexels.search_photos(query, per_page, orientation, cancel);
+        check_cancelled(cancel)?;
+        photos.with_context(|| format!("Pexels photo search failed for '{}'", query))?
+    } else {
+        Vec::new()
+    };
+    on_progress(AutoVideoProgress::Searching { done: 1, total: 1 });
+    
+    let clips = spans
+        .into_iter()
+        .enumerate()
+        .map(|(i, (start, duration))| {
+            let needed = seconds_needed(duration);
+            let video = (0..videos.len())
+                .map(|offset| &videos[(i + offset) % videos.len()])
+                .find(|v| v.duration >= needed)
+                .cloned();
+            let photo = if video.is_none() && !photos.is_empty() {
+                Some(photos[i % photos.len()].clone())
+            } else {
+                None
+            };
+            SuggestedClip {
+                query: query.to_string(),
+                segment: TranscriptSegment {
+                    start,
+                    end: start + duration,
+                    text: String::new(),
+                    words: Vec::new(),
+                },
+                start,
+                duration,
+                video,
+                photo,
+                local_path: None,
+                source: None,
+            }
+        })
+        .collect();
+    
+    Ok(AutoVideoResult {
+        timing,
+        duration: audio.duration,
+        clips,
+        warnings: Vec::new(),
+    })
+}
+
+/// Whole seconds a Pexels video must list to cover `duration`; Pexels
+/// rounds lengths, so a second is kept in hand
+fn seconds_needed(duration: f64) -> u32 {
+    (duration + 1.0).ceil() as u32
+}
+
+/// Beats to cut on, keeping every clip at least `MIN_BEAT_CLIP_SECONDS` long
+fn beat_cuts(beats: &[f64], duration: f64) -> Vec<f64> {
+    let mut cuts: Vec<f64> = Vec::new();
+    for &beat in beats {
+        let since_last = beat - cuts.last().copied().unwrap_or(0.0);
+        if since_last >= MIN_BEAT_CLIP_SECONDS && duration - beat >= MIN_BEAT_CLIP_SECONDS {
+            cuts.push(beat);
+        }
+    }
+    cuts
+}
+
+/// Start and length of the clips between cuts, covering zero to `duration`
+fn cut_spans(cuts: &[f64], duration: f64) -> Vec<(f64, f64)> {
+    std::iter::once(0.0)
+        .chain(cuts.iter().copied())
+        .zip(cuts.iter().copied().chain(std::iter::once(duration)))
+        .map(|(start, end)| (start, end - start))
+        .collect()
+}
+
+/// Timeline start and length for a clip per segment. Each clip runs until
+/// the next one starts, the first from zero and the last to the end of the
+/// audio, so pauses between sentences don't shift later clips.
+fn clip_spans(segments: &[(TranscriptSegment, String)], total: f64) -> Vec<(f64, f64)> {
+    let starts: Vec<f64> = segments
+        .iter()
+        .enumerate()
+        .map(|(i, (segment, _))| if i == 0 { 0.0 } else { segment.start })
+        .collect();
+    let end = total.max(segments.last().map_or(0.0, |(s, _)| s.end));
+    
+    starts
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| {
+            let next = starts.get(i + 1).copied().unwrap_or(end);
+            (start, (next - start).max(0.0))
+        })
+        .collect()
+}
+
+/// Files `download_clips` fetches at once unless told otherwise
+pub const DEFAULT_PARALLEL_DOWNLOADS: usize = 4;
+
+/// One file `download_clips` needs
+enum Download {
+    Video(PexelsVideo),
+    Photo(PexelsPhoto, PathBuf),
+}
+
+impl Download {
+    /// Fetch the file, returning where it landed; videos go to `dir`
+    fn fetch(&self, dir: &Path, cancel: &AtomicBool) -> Result<PathBuf> {
+        match self {
+            Self::Video(video) => pexels::download_video(video, dir, cancel),
+            Self::Photo(photo, path) => {
+                pexels::download_photo(photo, path, cancel)?;
+                Ok(path.clone())
+            }
+        }
+    }
+}
+
+/// Download all suggested videos (or their fallback photos), `parallel` at
+/// a time. A segment whose file failed or that found nothing gets a
+/// placeholder still, so the cut keeps pace with the audio; a cancelled
+/// download removes its partial file and stops the rest.
 pub fn download_clips(
     result: &mut AutoVideoResult,
     output_dir: &Path,
-    _pexels_api_key: &str,
+    parallel: usize,
+    on_progress: &ProgressCallback,
+    cancel: &AtomicBool,
 ) -> Result<()> {
+    check_cancelled(cancel)?;
+    
+    // One download per file: a video picked for several segments is
+    // fetched once, and stills already on disk aren't fetched again
+    let mut downloads: Vec<Download> = Vec::new();
+    let mut video_downloads: HashMap<u64, usize> = HashMap::new();
+    let mut clip_downloads: Vec<Option<usize>> = Vec::with_capacity(result.clips.len());
     for (i, clip) in result.clips.iter_mut().enumerate() {
-        if let Some(ref video) = clip.video {
-            let filename = format!("clip_{:03}_{}.mp4", i, clip.query.replace(' ', "_"));
+        let download = if let Some(ref video) = clip.video {
+            Some(*video_downloads.entry(video.id).or_insert_with(|| {
+                downloads.push(Download::Video(video.clone()));
+                downloads.len() - 1
+            }))
+        } else if let Some(ref photo) = clip.photo {
+            // The photo's id keeps a later run from reusing a different photo's file
+            let filename = format!("clip_{:03}_{}_{}.jpg", i, clip.query.replace(' ', "_"), photo.id);
             let output_path = output_dir.join(&filename);
-            
-            if !output_path.exists() {
-                tracing::info!("Downloading clip {}: {}", i, clip.query);
-                if let Err(e) = pexels::download_video(video, &output_path) {
-                    tracing::warn!("Failed to download clip {}: {}", i, e);
-                    continue;
+            if output_path.exists() {
+                clip.local_path = Some(output_path);
+                clip.source = Some(photo.source());
+                None
+            } else {
+                downloads.push(Download::Photo(photo.clone(), output_path));
+                Some(downloads.len() - 1)
+            }
+        } else {
+            None
+        };
+        clip_downloads.push(download);
+    }
+    
+    let total = downloads.len();
+    on_progress(AutoVideoProgress::Downloading { done: 0, total });
+    
+    let mut results: Vec<Option<Result<PathBuf>>> = (0..total).map(|_| None).collect();
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::scope(|scope| {
+        for _ in 0..parallel.max(1).min(total) {
+            let (tx, next, downloads) = (tx.clone(), &next, &downloads);
+            scope.spawn(move || {
+                while !cancel.load(Ordering::Relaxed) {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(download) = downloads.get(i) else { break };
+                    if tx.send((i, download.fetch(output_dir, cancel))).is_err() {
+                        break;
+                    }
                 }
+            });
+        }
+        drop(tx);
+        
+        // Progress is reported from here; the callback isn't shared with the workers
+        for (done, (i, result)) in rx.into_iter().enumerate() {
+            results[i] = Some(result);
+            on_progress(AutoVideoProgress::Downloading { done: done + 1, total });
+        }
+    });
+    check_cancelled(cancel)?;
+    
+    for (i, (clip, download)) in result.clips.iter_mut().zip(clip_downloads).enumerate() {
+        let Some(download) = download else { continue };
+        let path = match &results[download] {
+            Some(Ok(path)) => path.clone(),
+            Some(Err(e)) if e.is::<pexels::DownloadCancelled>() => return Err(Cancelled.into()),
+            Some(Err(e)) => {
+                tracing::warn!("Failed to download clip {}: {}", i, e);
+                continue;
+            }
+            None => continue,
+        };
+        
+        if clip.video.is_some() {
+            // Only the clip's span is used, but it has to be there
+            let length = media_probe::probe(&path).ok().and_then(|info| info.duration);
+            if let Some(length) = length
+                && length < clip.duration
+            {
+                tracing::warn!(
+                    "Clip {} is {:.1}s but needs {:.1}s; the video will run short",
+                    i, length, clip.duration
+                );
+                clip.duration = length;
             }
-            
-            clip.local_path = Some(output_path);
         }
+        clip.local_path = Some(path);
+        clip.source = clip
+            .video
+            .as_ref()
+            .map(PexelsVideo::source)
+            .or_else(|| clip.photo.as_ref().map(PexelsPhoto::source));
+    }
+    
+    if result.clips.iter().any(|c| c.local_path.is_none()) {
+        let missing = fill_missing_clips(&mut result.clips, &placeholder_still(output_dir)?);
+        result.warnings.push(format!(
+            "{} segment(s) got no footage and hold a black placeholder instead",
+            missing
+        ));
     }
     
     Ok(())
 }
+
+/// A black still for segments without footage, written once per folder
+fn placeholder_still(output_dir: &Path) -> Result<PathBuf> {
+    let path = output_dir.join("placeholder.png");
+    if !path.exists() {
+        image::RgbImage::new(1280, 720)
+            .save(&path)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(path)
+}
+
+/// Point every clip that has no file at `placeholder`, keeping its time
+/// span; returns how many there were
+fn fill_missing_clips(clips: &mut [SuggestedClip], placeholder: &Path) -> usize {
+    let mut missing = 0;
+    for clip in clips.iter_mut().filter(|c| c.local_path.is_none()) {
+        clip.video = None;
+        clip.photo = None;
+        clip.source = None;
+        clip.local_path = Some(placeholder.to_path_buf());
+        missing += 1;
+    }
+    missing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn simple(count: usize) -> Vec<(TranscriptSegment, String)> {
+        (0..count)
+            .map(|i| {
+                let segment = TranscriptSegment {
+                    start: i as f64,
+                    end: i as f64 + 1.0,
+                    text: format!("segment {}", i),
+                    words: Vec::new(),
+                };
+                (segment, format!("simple {}", i))
+            })
+            .collect()
+    }
+    
+    fn answer(pairs: &[(usize, &str)]) -> Result<Vec<QuerySuggestion>> {
+        Ok(pairs
+            .iter()
+            .map(|&(segment_index, query)| QuerySuggestion { segment_index, query: query.to_string() })
+            .collect())
+    }
+    
+    fn segment_clip(start: f64, duration: f64, local_path: Option<&str>) -> SuggestedClip {
+        SuggestedClip {
+            query: "city".to_string(),
+            segment: TranscriptSegment { start, end: start + duration, text: String::new(), words: Vec::new() },
+            start,
+            duration,
+            video: None,
+            photo: None,
+            local_path: local_path.map(PathBuf::from),
+            source: None,
+        }
+    }
+    
+    fn queries(merged: &[(TranscriptSegment, String)]) -> Vec<&str> {
+        merged.iter().map(|(_, query)| query.as_str()).collect()
+    }
+    
+    #[test]
+    fn test_batch_indices_offset_by_first_segment() {
+        let merged = merge_keyword_batches(simple(4), vec![
+            (0, 2, answer(&[(0, "beach"), (1, "city")])),
+            (2, 2, answer(&[(1, "forest"), (0, "desk")])),
+        ])
+        .unwrap();
+        assert_eq!(queries(&merged), ["beach", "city", "desk", "forest"]);
+        assert_eq!(merged[3].0.text, "segment 3");
+    }
+    
+    #[test]
+    fn test_omitted_segments_keep_simple_query() {
+        // The second batch skips its first segment and strays past its end
+        let merged = merge_keyword_batches(simple(4), vec![
+            (0, 2, answer(&[(0, "beach"), (1, "city")])),
+            (2, 2, answer(&[(1, "forest"), (2, "ocean")])),
+        ])
+        .unwrap();
+        assert_eq!(queries(&merged), ["beach", "city", "simple 2", "forest"]);
+    }
+    
+    #[test]
+    fn test_failed_batch_falls_back_to_simple() {
+        let merged = merge_keyword_batches(simple(4), vec![
+            (0, 2, Err(anyhow::anyhow!("timed out"))),
+            (2, 2, answer(&[(0, "desk"), (1, "forest")])),
+        ])
+        .unwrap();
+        assert_eq!(queries(&merged), ["simple 0", "simple 1", "desk", "forest"]);
+        
+        let all_failed = merge_keyword_batches(simple(2), vec![
+            (0, 1, Err(anyhow::anyhow!("timed out"))),
+            (1, 1, Err(anyhow::anyhow!("refused"))),
+        ]);
+        assert!(all_failed.is_err());
+    }
+    
+    #[test]
+    fn test_missing_clips_hold_their_span() {
+        let mut clips = vec![
+            segment_clip(0.0, 2.0, Some("/stock/a.mp4")),
+            segment_clip(2.0, 3.0, None),
+            segment_clip(5.0, 1.5, Some("/stock/c.jpg")),
+            segment_clip(6.5, 4.0, None),
+        ];
+        assert_eq!(fill_missing_clips(&mut clips, Path::new("/stock/placeholder.png")), 2);
+        
+        let paths: Vec<&str> = clips.iter().map(|c| c.local_path.as_ref().unwrap().to_str().unwrap()).collect();
+        assert_eq!(paths, ["/stock/a.mp4", "/stock/placeholder.png", "/stock/c.jpg", "/stock/placeholder.png"]);
+        let total: f64 = clips.iter().map(|c| c.duration).sum();
+        assert_eq!(total, 10.5);
+    }
+}
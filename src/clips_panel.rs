@@ -1,5 +1,62 @@
 use gpui::*;
-use crate::project::{Clip, MediaType};
+use gpui::prelude::FluentBuilder;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use crate::config::Theme;
+use crate::project::{Clip, ClipLabel, MediaType};
+use crate::text_buffer::TextBuffer;
+use crate::thumbnails;
+
+/// Payload carried while clips are being dragged; also renders the drag preview
+#[derive(Clone)]
+struct DraggedClip {
+    /// Positions of the dragged clips when the drag started, in order
+    indices: Vec<usize>,
+    label: SharedString,
+    theme: Theme,
+}
+
+impl Render for DraggedClip {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .px_2()
+            .py_1()
+            .bg(rgb(self.theme.control_hover))
+            .border_1()
+            .border_color(rgb(self.theme.accent))
+            .rounded_md()
+            .text_sm()
+            .text_color(rgb(self.theme.text))
+            .child(self.label.clone())
+    }
+}
+
+/// A clip description being edited in place
+struct Rename {
+    buffer: TextBuffer,
+    clip_id: String,
+    /// Commits the edit when the field loses focus
+    _blur: Subscription,
+}
+
+/// Which clips the panel lists
+#[derive(Clone, PartialEq)]
+enum ClipFilter {
+    All,
+    Label(ClipLabel),
+    Media(MediaType),
+}
+
+impl ClipFilter {
+    fn matches(&self, clip: &Clip) -> bool {
+        match self {
+            Self::All => true,
+            Self::Label(label) => clip.label.as_ref() == Some(label),
+            Self::Media(media_type) => clip.media_type == *media_type,
+        }
+    }
+}
 
 /// Events emitted by the clips panel
 pub enum ClipsPanelEvent {
@@ -7,10 +64,24 @@ pub enum ClipsPanelEvent {
     SelectClip(String),
     /// User wants to delete a clip
     DeleteClip(String),
+    /// User pressed Delete with several clips selected
+    DeleteMany(Vec<String>),
     /// User wants to move a clip up
     MoveUp(String),
     /// User wants to move a clip down
     MoveDown(String),
+    /// User dragged a selection of clips (by index) into the gap before `slot`
+    MoveMany { indices: Vec<usize>, slot: usize },
+    /// User wants a copy of a clip right after it
+    DuplicateClip(String),
+    /// User renamed a clip inline
+    Rename { id: String, new_description: String },
+    /// User picked a label for clips (None clears it)
+    SetLabel { ids: Vec<String>, label: Option<ClipLabel> },
+    /// User dragged a clip from one position to another (final index)
+    Reorder { from: usize, to: usize },
+    /// User dropped files into the gap before `index`, in the order given
+    InsertFiles { index: usize, paths: Vec<PathBuf> },
 }
 
 impl EventEmitter<ClipsPanelEvent> for ClipsPanel {}
@@ -19,38 +90,479 @@ impl EventEmitter<ClipsPanelEvent> for ClipsPanel {}
 pub struct ClipsPanel {
     /// Clips to display
     clips: Vec<Clip>,
-    /// Currently selected clip ID
-    selected_id: Option<String>,
+    /// Gap a dragged clip or file would land in (0 = before the first clip)
+    drop_slot: Option<usize>,
+    /// Only clips matching this are listed
+    filter: ClipFilter,
+    /// Focus so Delete reaches the panel
+    focus_handle: FocusHandle,
+    /// Clip whose label swatches are open
+    label_menu: Option<String>,
+    /// Focus for the inline rename field
+    rename_focus: FocusHandle,
+    /// Clip being renamed, if any
+    renaming: Option<Rename>,
+    /// Last clip clicked without shift; shift-click selects the range from here
+    selection_anchor: Option<String>,
+    /// Selected clip IDs
+    selected: HashSet<String>,
+    /// Thumbnail files already being extracted (or that failed to)
+    thumbnails_requested: HashSet<PathBuf>,
+    theme: Theme,
+    /// Thumbnail images by clip ID
+    thumbnails: HashMap<String, PathBuf>,
 }
 
 impl ClipsPanel {
-    pub fn new() -> Self {
+    pub fn new(cx: &mut Context<Self>) -> Self {
         Self {
             clips: Vec::new(),
-            selected_id: None,
+            drop_slot: None,
+            filter: ClipFilter::All,
+            focus_handle: cx.focus_handle(),
+            label_menu: None,
+            rename_focus: cx.focus_handle(),
+            renaming: None,
+            selection_anchor: None,
+            selected: HashSet::new(),
+            theme: Theme::DARK,
+            thumbnails_requested: HashSet::new(),
+            thumbnails: HashMap::new(),
         }
     }
     
     /// Update the clips list
-    pub fn set_clips(&mut self, clips: Vec<Clip>) {
+    pub fn set_clips(&mut self, clips: Vec<Clip>, cx: &mut Context<Self>) {
         self.clips = clips;
+        // A tag nobody uses anymore has no chip to turn its filter off
+        if let ClipFilter::Label(ClipLabel::Tag(_)) = self.filter
+            && !self.clips.iter().any(|c| self.filter.matches(c))
+        {
+            self.filter = ClipFilter::All;
+        }
+        self.deselect_hidden();
+        // Keep an edit in progress unless its clip went away
+        if let Some(ref rename) = self.renaming
+            && !self.clips.iter().any(|c| c.id == rename.clip_id)
+        {
+            self.renaming = None;
+        }
+        self.request_thumbnails(cx);
+    }
+    
+    /// Swap a clip's label for an edit field
+    fn start_rename(&mut self, clip_id: String, description: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let mut buffer = TextBuffer::new();
+        buffer.set_text(description);
+        buffer.select_all();
+        // Clicking elsewhere keeps what was typed, like Enter
+        let blur = cx.on_blur(&self.rename_focus, window, |this, _window, cx| this.commit_rename(cx));
+        self.renaming = Some(Rename { buffer, clip_id, _blur: blur });
+        self.rename_focus.focus(window, cx);
+        cx.notify();
+    }
+    
+    /// Apply the edit; an empty name keeps the old description
+    fn commit_rename(&mut self, cx: &mut Context<Self>) {
+        let Some(rename) = self.renaming.take() else {
+            return;
+        };
+        let new_description = rename.buffer.text().trim().to_string();
+        let unchanged = self.clips
+            .iter()
+            .any(|c| c.id == rename.clip_id && c.description == new_description);
+        if !new_description.is_empty() && !unchanged {
+            cx.emit(ClipsPanelEvent::Rename { id: rename.clip_id, new_description });
+        }
+        cx.notify();
+    }
+    
+    fn handle_rename_key(&mut self, event: &KeyDownEvent, cx: &mut Context<Self>) {
+        let Some(ref mut rename) = self.renaming else {
+            return;
+        };
+        let buffer = &mut rename.buffer;
+        let modifiers = &event.keystroke.modifiers;
+        let command = modifiers.control || modifiers.platform;
+        
+        match event.keystroke.key.as_str() {
+            "enter" => {
+                self.commit_rename(cx);
+                return;
+            }
+            "escape" => self.renaming = None,
+            "a" if command => buffer.select_all(),
+            "backspace" if modifiers.alt => buffer.delete_word_backward(),
+            "backspace" => buffer.backspace(),
+            "delete" => buffer.delete(),
+            "end" => buffer.move_end(),
+            "home" => buffer.move_home(),
+            "left" => buffer.move_left(),
+            "right" => buffer.move_right(),
+            _ => {
+                if command || modifiers.alt {
+                    return;
+                }
+                let Some(ch) = &event.keystroke.key_char else {
+                    return;
+                };
+                buffer.insert(ch);
+            }
+        }
+        cx.notify();
+    }
+    
+    /// Edit field shown in place of a clip's title
+    fn render_rename_field(&self, rename: &Rename, cx: &mut Context<Self>) -> AnyElement {
+        let theme = self.theme;
+        let text = rename.buffer.text();
+        let content = match rename.buffer.selection() {
+            Some((start, end)) => div()
+                .flex()
+                .child(text[..start].to_string())
+                .child(div().bg(rgb(theme.selected)).child(text[start..end].to_string()))
+                .child(text[end..].to_string()),
+            None => {
+                let cursor = rename.buffer.cursor();
+                div()
+                    .flex()
+                    .child(text[..cursor].to_string())
+                    .child(div().text_color(rgb(theme.accent)).child("│"))
+                    .child(text[cursor..].to_string())
+            }
+        };
+        
+        div()
+            .id("rename-field")
+            .key_context("TextInput")
+            .track_focus(&self.rename_focus)
+            .on_key_down(cx.listener(|this, event: &KeyDownEvent, _window, cx| {
+                cx.stop_propagation();
+                this.handle_rename_key(event, cx);
+            }))
+            // Clicks inside the field shouldn't select or drag the clip
+            .on_click(|_event, _window, cx| cx.stop_propagation())
+            .px_1()
+            .w(px(110.0))
+            .overflow_hidden()
+            .bg(rgb(theme.background))
+            .border_1()
+            .border_color(rgb(theme.accent))
+            .rounded_sm()
+            .text_sm()
+            .text_color(rgb(theme.text))
+            .child(content)
+            .into_any_element()
+    }
+    
+    /// Use cached thumbnails and extract missing ones in the background
+    fn request_thumbnails(&mut self, cx: &mut Context<Self>) {
+        for clip in &self.clips {
+            if clip.media_type != MediaType::Video {
+                continue;
+            }
+            // The cache name changes with the file's mtime, so edited files regenerate
+            let Some(cache_path) = thumbnails::cache_path(&clip.path) else {
+                continue;
+            };
+            if self.thumbnails.get(&clip.id) == Some(&cache_path) {
+                continue;
+            }
+            if cache_path.exists() {
+                self.thumbnails.insert(clip.id.clone(), cache_path);
+                continue;
+            }
+            if !self.thumbnails_requested.insert(cache_path) {
+                continue;
+            }
+            
+            let clip_id = clip.id.clone();
+            let source = clip.path.clone();
+            let task = cx.background_executor().spawn(async move {
+                thumbnails::get_or_create(&source)
+            });
+            
+            cx.spawn(async move |this, cx| {
+                let result = task.await;
+                let _ = this.update(cx, |this, cx| match result {
+                    Ok(path) => {
+                        this.thumbnails.insert(clip_id, path);
+                        cx.notify();
+                    }
+                    Err(e) => tracing::warn!("No thumbnail for clip {}: {}", clip_id, e),
+                });
+            })
+            .detach();
+        }
+    }
+    
+    /// Colors to draw the panel with
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
     }
     
     /// Set the selected clip
-    #[allow(dead_code)]
     pub fn set_selected(&mut self, id: Option<String>) {
-        self.selected_id = id;
+        self.selected = id.iter().cloned().collect();
+        self.selection_anchor = id;
+    }
+    
+    /// Update the selection for a click on the clip at `index`
+    ///
+    /// Plain click selects just that clip, cmd/ctrl-click toggles it and
+    /// shift-click selects everything between it and the anchor.
+    fn click_select(&mut self, index: usize, modifiers: Modifiers, cx: &mut Context<Self>) {
+        let Some(id) = self.clips.get(index).map(|c| c.id.clone()) else {
+            return;
+        };
+        let anchor = self.selection_anchor
+            .as_ref()
+            .and_then(|anchor| self.clips.iter().position(|c| c.id == *anchor));
+        
+        if modifiers.shift && let Some(anchor) = anchor {
+            // Only what the filter shows, so Delete never reaches hidden clips
+            let (first, last) = (anchor.min(index), anchor.max(index));
+            self.selected = self.clips[first..=last]
+                .iter()
+                .filter(|c| self.filter.matches(c))
+                .map(|c| c.id.clone())
+                .collect();
+        } else if modifiers.platform || modifiers.control {
+            if !self.selected.remove(&id) {
+                self.selected.insert(id.clone());
+            }
+            self.selection_anchor = Some(id);
+        } else {
+            self.selected = HashSet::from([id.clone()]);
+            self.selection_anchor = Some(id.clone());
+            cx.emit(ClipsPanelEvent::SelectClip(id));
+        }
+        cx.notify();
+    }
+    
+    /// Move a clip one place up or down, taking the selection along when
+    /// the clip is part of it
+    fn move_clip(&mut self, clip_id: &str, up: bool, cx: &mut Context<Self>) {
+        let indices = self.selected_indices();
+        if indices.len() < 2 || !self.selected.contains(clip_id) {
+            let id = clip_id.to_string();
+            cx.emit(if up { ClipsPanelEvent::MoveUp(id) } else { ClipsPanelEvent::MoveDown(id) });
+            return;
+        }
+        let (first, last) = (indices[0], indices[indices.len() - 1]);
+        let slot = if up {
+            first.checked_sub(1)
+        } else {
+            (last + 1 < self.clips.len()).then_some(last + 2)
+        };
+        if let Some(slot) = slot {
+            cx.emit(ClipsPanelEvent::MoveMany { indices, slot });
+        }
+    }
+    
+    /// Tags in use, in the order they first appear
+    fn tags(&self) -> Vec<ClipLabel> {
+        let mut tags = Vec::new();
+        for label in self.clips.iter().filter_map(|c| c.label.as_ref()) {
+            if matches!(label, ClipLabel::Tag(_)) && !tags.contains(label) {
+                tags.push(label.clone());
+            }
+        }
+        tags
+    }
+    
+    /// Label the clip, or the whole selection when the clip is part of it
+    fn set_label(&mut self, clip_id: &str, label: Option<ClipLabel>, cx: &mut Context<Self>) {
+        let ids = if self.selected.contains(clip_id) {
+            self.selected_indices().into_iter().map(|i| self.clips[i].id.clone()).collect()
+        } else {
+            vec![clip_id.to_string()]
+        };
+        self.label_menu = None;
+        cx.emit(ClipsPanelEvent::SetLabel { ids, label });
+        cx.notify();
+    }
+    
+    /// Swatches for picking a clip's label, shown under its title
+    fn render_label_menu(&self, clip: &Clip, cx: &mut Context<Self>) -> AnyElement {
+        let theme = self.theme;
+        let choices = ClipLabel::COLORS.into_iter().chain(self.tags());
+        let swatches = choices.enumerate().map(|(i, label)| {
+            let clip_id = clip.id.clone();
+            let is_current = clip.label.as_ref() == Some(&label);
+            let swatch = div()
+                .id(SharedString::from(format!("label-{}-{}", clip.id, i)))
+                .cursor_pointer()
+                .border_1()
+                .border_color(if is_current { rgb(theme.text) } else { rgb(theme.background) });
+            let swatch = match label {
+                ClipLabel::Tag(ref tag) => swatch
+                    .px_1()
+                    .rounded_sm()
+                    .text_xs()
+                    .text_color(rgb(theme.text_dim))
+                    .bg(rgb(theme.control_hover))
+                    .child(tag.clone()),
+                _ => swatch.size(px(12.0)).rounded_full().bg(rgb(label.rgb())),
+            };
+            swatch
+                .on_click(cx.listener(move |this, _event: &ClickEvent, _window, cx| {
+                    cx.stop_propagation();
+                    this.set_label(&clip_id, Some(label.clone()), cx);
+                }))
+                .into_any_element()
+        });
+        let clip_id = clip.id.clone();
+        
+        div()
+            .flex()
+            .flex_wrap()
+            .items_center()
+            .gap_1()
+            .children(swatches)
+            .child(
+                div()
+                    .id(SharedString::from(format!("label-clear-{}", clip.id)))
+                    .text_xs()
+                    .text_color(rgb(theme.text_muted))
+                    .cursor_pointer()
+                    .hover(|s| s.text_color(rgb(0xff6b6b)))
+                    .child("none")
+                    .on_click(cx.listener(move |this, _event: &ClickEvent, _window, cx| {
+                        cx.stop_propagation();
+                        this.set_label(&clip_id, None, cx);
+                    }))
+            )
+            .into_any_element()
+    }
+    
+    /// Chips along the top for showing one label or media type only
+    fn render_filter_row(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = self.theme;
+        let mut filters: Vec<(ClipFilter, Option<u32>, SharedString)> = vec![
+            (ClipFilter::All, None, "All".into()),
+            (ClipFilter::Media(MediaType::Video), None, "🎬".into()),
+            (ClipFilter::Media(MediaType::Image), None, "🖼️".into()),
+            (ClipFilter::Media(MediaType::Audio), None, "🎵".into()),
+        ];
+        for label in ClipLabel::COLORS.into_iter().chain(self.tags()) {
+            let color = (!matches!(label, ClipLabel::Tag(_))).then(|| label.rgb());
+            let name = label.name().to_string().into();
+            filters.push((ClipFilter::Label(label), color, name));
+        }
+        
+        let chips = filters.into_iter().enumerate().map(|(i, (filter, color, name))| {
+            let is_active = self.filter == filter;
+            let chip = div()
+                .id(("clip-filter", i))
+                .cursor_pointer()
+                .border_1()
+                .border_color(if is_active { rgb(theme.accent) } else { rgb(theme.control) });
+            let chip = match color {
+                Some(color) => chip.size(px(12.0)).rounded_full().bg(rgb(color)),
+                None => chip
+                    .px_1()
+                    .rounded_sm()
+                    .text_xs()
+                    .text_color(if is_active { rgb(theme.text) } else { rgb(theme.text_muted) })
+                    .child(name),
+            };
+            chip.on_click(cx.listener(move |this, _event: &ClickEvent, _window, cx| {
+                this.filter = filter.clone();
+                this.deselect_hidden();
+                cx.notify();
+            }))
+        });
+        
+        div()
+            .flex()
+            .flex_wrap()
+            .items_center()
+            .gap_1()
+            .px_2()
+            .py_1()
+            .border_b_1()
+            .border_color(rgb(theme.control))
+            .children(chips)
+    }
+    
+    /// Show the gap a drag over the clip at `index` would land in: the
+    /// upper half drops before it, the lower half after it
+    fn hover_slot(&mut self, index: usize, bounds: Bounds<Pixels>, position: Point<Pixels>, cx: &mut Context<Self>) {
+        if !bounds.contains(&position) {
+            return;
+        }
+        let slot = if position.y < bounds.center().y { index } else { index + 1 };
+        if self.drop_slot != Some(slot) {
+            self.drop_slot = Some(slot);
+            cx.notify();
+        }
+    }
+    
+    /// Drop clips that are gone or that the filter hides from the selection
+    fn deselect_hidden(&mut self) {
+        let visible: HashSet<&str> = self.clips
+            .iter()
+            .filter(|c| self.filter.matches(c))
+            .map(|c| c.id.as_str())
+            .collect();
+        self.selected.retain(|id| visible.contains(id.as_str()));
+        if self.selection_anchor.as_ref().is_some_and(|id| !visible.contains(id.as_str())) {
+            self.selection_anchor = None;
+        }
+    }
+    
+    /// Selected clips' indices in timeline order
+    fn selected_indices(&self) -> Vec<usize> {
+        self.clips
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| self.selected.contains(&c.id))
+            .map(|(i, _)| i)
+            .collect()
     }
     
     fn render_clip(&self, clip: &Clip, index: usize, total: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = self.theme;
         let clip_id = clip.id.clone();
         let clip_id_for_select = clip.id.clone();
+        let description = clip.description.clone();
         let clip_id_for_delete = clip.id.clone();
+        let clip_id_for_duplicate = clip.id.clone();
         let clip_id_for_up = clip.id.clone();
         let clip_id_for_down = clip.id.clone();
-        let is_selected = self.selected_id.as_ref() == Some(&clip.id);
+        let is_selected = self.selected.contains(&clip.id);
         let is_first = index == 0;
         let is_last = index == total - 1;
+        let title = if clip.description.is_empty() {
+            "Untitled".to_string()
+        } else {
+            clip.description.clone()
+        };
+        // Dragging a selected clip carries the whole selection along
+        let dragged = if is_selected && self.selected.len() > 1 {
+            let indices = self.selected_indices();
+            DraggedClip {
+                label: format!("{} clips", indices.len()).into(),
+                indices,
+                theme,
+            }
+        } else {
+            DraggedClip {
+                indices: vec![index],
+                label: title.clone().into(),
+                theme,
+            }
+        };
+        let rename_field = self.renaming
+            .as_ref()
+            .filter(|r| r.clip_id == clip.id)
+            .map(|r| self.render_rename_field(r, cx));
+        let label_menu = (self.label_menu.as_ref() == Some(&clip.id))
+            .then(|| self.render_label_menu(clip, cx));
+        let clip_id_for_menu = clip.id.clone();
+        let clip_id_for_swatch = clip.id.clone();
+        let label_color = clip.label.as_ref().map(ClipLabel::rgb);
         
         let icon = match clip.media_type {
             MediaType::Video => "🎬",
@@ -58,25 +570,73 @@ impl ClipsPanel {
             MediaType::Image => "🖼️",
         };
         
-        let file_name = clip.path
+        let mut file_name = clip.path
             .file_name()
             .map(|n| n.to_string_lossy().to_string())
             .unwrap_or_else(|| "Unknown".to_string());
+        if clip.media_type == MediaType::Image {
+            let seconds = clip.duration.unwrap_or(Clip::DEFAULT_STILL_DURATION);
+            file_name.push_str(&format!(" · {}s", seconds));
+        } else if let Some(seconds) = clip.duration {
+            let seconds = seconds.round() as u64;
+            file_name.push_str(&format!(" · {}:{:02}", seconds / 60, seconds % 60));
+        }
+        
+        // The thumbnail stands in for the icon once it's ready
+        let thumbnail = self.thumbnails.get(&clip.id).cloned();
+        let has_thumbnail = thumbnail.is_some();
         
         div()
             .id(SharedString::from(clip.id.clone()))
             .w_full()
             .p_2()
             .mb_1()
-            .bg(if is_selected { rgb(0x3a3a3a) } else { rgb(0x2a2a2a) })
+            .bg(if is_selected { rgb(theme.control_hover) } else { rgb(theme.surface) })
             .border_1()
-            .border_color(if is_selected { rgb(0x4fc3f7) } else { rgb(0x333333) })
+            .border_color(if is_selected { rgb(theme.accent) } else { rgb(theme.control) })
             .rounded_md()
             .cursor_pointer()
-            .hover(|s| s.bg(rgb(0x333333)))
-            .on_click(cx.listener(move |this, _event: &ClickEvent, _window, cx| {
-                this.selected_id = Some(clip_id_for_select.clone());
-                cx.emit(ClipsPanelEvent::SelectClip(clip_id_for_select.clone()));
+            .hover(|s| s.bg(rgb(theme.control)))
+            .on_click(cx.listener(move |this, event: &ClickEvent, window, cx| {
+                // Double-click renames in place
+                if event.click_count() == 2 {
+                    this.start_rename(clip_id_for_select.clone(), &description, window, cx);
+                    return;
+                }
+                this.focus_handle.focus(window, cx);
+                this.click_select(index, event.modifiers(), cx);
+            }))
+            // Right-click opens the label swatches
+            .on_mouse_down(MouseButton::Right, cx.listener(move |this, _event: &MouseDownEvent, _window, cx| {
+                this.label_menu = Some(clip_id_for_menu.clone());
+                cx.notify();
+            }))
+            .on_drag(dragged, |dragged, _offset, _window, cx| cx.new(|_| dragged.clone()))
+            .on_drag_move(cx.listener(move |this, event: &DragMoveEvent<DraggedClip>, _window, cx| {
+                this.hover_slot(index, event.bounds, event.event.position, cx);
+            }))
+            .on_drag_move(cx.listener(move |this, event: &DragMoveEvent<ExternalPaths>, _window, cx| {
+                this.hover_slot(index, event.bounds, event.event.position, cx);
+            }))
+            .on_drop(cx.listener(move |this, paths: &ExternalPaths, _window, cx| {
+                let index = this.drop_slot.take().unwrap_or(index);
+                cx.emit(ClipsPanelEvent::InsertFiles { index, paths: paths.paths().to_vec() });
+                cx.notify();
+            }))
+            .on_drop(cx.listener(move |this, dragged: &DraggedClip, _window, cx| {
+                let slot = this.drop_slot.take().unwrap_or(index);
+                match *dragged.indices.as_slice() {
+                    [from] => {
+                        let to = if slot > from { slot - 1 } else { slot };
+                        if to != from {
+                            cx.emit(ClipsPanelEvent::Reorder { from, to });
+                        }
+                    }
+                    _ => cx.emit(ClipsPanelEvent::MoveMany {
+                        indices: dragged.indices.clone(),
+                        slot,
+                    }),
+                }
                 cx.notify();
             }))
             .child(
@@ -95,29 +655,52 @@ impl ClipsPanel {
                                     .flex()
                                     .items_center()
                                     .gap_2()
+                                    // Label swatch, which also toggles the label menu
+                                    .child(
+                                        div()
+                                            .id(SharedString::from(format!("swatch-{}", clip_id)))
+                                            .size(px(8.0))
+                                            .flex_none()
+                                            .rounded_full()
+                                            .cursor_pointer()
+                                            .map(|d| match label_color {
+                                                Some(color) => d.bg(rgb(color)),
+                                                None => d.border_1().border_color(rgb(theme.text_muted)),
+                                            })
+                                            .on_click(cx.listener(move |this, _event: &ClickEvent, _window, cx| {
+                                                cx.stop_propagation();
+                                                this.label_menu = match this.label_menu.take() {
+                                                    Some(open) if open == clip_id_for_swatch => None,
+                                                    _ => Some(clip_id_for_swatch.clone()),
+                                                };
+                                                cx.notify();
+                                            }))
+                                    )
                                     // Order number
                                     .child(
                                         div()
                                             .text_xs()
-                                            .text_color(rgb(0x555555))
+                                            .text_color(rgb(theme.text_muted))
                                             .child(format!("{}.", index + 1))
                                     )
-                                    .child(div().text_sm().child(icon))
-                                    .child(
-                                        div()
+                                    .when(!has_thumbnail, |d| d.child(div().text_sm().child(icon)))
+                                    // The file couldn't be opened; export leaves it out
+                                    .when(clip.error.is_some(), |d| {
+                                        d.child(div().text_sm().text_color(rgb(0xff6b6b)).child("⚠"))
+                                    })
+                                    .child(match rename_field {
+                                        Some(field) => field,
+                                        None => div()
                                             .text_sm()
                                             .font_weight(FontWeight::MEDIUM)
-                                            .text_color(rgb(0xffffff))
+                                            .text_color(rgb(theme.text))
                                             .overflow_hidden()
                                             .max_w(px(100.0))
-                                            .child(if clip.description.is_empty() {
-                                                "Untitled".to_string()
-                                            } else {
-                                                clip.description.clone()
-                                            })
-                                    )
+                                            .child(title)
+                                            .into_any_element(),
+                                    })
                             )
-                            // Controls: up, down, delete
+                            // Controls: up, down, duplicate, delete
                             .child(
                                 div()
                                     .flex()
@@ -128,13 +711,13 @@ impl ClipsPanel {
                                         div()
                                             .id(SharedString::from(format!("up-{}", clip_id)))
                                             .text_xs()
-                                            .text_color(if is_first { rgb(0x444444) } else { rgb(0x666666) })
+                                            .text_color(if is_first { rgb(theme.control_hover) } else { rgb(theme.text_muted) })
                                             .cursor(if is_first { CursorStyle::default() } else { CursorStyle::PointingHand })
-                                            .hover(|s| if is_first { s } else { s.text_color(rgb(0x4fc3f7)) })
+                                            .hover(|s| if is_first { s } else { s.text_color(rgb(theme.accent)) })
                                             .child("▲")
-                                            .on_click(cx.listener(move |_this, _event: &ClickEvent, _window, cx| {
+                                            .on_click(cx.listener(move |this, _event: &ClickEvent, _window, cx| {
                                                 if !is_first {
-                                                    cx.emit(ClipsPanelEvent::MoveUp(clip_id_for_up.clone()));
+                                                    this.move_clip(&clip_id_for_up, true, cx);
                                                 }
                                             }))
                                     )
@@ -143,22 +726,35 @@ impl ClipsPanel {
                                         div()
                                             .id(SharedString::from(format!("down-{}", clip_id_for_down.clone())))
                                             .text_xs()
-                                            .text_color(if is_last { rgb(0x444444) } else { rgb(0x666666) })
+                                            .text_color(if is_last { rgb(theme.control_hover) } else { rgb(theme.text_muted) })
                                             .cursor(if is_last { CursorStyle::default() } else { CursorStyle::PointingHand })
-                                            .hover(|s| if is_last { s } else { s.text_color(rgb(0x4fc3f7)) })
+                                            .hover(|s| if is_last { s } else { s.text_color(rgb(theme.accent)) })
                                             .child("▼")
-                                            .on_click(cx.listener(move |_this, _event: &ClickEvent, _window, cx| {
+                                            .on_click(cx.listener(move |this, _event: &ClickEvent, _window, cx| {
                                                 if !is_last {
-                                                    cx.emit(ClipsPanelEvent::MoveDown(clip_id_for_down.clone()));
+                                                    this.move_clip(&clip_id_for_down, false, cx);
                                                 }
                                             }))
                                     )
+                                    // Duplicate
+                                    .child(
+                                        div()
+                                            .id(SharedString::from(format!("duplicate-{}", clip_id)))
+                                            .text_xs()
+                                            .text_color(rgb(theme.text_muted))
+                                            .cursor_pointer()
+                                            .hover(|s| s.text_color(rgb(theme.accent)))
+                                            .child("⧉")
+                                            .on_click(cx.listener(move |_this, _event: &ClickEvent, _window, cx| {
+                                                cx.emit(ClipsPanelEvent::DuplicateClip(clip_id_for_duplicate.clone()));
+                                            }))
+                                    )
                                     // Delete
                                     .child(
                                         div()
                                             .id(SharedString::from(format!("delete-{}", clip_id_for_delete.clone())))
                                             .text_xs()
-                                            .text_color(rgb(0x666666))
+                                            .text_color(rgb(theme.text_muted))
                                             .cursor_pointer()
                                             .hover(|s| s.text_color(rgb(0xff6b6b)))
                                             .child("×")
@@ -168,37 +764,111 @@ impl ClipsPanel {
                                     )
                             )
                     )
+                    .when_some(label_menu, |d, menu| d.child(menu))
+                    .when_some(thumbnail, |d, path| {
+                        d.child(
+                            img(path)
+                                .w(px(thumbnails::WIDTH as f32))
+                                .h(px(thumbnails::HEIGHT as f32))
+                                .rounded_sm()
+                                .object_fit(ObjectFit::Cover)
+                        )
+                    })
                     // File name
                     .child(
                         div()
                             .text_xs()
-                            .text_color(rgb(0x666666))
+                            .text_color(rgb(theme.text_muted))
                             .overflow_hidden()
                             .child(file_name)
                     )
+                    // Attribution for downloaded footage
+                    .when_some(clip.source.as_ref(), |d, source| {
+                        d.child(
+                            div()
+                                .id(SharedString::from(format!("source-{}", clip.id)))
+                                .text_xs()
+                                .text_color(rgb(theme.text_muted))
+                                .overflow_hidden()
+                                .child(format!("© {}", source.credit()))
+                                .when(!source.source_url.is_empty(), |d| {
+                                    let url = source.source_url.clone();
+                                    d.cursor_pointer()
+                                        .hover(|s| s.text_color(rgb(theme.text_dim)))
+                                        .on_click(move |_event, _window, cx| cx.open_url(&url))
+                                })
+                        )
+                    })
+                    .when_some(clip.error.clone(), |d, error| {
+                        d.child(div().text_xs().text_color(rgb(0xff6b6b)).child(error))
+                    })
             )
     }
 }
 
+/// Line showing where a dragged clip will land
+fn drop_indicator(color: u32) -> AnyElement {
+    div()
+        .w_full()
+        .h(px(2.0))
+        .mb_1()
+        .bg(rgb(color))
+        .into_any_element()
+}
+
 impl Render for ClipsPanel {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = self.theme;
+        // A drag that ended outside the list leaves a stale slot behind
+        if !cx.has_active_drag() {
+            self.drop_slot = None;
+        }
+        
         // Pre-render clips to avoid closure lifetime issues
         let total = self.clips.len();
-        let clip_elements: Vec<AnyElement> = self.clips
-            .iter()
-            .enumerate()
-            .map(|(i, c)| self.render_clip(c, i, total, cx).into_any_element())
-            .collect();
-        let clips_count = total;
+        let mut clip_elements: Vec<AnyElement> = Vec::with_capacity(total + 1);
+        for (i, clip) in self.clips.iter().enumerate() {
+            if !self.filter.matches(clip) {
+                continue;
+            }
+            if self.drop_slot == Some(i) {
+                clip_elements.push(drop_indicator(theme.accent));
+            }
+            clip_elements.push(self.render_clip(clip, i, total, cx).into_any_element());
+        }
+        if total > 0 && self.drop_slot == Some(total) {
+            clip_elements.push(drop_indicator(theme.accent));
+        }
+        let shown = clip_elements.len();
+        let mut clips_count = if self.filter == ClipFilter::All {
+            total.to_string()
+        } else {
+            format!("{} of {}", self.clips.iter().filter(|c| self.filter.matches(c)).count(), total)
+        };
+        if self.selected.len() > 1 {
+            clips_count = format!("{} selected · {}", self.selected.len(), clips_count);
+        }
+        let filter_row = self.render_filter_row(cx);
         
         div()
+            .id("clips-panel")
+            .track_focus(&self.focus_handle)
+            .on_key_down(cx.listener(|this, event: &KeyDownEvent, _window, cx| {
+                if matches!(event.keystroke.key.as_str(), "delete" | "backspace") && !this.selected.is_empty() {
+                    let ids = this.selected_indices()
+                        .into_iter()
+                        .map(|i| this.clips[i].id.clone())
+                        .collect();
+                    cx.emit(ClipsPanelEvent::DeleteMany(ids));
+                }
+            }))
             .h_full()
             .w(px(200.0))
             .flex()
             .flex_col()
-            .bg(rgb(0x1e1e1e))
+            .bg(rgb(theme.background))
             .border_r_1()
-            .border_color(rgb(0x333333))
+            .border_color(rgb(theme.control))
             // Header
             .child(
                 div()
@@ -207,36 +877,49 @@ impl Render for ClipsPanel {
                     .justify_between()
                     .p_3()
                     .border_b_1()
-                    .border_color(rgb(0x333333))
+                    .border_color(rgb(theme.control))
                     .child(
                         div()
                             .text_sm()
                             .font_weight(FontWeight::BOLD)
-                            .text_color(rgb(0x888888))
+                            .text_color(rgb(theme.text_muted))
                             .child("CLIPS")
                     )
                     .child(
                         div()
                             .text_xs()
-                            .text_color(rgb(0x666666))
-                            .child(format!("{}", clips_count))
+                            .text_color(rgb(theme.text_muted))
+                            .child(clips_count)
                     )
             )
+            .when(total > 0, |d| d.child(filter_row))
             // Clips list
             .child(
                 div()
                     .flex_1()
                     .overflow_hidden()
                     .p_2()
-                    .child(if clip_elements.is_empty() {
+                    // Files dragged back out of the list would otherwise keep the line
+                    .on_drag_move(cx.listener(|this, event: &DragMoveEvent<ExternalPaths>, _window, cx| {
+                        if !event.bounds.contains(&event.event.position) && this.drop_slot.take().is_some() {
+                            cx.notify();
+                        }
+                    }))
+                    // Below the last clip, or into an empty list
+                    .on_drop(cx.listener(move |this, paths: &ExternalPaths, _window, cx| {
+                        let index = this.drop_slot.take().unwrap_or(total);
+                        cx.emit(ClipsPanelEvent::InsertFiles { index, paths: paths.paths().to_vec() });
+                        cx.notify();
+                    }))
+                    .child(if shown == 0 {
                         div()
                             .flex()
                             .items_center()
                             .justify_center()
                             .h_full()
                             .text_sm()
-                            .text_color(rgb(0x555555))
-                            .child("No clips yet")
+                            .text_color(rgb(theme.text_muted))
+                            .child(if total == 0 { "No clips yet" } else { "No matching clips" })
                             .into_any_element()
                     } else {
                         div()
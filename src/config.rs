@@ -1,6 +1,11 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::agent::{AgentBackend, AgentBackendKind, OllamaBackend, OllamaEndpoint, OpenAiCompatibleBackend};
+use crate::project::ExportPreferences;
+use crate::transcription::TranscribeOptions;
 
 /// App configuration stored between sessions
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -15,12 +20,220 @@ pub struct AppConfig {
     /// Pexels API key for stock footage
     #[serde(default)]
     pub pexels_api_key: Option<String>,
+    
+    /// Ollama model used by the agent (defaults to `DEFAULT_OLLAMA_MODEL`)
+    #[serde(default)]
+    pub ollama_model: Option<String>,
+    
+    /// Ollama server URL (defaults to `DEFAULT_OLLAMA_URL`)
+    #[serde(default)]
+    pub ollama_url: Option<String>,
+    
+    /// Which service answers agent requests (default: Ollama)
+    #[serde(default)]
+    pub agent_backend: AgentBackendKind,
+    
+    /// Seconds to wait for an agent reply, whichever the backend (defaults
+    /// to `DEFAULT_AGENT_TIMEOUT_SECS`); a timed-out request is retried once
+    #[serde(default)]
+    pub agent_timeout_secs: Option<u64>,
+    
+    /// Root of an OpenAI-compatible API (defaults to `DEFAULT_OPENAI_URL`)
+    #[serde(default)]
+    pub openai_base_url: Option<String>,
+    
+    /// Key for that API; local servers such as LM Studio don't need one
+    #[serde(default)]
+    pub openai_api_key: Option<String>,
+    
+    /// Model to ask that API for (defaults to `DEFAULT_OPENAI_MODEL`)
+    #[serde(default)]
+    pub openai_model: Option<String>,
+    
+    /// Whisper model name, e.g. "small" or "medium.en" (default: base)
+    #[serde(default)]
+    pub whisper_model: Option<String>,
+    
+    /// whisper.cpp model file, for models outside the usual folder
+    #[serde(default)]
+    pub whisper_model_path: Option<PathBuf>,
+    
+    /// Language the audio is spoken in, e.g. "fr" (default: detected)
+    #[serde(default)]
+    pub whisper_language: Option<String>,
+    
+    /// Stock clips downloaded at once (defaults to `DEFAULT_PARALLEL_DOWNLOADS`)
+    #[serde(default)]
+    pub parallel_downloads: Option<usize>,
+    
+    /// Transcript segments per auto-video keyword request, for long
+    /// transcripts (defaults to `DEFAULT_KEYWORD_BATCH`; 0 sends one request)
+    #[serde(default)]
+    pub keyword_batch_size: Option<usize>,
+    
+    /// Submitted prompts, oldest first
+    #[serde(default)]
+    pub prompt_history: Vec<String>,
+    
+    /// Export dialog choices for projects that haven't been exported yet
+    #[serde(default)]
+    pub default_export_settings: Option<ExportPreferences>,
+    
+    /// Color palette for the whole window (default: dark)
+    #[serde(default)]
+    pub theme: ThemeName,
+    
+    /// Ask before applying agent edits that remove or split clips (default: on)
+    #[serde(default)]
+    pub agent_confirm_destructive: Option<bool>,
+    
+    /// Where downloaded stock footage goes (default `~/.montage/media`)
+    #[serde(default)]
+    pub media_dir: Option<PathBuf>,
+    
+    /// Copy media that lives elsewhere next to the project when saving
+    #[serde(default)]
+    pub copy_media_into_project: bool,
+}
+
+/// The palettes a user can pick from
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeName {
+    #[default]
+    Dark,
+    /// Black background, white text and a yellow-on-gray waveform
+    HighContrast,
+    Light,
+}
+
+impl ThemeName {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Dark => "dark",
+            Self::HighContrast => "high contrast",
+            Self::Light => "light",
+        }
+    }
+    
+    /// Parse a theme name ("dark", "high contrast", "light"...)
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.trim().to_lowercase();
+        let name = name.strip_suffix(" theme").unwrap_or(&name).trim();
+        match name.replace(['-', '_'], " ").as_str() {
+            "dark" | "default" | "night" => Some(Self::Dark),
+            "high contrast" | "contrast" | "accessible" | "accessibility" => Some(Self::HighContrast),
+            "light" | "bright" | "day" => Some(Self::Light),
+            _ => None,
+        }
+    }
+    
+    pub fn theme(&self) -> Theme {
+        match self {
+            Self::Dark => Theme::DARK,
+            Self::HighContrast => Theme::HIGH_CONTRAST,
+            Self::Light => Theme::LIGHT,
+        }
+    }
+}
+
+/// UI colors as 0xRRGGBB, handed to each view that draws
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Theme {
+    /// Window background
+    pub background: u32,
+    /// Panels and dialogs
+    pub surface: u32,
+    /// Buttons, chips and dividers
+    pub control: u32,
+    pub control_hover: u32,
+    /// Chosen chip or toggled button
+    pub selected: u32,
+    /// Headings and primary text
+    pub text: u32,
+    /// Button labels and secondary text
+    pub text_dim: u32,
+    /// Hints and placeholders
+    pub text_muted: u32,
+    pub accent: u32,
+    pub accent_hover: u32,
+    /// Waveform bars after the playhead
+    pub waveform: u32,
+    /// Waveform bars before the playhead
+    pub waveform_played: u32,
+    pub playhead: u32,
+}
+
+impl Theme {
+    pub const DARK: Self = Self {
+        background: 0x1a1a1a,
+        surface: 0x252525,
+        control: 0x333333,
+        control_hover: 0x444444,
+        selected: 0x2f5f7f,
+        text: 0xdddddd,
+        text_dim: 0xcccccc,
+        text_muted: 0x888888,
+        accent: 0x4fc3f7,
+        accent_hover: 0x81d4fa,
+        waveform: 0x4fc3f7,
+        waveform_played: 0x81d4fa,
+        playhead: 0xffffff,
+    };
+    
+    /// Played and unplayed bars differ in both hue and brightness, not just shade
+    pub const HIGH_CONTRAST: Self = Self {
+        background: 0x000000,
+        surface: 0x101010,
+        control: 0x2a2a2a,
+        control_hover: 0x4a4a4a,
+        selected: 0x005a9e,
+        text: 0xffffff,
+        text_dim: 0xffffff,
+        text_muted: 0xc8c8c8,
+        accent: 0xffd600,
+        accent_hover: 0xffff4d,
+        waveform: 0x8c8c8c,
+        waveform_played: 0xffd600,
+        playhead: 0xff4081,
+    };
+    
+    pub const LIGHT: Self = Self {
+        background: 0xf2f2f2,
+        surface: 0xffffff,
+        control: 0xe0e0e0,
+        control_hover: 0xd0d0d0,
+        selected: 0xb3dcf5,
+        text: 0x1a1a1a,
+        text_dim: 0x333333,
+        text_muted: 0x6e6e6e,
+        accent: 0x0277bd,
+        accent_hover: 0x0288d1,
+        waveform: 0x90caf9,
+        waveform_played: 0x0d47a1,
+        playhead: 0x1a1a1a,
+    };
 }
 
 impl AppConfig {
     /// Maximum number of recent projects to remember
     const MAX_RECENT: usize = 10;
     
+    /// Small local models can take a while on CPU
+    pub const DEFAULT_AGENT_TIMEOUT_SECS: u64 = 60;
+    
+    /// Model used when none is configured
+    pub const DEFAULT_OLLAMA_MODEL: &'static str = "qwen2.5:3b";
+    
+    /// Ollama's default local address
+    pub const DEFAULT_OLLAMA_URL: &'static str = "http://localhost:11434";
+    
+    /// OpenAI's own API
+    pub const DEFAULT_OPENAI_URL: &'static str = "https://api.openai.com/v1";
+    
+    /// Cheap enough to send the whole project with every command
+    pub const DEFAULT_OPENAI_MODEL: &'static str = "gpt-4o-mini";
+    
     /// Get the config file path
     fn config_path() -> Result<PathBuf> {
         let config_dir = dirs::config_dir()
@@ -73,6 +286,20 @@ impl AppConfig {
         }
     }
     
+    /// Remember a submitted prompt for up-arrow recall
+    pub fn push_prompt_history(&mut self, text: String) {
+        if self.prompt_history.last() == Some(&text) {
+            return;
+        }
+        self.prompt_history.push(text);
+        let excess = self.prompt_history.len().saturating_sub(crate::prompt::HISTORY_LIMIT);
+        self.prompt_history.drain(..excess);
+        
+        if let Err(e) = self.save() {
+            tracing::warn!("Failed to save config: {}", e);
+        }
+    }
+    
     /// Set the Pexels API key
     pub fn set_pexels_api_key(&mut self, key: String) {
         self.pexels_api_key = Some(key);
@@ -81,6 +308,99 @@ impl AppConfig {
         }
     }
     
+    /// How long either agent backend waits for a reply
+    fn agent_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.agent_timeout_secs.unwrap_or(Self::DEFAULT_AGENT_TIMEOUT_SECS))
+    }
+    
+    /// Where the agent should send its requests
+    pub fn ollama_endpoint(&self) -> OllamaEndpoint {
+        OllamaEndpoint {
+            model: self.ollama_model
+                .clone()
+                .unwrap_or_else(|| Self::DEFAULT_OLLAMA_MODEL.to_string()),
+            timeout: self.agent_timeout(),
+            url: self.ollama_url
+                .clone()
+                .unwrap_or_else(|| Self::DEFAULT_OLLAMA_URL.to_string()),
+        }
+    }
+    
+    /// The backend `agent_backend` selects, ready to take requests
+    pub fn agent_backend(&self) -> Arc<dyn AgentBackend> {
+        match self.agent_backend {
+            AgentBackendKind::Ollama => Arc::new(OllamaBackend {
+                endpoint: self.ollama_endpoint(),
+            }),
+            AgentBackendKind::OpenAiCompatible => Arc::new(OpenAiCompatibleBackend {
+                api_key: self.openai_api_key.clone(),
+                base_url: self.openai_base_url
+                    .clone()
+                    .unwrap_or_else(|| Self::DEFAULT_OPENAI_URL.to_string()),
+                model: self.openai_model
+                    .clone()
+                    .unwrap_or_else(|| Self::DEFAULT_OPENAI_MODEL.to_string()),
+                timeout: self.agent_timeout(),
+            }),
+        }
+    }
+    
+    /// How many stock clips auto-video fetches at once
+    pub fn parallel_downloads(&self) -> usize {
+        self.parallel_downloads
+            .unwrap_or(crate::auto_video::DEFAULT_PARALLEL_DOWNLOADS)
+            .max(1)
+    }
+    
+    /// Folder for downloaded media; kept out of the system temp dir so
+    /// saved projects don't lose their clips when it gets cleaned
+    pub fn media_dir(&self) -> PathBuf {
+        self.media_dir.clone().unwrap_or_else(|| {
+            dirs::home_dir()
+                .map(|home| home.join(".montage").join("media"))
+                .unwrap_or_else(|| std::env::temp_dir().join("montage_media"))
+        })
+    }
+    
+    /// How many transcript segments each keyword request covers
+    pub fn keyword_batch_size(&self) -> usize {
+        self.keyword_batch_size.unwrap_or(crate::auto_video::DEFAULT_KEYWORD_BATCH)
+    }
+    
+    /// How Whisper should be run for a plain transcription
+    pub fn transcribe_options(&self) -> TranscribeOptions {
+        TranscribeOptions {
+            model: self.whisper_model.clone(),
+            model_path: self.whisper_model_path.clone(),
+            language: self.whisper_language.clone(),
+            ..Default::default()
+        }
+    }
+    
+    /// Set the model used by the agent, on whichever backend is active
+    pub fn set_agent_model(&mut self, model: String) {
+        match self.agent_backend {
+            AgentBackendKind::Ollama => self.ollama_model = Some(model),
+            AgentBackendKind::OpenAiCompatible => self.openai_model = Some(model),
+        }
+        if let Err(e) = self.save() {
+            tracing::warn!("Failed to save config: {}", e);
+        }
+    }
+    
+    /// Switch palettes and remember the choice
+    pub fn set_theme(&mut self, theme: ThemeName) {
+        self.theme = theme;
+        if let Err(e) = self.save() {
+            tracing::warn!("Failed to save config: {}", e);
+        }
+    }
+    
+    /// Whether risky agent edits wait for Apply
+    pub fn confirm_destructive(&self) -> bool {
+        self.agent_confirm_destructive.unwrap_or(true)
+    }
+    
     /// Check if Pexels API key is configured
     #[allow(dead_code)]
     pub fn has_pexels_key(&self) -> bool {
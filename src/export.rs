@@ -1,10 +1,503 @@
 use anyhow::{Context, Result};
 use gstreamer as gst;
 use gstreamer::prelude::*;
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
 
-use crate::project::{Clip, MediaType, Project};
+use crate::pexels::Orientation;
+use crate::project::{AudioTrack, Clip, Marker, MediaType, Project};
+use crate::transcription;
+
+/// Output frame sizes offered in the export dialog
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExportPreset {
+    /// Any size, e.g. from "export at 1280x720"
+    Custom { height: u32, width: u32 },
+    /// 1920x1080 (16:9)
+    Landscape1080p,
+    /// 1080x1080 (1:1)
+    Square1x1,
+    /// 1080x1920 (9:16), for TikTok/Reels/Shorts
+    Vertical9x16,
+}
+
+impl ExportPreset {
+    /// Output size as (width, height)
+    pub fn dimensions(&self) -> (u32, u32) {
+        match *self {
+            Self::Custom { height, width } => (width, height),
+            Self::Landscape1080p => (1920, 1080),
+            Self::Square1x1 => (1080, 1080),
+            Self::Vertical9x16 => (1080, 1920),
+        }
+    }
+    
+    /// Stock footage orientation that fits this frame best
+    pub fn orientation(&self) -> Orientation {
+        let (width, height) = self.dimensions();
+        Orientation::of(width, height)
+    }
+    
+    /// How clips should be fitted when the user didn't say: mostly-landscape
+    /// stock footage looks better cropped than boxed into a tall frame
+    pub fn default_aspect_mode(&self) -> AspectMode {
+        match self {
+            Self::Square1x1 | Self::Vertical9x16 => AspectMode::CropToFill,
+            Self::Custom { .. } | Self::Landscape1080p => AspectMode::Letterbox,
+        }
+    }
+    
+    pub fn label(&self) -> String {
+        match self {
+            Self::Custom { height, width } => format!("{}x{}", width, height),
+            Self::Landscape1080p => "Landscape 16:9".to_string(),
+            Self::Square1x1 => "Square 1:1".to_string(),
+            Self::Vertical9x16 => "Vertical 9:16".to_string(),
+        }
+    }
+    
+    /// Parse a preset name ("vertical", "9:16", "square", "1280x720"...)
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.trim().to_lowercase();
+        match name.as_str() {
+            "landscape" | "landscape 16:9" | "horizontal" | "16:9" | "1080p" => Some(Self::Landscape1080p),
+            "square" | "square 1:1" | "1:1" | "instagram" => Some(Self::Square1x1),
+            "vertical" | "vertical 9:16" | "portrait" | "9:16" | "tiktok" | "reels" | "shorts" => Some(Self::Vertical9x16),
+            _ => {
+                let (width, height) = name.split_once('x')?;
+                let width: u32 = width.trim().parse().ok()?;
+                let height: u32 = height.trim().parse().ok()?;
+                (width > 0 && height > 0).then_some(Self::Custom { height, width })
+            }
+        }
+    }
+}
+
+/// Output resolution, as the short side of the frame; the preset decides
+/// the shape
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Resolution {
+    P720,
+    #[default]
+    P1080,
+    P1440,
+    P4K,
+}
+
+impl Resolution {
+    /// Pixels along the frame's shorter side
+    pub fn short_side(&self) -> u32 {
+        match self {
+            Self::P720 => 720,
+            Self::P1080 => 1080,
+            Self::P1440 => 1440,
+            Self::P4K => 2160,
+        }
+    }
+    
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::P720 => "720p",
+            Self::P1080 => "1080p",
+            Self::P1440 => "1440p",
+            Self::P4K => "4K",
+        }
+    }
+    
+    /// Parse a resolution name ("720p", "full hd", "4k"...)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "720" | "720p" | "hd" => Some(Self::P720),
+            "1080" | "1080p" | "full hd" | "fhd" => Some(Self::P1080),
+            "1440" | "1440p" | "2k" | "qhd" => Some(Self::P1440),
+            "2160" | "2160p" | "4k" | "uhd" => Some(Self::P4K),
+            _ => None,
+        }
+    }
+}
+
+/// Encoding quality: constant quality (CRF) for x264, and a bitrate scaled
+/// to the frame size for hardware encoders
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Quality {
+    Low,
+    Medium,
+    High,
+}
+
+impl Quality {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Low => "Low",
+            Self::Medium => "Medium",
+            Self::High => "High",
+        }
+    }
+    
+    /// Parse a quality name ("low", "high", "best"...)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "low" | "draft" | "small" => Some(Self::Low),
+            "medium" | "normal" | "standard" => Some(Self::Medium),
+            "high" | "best" | "max" => Some(Self::High),
+            _ => None,
+        }
+    }
+    
+    /// x264 constant rate factor; lower is better
+    fn crf(&self) -> u32 {
+        match self {
+            Self::Low => 28,
+            Self::Medium => 23,
+            Self::High => 18,
+        }
+    }
+    
+    /// Bitrate for a frame size at 30 fps, in kbps (about 6 Mbps for
+    /// 1080p at medium)
+    fn bitrate_kbps(&self, width: u32, height: u32) -> u32 {
+        let bits_per_pixel = match self {
+            Self::Low => 0.05,
+            Self::Medium => 0.1,
+            Self::High => 0.15,
+        };
+        (f64::from(width) * f64::from(height) * 30.0 * bits_per_pixel / 1000.0).round() as u32
+    }
+}
+
+/// Container and codecs of the exported file
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum OutputFormat {
+    /// H.264 video and AAC audio in MP4
+    #[default]
+    Mp4H264,
+    /// VP9 video and Opus audio in WebM
+    WebMVp9,
+    /// Animated GIF, without sound
+    Gif,
+}
+
+impl OutputFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Mp4H264 => "mp4",
+            Self::WebMVp9 => "webm",
+            Self::Gif => "gif",
+        }
+    }
+    
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Mp4H264 => "MP4",
+            Self::WebMVp9 => "WebM",
+            Self::Gif => "GIF",
+        }
+    }
+    
+    /// Parse a format name ("mp4", "webm", "gif"...)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().trim_start_matches('.').to_lowercase().as_str() {
+            "mp4" | "h264" | "h.264" => Some(Self::Mp4H264),
+            "webm" | "vp9" => Some(Self::WebMVp9),
+            "gif" => Some(Self::Gif),
+            _ => None,
+        }
+    }
+    
+    /// Whether the file carries a soundtrack
+    pub fn has_audio(&self) -> bool {
+        *self != Self::Gif
+    }
+}
+
+/// How the encoder spends bits when no quality tier is chosen
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VideoQuality {
+    /// Fixed bitrate in kbps: predictable size, uneven quality
+    Bitrate(u32),
+    /// Constant quality (x264 CRF, lower is better): even quality, size varies.
+    /// Hardware encoders get a bitrate for the frame size instead.
+    Crf(u8),
+}
+
+impl Default for VideoQuality {
+    fn default() -> Self {
+        Self::Crf(20)
+    }
+}
+
+/// How clips whose aspect ratio differs from the output are fitted
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum AspectMode {
+    /// Scale up until the frame is covered, cropping the overflow (centered)
+    CropToFill,
+    /// Scale to fit inside the frame with black bars
+    #[default]
+    Letterbox,
+    /// Scale to the frame size, distorting the picture
+    Stretch,
+}
+
+impl AspectMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::CropToFill => "Crop to fill",
+            Self::Letterbox => "Letterbox",
+            Self::Stretch => "Stretch",
+        }
+    }
+    
+    /// Parse a mode name ("crop", "letterbox", "stretch"...)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "crop" | "fill" | "crop_to_fill" | "crop to fill" | "cover" => Some(Self::CropToFill),
+            "letterbox" | "fit" | "pad" | "contain" => Some(Self::Letterbox),
+            "stretch" => Some(Self::Stretch),
+            _ => None,
+        }
+    }
+}
+
+/// What to do with the transcript when exporting video
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SubtitleMode {
+    /// Draw the subtitles into the picture
+    Burned,
+    /// No subtitles
+    #[default]
+    None,
+    /// Write an `.srt` next to the video
+    Sidecar,
+}
+
+impl SubtitleMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Burned => "Burned in",
+            Self::None => "None",
+            Self::Sidecar => "Sidecar .srt",
+        }
+    }
+    
+    /// Parse a mode name ("burn", "srt", "none"...)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "burn" | "burned" | "burnt" | "burned_in" | "burned in" | "hardcoded" => Some(Self::Burned),
+            "none" | "off" | "no" => Some(Self::None),
+            "sidecar" | "sidecar .srt" | "srt" | "file" => Some(Self::Sidecar),
+            _ => None,
+        }
+    }
+}
+
+/// How one clip gives way to the next
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Transition {
+    /// Blend into the next clip over this many seconds
+    Crossfade(f64),
+    /// Hard cut
+    #[default]
+    Cut,
+}
+
+impl Transition {
+    /// Used when a crossfade is asked for without a length
+    pub const DEFAULT_CROSSFADE: f64 = 1.0;
+    
+    pub fn label(&self) -> String {
+        match self {
+            Self::Crossfade(seconds) => format!("Crossfade {}s", seconds),
+            Self::Cut => "Cut".to_string(),
+        }
+    }
+    
+    /// Parse "cut", "crossfade", "crossfade 0.5", "0.5s"...
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.trim().to_lowercase();
+        let seconds = name
+            .trim_start_matches(|c: char| c.is_alphabetic() || c.is_whitespace())
+            .trim_end_matches('s')
+            .trim();
+        match name.split_whitespace().next()? {
+            "cut" | "none" | "hard" => Some(Self::Cut),
+            word if seconds.is_empty() => matches!(word, "crossfade" | "fade" | "dissolve")
+                .then_some(Self::Crossfade(Self::DEFAULT_CROSSFADE)),
+            _ => seconds
+                .parse::<f64>()
+                .ok()
+                .filter(|s| *s > 0.0)
+                .map(Self::Crossfade),
+        }
+    }
+    
+    /// Fade length at each boundary between `durations.len()` clips. A clip
+    /// shorter than the fade hard-cuts instead, and no clip gives more than
+    /// half its length to a fade so fades never overlap.
+    pub fn fade_lengths(&self, durations: &[f64]) -> Vec<f64> {
+        let Self::Crossfade(fade) = *self else {
+            return vec![0.0; durations.len().saturating_sub(1)];
+        };
+        durations
+            .windows(2)
+            .map(|pair| {
+                if pair[0] < fade || pair[1] < fade {
+                    0.0
+                } else {
+                    fade.min(pair[0] / 2.0).min(pair[1] / 2.0)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Which H.264 encoder writes the video stream
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum VideoEncoder {
+    /// The first hardware encoder this machine has, else software
+    Auto,
+    /// NVIDIA NVENC
+    Nvenc,
+    /// x264 on the CPU
+    #[default]
+    Software,
+    /// VA-API (Intel and AMD GPUs on Linux)
+    Vaapi,
+    /// Apple VideoToolbox
+    VideoToolbox,
+}
+
+impl VideoEncoder {
+    /// Hardware encoders in the order `Auto` tries them
+    const HARDWARE: [Self; 3] = [Self::VideoToolbox, Self::Nvenc, Self::Vaapi];
+    
+    /// Render node FFmpeg uploads frames to for VA-API
+    const VAAPI_DEVICE: &'static str = "/dev/dri/renderD128";
+    
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Auto => "Auto",
+            Self::Nvenc => "NVENC",
+            Self::Software => "Software (x264)",
+            Self::Vaapi => "VA-API",
+            Self::VideoToolbox => "VideoToolbox",
+        }
+    }
+    
+    /// Parse an encoder name ("hardware", "nvenc", "x264"...)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "auto" | "hardware" | "hw" | "gpu" => Some(Self::Auto),
+            "nvenc" | "nvidia" | "cuda" => Some(Self::Nvenc),
+            "software" | "software (x264)" | "x264" | "libx264" | "cpu" => Some(Self::Software),
+            "vaapi" | "va-api" | "intel" | "amd" => Some(Self::Vaapi),
+            "videotoolbox" | "vt" | "apple" | "mac" => Some(Self::VideoToolbox),
+            _ => None,
+        }
+    }
+    
+    /// GStreamer element factory behind this encoder
+    fn gst_factory(&self) -> &'static str {
+        match self {
+            Self::Auto | Self::Software => "x264enc",
+            Self::Nvenc => "nvh264enc",
+            Self::Vaapi => "vaapih264enc",
+            Self::VideoToolbox => "vtenc_h264",
+        }
+    }
+    
+    /// Encoder element for a `gst::parse::launch` string, at `kbps` or,
+    /// for x264, at constant quality `crf` when given
+    fn gst_element(&self, kbps: u32, crf: Option<u32>) -> String {
+        // All four take kbit/s, but the hardware ones only hold it in CBR mode
+        match self {
+            Self::Auto | Self::Software => match crf {
+                Some(crf) => format!("x264enc pass=qual quantizer={}", crf),
+                None => format!("x264enc bitrate={}", kbps),
+            },
+            Self::Nvenc => format!("nvh264enc rc-mode=cbr bitrate={}", kbps),
+            Self::Vaapi => format!("vaapih264enc rate-control=cbr bitrate={}", kbps),
+            Self::VideoToolbox => format!("vtenc_h264 realtime=false bitrate={}", kbps),
+        }
+    }
+    
+    /// FFmpeg codec name behind this encoder
+    fn ffmpeg_codec(&self) -> &'static str {
+        match self {
+            Self::Auto | Self::Software => "libx264",
+            Self::Nvenc => "h264_nvenc",
+            Self::Vaapi => "h264_vaapi",
+            Self::VideoToolbox => "h264_videotoolbox",
+        }
+    }
+    
+    /// FFmpeg `-c:v` and friends, at `kbps` or, for x264, at constant
+    /// quality `crf` when given
+    fn ffmpeg_args(&self, kbps: u32, crf: Option<u32>) -> Vec<String> {
+        let codec = self.ffmpeg_codec();
+        let mut args = vec!["-c:v".to_string(), codec.to_string()];
+        match self {
+            Self::Auto | Self::Software => args.extend(["-preset".to_string(), "medium".to_string()]),
+            Self::Nvenc => args.extend(["-preset".to_string(), "p4".to_string()]),
+            Self::Vaapi | Self::VideoToolbox => {}
+        }
+        match crf {
+            Some(crf) if self.ffmpeg_codec() == "libx264" => args.extend(["-crf".to_string(), crf.to_string()]),
+            _ => args.extend(["-b:v".to_string(), format!("{}k", kbps)]),
+        }
+        args
+    }
+    
+    /// Frames must be uploaded to the GPU before `h264_vaapi` sees them
+    fn ffmpeg_needs_upload(&self) -> bool {
+        *self == Self::Vaapi
+    }
+    
+    /// The encoder to actually use, given which ones `is_available` accepts.
+    /// An unavailable hardware choice falls back to software with a warning.
+    fn resolve(self, is_available: impl Fn(Self) -> bool) -> Self {
+        match self {
+            Self::Software => self,
+            Self::Auto => {
+                let found = Self::HARDWARE.into_iter().find(|e| is_available(*e));
+                if found.is_none() {
+                    tracing::info!("No hardware H.264 encoder found; using x264");
+                }
+                found.unwrap_or(Self::Software)
+            }
+            _ if is_available(self) => self,
+            _ => {
+                tracing::warn!("{} encoder is not available; falling back to x264", self.label());
+                Self::Software
+            }
+        }
+    }
+    
+    /// Resolve against the GStreamer plugins installed here
+    fn resolve_gst(self) -> Self {
+        self.resolve(|e| gst::ElementFactory::find(e.gst_factory()).is_some())
+    }
+    
+    /// Resolve against the encoders this FFmpeg build lists
+    fn resolve_ffmpeg(self) -> Self {
+        if self == Self::Software {
+            return self;
+        }
+        let encoders = Command::new("ffmpeg")
+            .args(["-hide_banner", "-encoders"])
+            .output()
+            .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
+            .unwrap_or_default();
+        self.resolve(|e| {
+            let listed = encoders.split_whitespace().any(|word| word == e.ffmpeg_codec());
+            // VA-API is built in widely but useless without a render node
+            listed && (e != Self::Vaapi || Path::new(Self::VAAPI_DEVICE).exists())
+        })
+    }
+}
 
 /// Export settings
 #[derive(Clone, Debug)]
@@ -15,10 +508,48 @@ pub struct ExportSettings {
     pub width: u32,
     /// Video height (default: 1080)
     pub height: u32,
-    /// Video bitrate in kbps (default: 5000)
-    pub video_bitrate: u32,
+    /// How clips with a different aspect ratio are fitted (default: letterbox)
+    pub aspect_mode: AspectMode,
+    /// Seconds an image clip stays on screen unless it has its own duration (default: 5.0)
+    pub still_duration: f64,
+    /// Constant quality or a fixed bitrate (default: CRF 20)
+    pub video_quality: VideoQuality,
+    /// Named quality tier; overrides `video_quality` when set (default: none)
+    pub quality: Option<Quality>,
     /// Audio bitrate in kbps (default: 192)
     pub audio_bitrate: u32,
+    /// Volume of the clips' own audio when mixed under a voiceover (default: 1.0)
+    pub clip_audio_gain: f64,
+    /// Drop the clips' own audio entirely (talking head over b-roll)
+    pub mute_clip_audio: bool,
+    /// How far clip audio dips while the transcript has speech, in dB; 0 turns it off (default: 10)
+    pub ducking_db: f64,
+    /// Seconds clip audio takes to dip before speech starts (default: 0.3)
+    pub ducking_attack: f64,
+    /// Seconds clip audio takes to come back after speech ends (default: 0.6)
+    pub ducking_release: f64,
+    /// Volume of the project's main audio track (default: 1.0)
+    pub voiceover_gain: f64,
+    /// Subtitles from the project transcript (default: none)
+    pub subtitles: SubtitleMode,
+    /// Whisper model and language, when subtitles need a fresh transcript
+    pub whisper: transcription::TranscribeOptions,
+    /// Between consecutive clips (default: cut)
+    pub transition: Transition,
+    /// H.264 encoder; hardware ones fall back to x264 when missing (default: software)
+    pub encoder: VideoEncoder,
+    /// Bring the final mix to `LOUDNESS_TARGET_LUFS` (default: off)
+    pub normalize_audio: bool,
+    /// Turn timeline markers into chapters (default: off)
+    pub export_chapters: bool,
+    /// Only render this stretch of the timeline, as (start, end) seconds (default: all of it)
+    pub range: Option<(f64, f64)>,
+    /// Container and codecs (default: H.264 MP4)
+    pub format: OutputFormat,
+    /// Frames per second of a GIF export (default: 15)
+    pub gif_fps: u32,
+    /// Widest a GIF export gets; narrower frames keep their size (default: 480)
+    pub gif_max_width: u32,
 }
 
 impl Default for ExportSettings {
@@ -27,56 +558,840 @@ impl Default for ExportSettings {
             output_path: std::path::PathBuf::from("output.mp4"),
             width: 1920,
             height: 1080,
-            video_bitrate: 5000,
+            aspect_mode: AspectMode::default(),
+            still_duration: Clip::DEFAULT_STILL_DURATION,
+            video_quality: VideoQuality::default(),
+            quality: None,
             audio_bitrate: 192,
+            clip_audio_gain: 1.0,
+            mute_clip_audio: false,
+            ducking_db: 10.0,
+            ducking_attack: 0.3,
+            ducking_release: 0.6,
+            voiceover_gain: 1.0,
+            subtitles: SubtitleMode::default(),
+            whisper: transcription::TranscribeOptions::default(),
+            transition: Transition::default(),
+            encoder: VideoEncoder::default(),
+            normalize_audio: false,
+            export_chapters: false,
+            range: None,
+            format: OutputFormat::default(),
+            gif_fps: 15,
+            gif_max_width: 480,
+        }
+    }
+}
+
+impl ExportSettings {
+    /// Use a preset's output size
+    pub fn with_preset(mut self, preset: ExportPreset) -> Self {
+        (self.width, self.height) = preset.dimensions();
+        self
+    }
+    
+    /// Scale the frame so its short side matches `resolution`, keeping
+    /// its shape (and even dimensions, which H.264 needs)
+    pub fn with_resolution(mut self, resolution: Resolution) -> Self {
+        let scale = f64::from(resolution.short_side()) / f64::from(self.width.min(self.height).max(1));
+        let even = |side: u32| ((f64::from(side) * scale / 2.0).round() as u32 * 2).max(2);
+        (self.width, self.height) = (even(self.width), even(self.height));
+        self
+    }
+    
+    /// Bitrate in kbps, and the x264 CRF when encoding for constant quality
+    fn video_rate(&self) -> (u32, Option<u32>) {
+        match self.quality {
+            Some(quality) => (quality.bitrate_kbps(self.width, self.height), Some(quality.crf())),
+            None => match self.video_quality {
+                VideoQuality::Bitrate(kbps) => (kbps, None),
+                VideoQuality::Crf(crf) => (Quality::Medium.bitrate_kbps(self.width, self.height), Some(u32::from(crf))),
+            },
+        }
+    }
+    
+    /// The H.264 encoder to use, found with `resolve`; other formats only
+    /// have a software encoder
+    fn video_encoder(&self, resolve: impl FnOnce(VideoEncoder) -> VideoEncoder) -> VideoEncoder {
+        if self.format == OutputFormat::Mp4H264 {
+            return resolve(self.encoder);
+        }
+        if self.encoder != VideoEncoder::Software {
+            tracing::info!("{} export encodes in software", self.format.label());
+        }
+        VideoEncoder::Software
+    }
+    
+    /// GStreamer encoder element at this export's rate
+    fn gst_encoder(&self, encoder: VideoEncoder) -> String {
+        let (kbps, crf) = self.video_rate();
+        encoder.gst_element(kbps, crf)
+    }
+    
+    /// FFmpeg filter normalizing loudness, applied once to the whole mix
+    ///
+    /// Single-pass EBU R128 `loudnorm` aiming at `LOUDNESS_TARGET_LUFS`
+    /// integrated loudness with peaks kept under `TRUE_PEAK_LIMIT_DBTP`.
+    fn ffmpeg_loudness_filter(&self) -> Option<String> {
+        self.normalize_audio.then(|| {
+            format!(
+                "loudnorm=I={}:TP={}:LRA=11,aresample=48000",
+                LOUDNESS_TARGET_LUFS, TRUE_PEAK_LIMIT_DBTP
+            )
+        })
+    }
+    
+    /// GStreamer elements normalizing loudness, ending in a ` ! `; empty when
+    /// off or when `audioloudnorm` (gst-plugins-rs) isn't installed
+    fn gst_loudness_chain(&self) -> String {
+        if !self.normalize_audio {
+            return String::new();
+        }
+        if gst::ElementFactory::find("audioloudnorm").is_none() {
+            tracing::warn!("audioloudnorm not installed; exporting without loudness normalization");
+            return String::new();
         }
+        // audioloudnorm works on 192 kHz float samples
+        format!(
+            "audioconvert ! audioresample ! audioloudnorm loudness-target={} max-true-peak={} ! \
+             audioconvert ! audioresample ! ",
+            LOUDNESS_TARGET_LUFS, TRUE_PEAK_LIMIT_DBTP
+        )
+    }
+    
+    /// FFmpeg `-vf` chain fitting each frame into the output size
+    fn ffmpeg_scale_filter(&self) -> String {
+        let (w, h) = (self.width, self.height);
+        match self.aspect_mode {
+            AspectMode::CropToFill => format!(
+                "scale={w}:{h}:force_original_aspect_ratio=increase,crop={w}:{h},setsar=1"
+            ),
+            AspectMode::Letterbox => format!(
+                "scale={w}:{h}:force_original_aspect_ratio=decrease,pad={w}:{h}:(ow-iw)/2:(oh-ih)/2,setsar=1"
+            ),
+            AspectMode::Stretch => format!("scale={w}:{h},setsar=1"),
+        }
+    }
+    
+    /// GIF frame size: the output scaled down to `gif_max_width`, kept even
+    fn gif_size(&self) -> (u32, u32) {
+        let width = self.width.min(self.gif_max_width).max(2);
+        let height = (f64::from(self.height) * f64::from(width) / f64::from(self.width.max(1))).round() as u32;
+        (width / 2 * 2, (height / 2 * 2).max(2))
+    }
+    
+    /// FFmpeg filters turning finished frames into a GIF: fewer frames,
+    /// smaller, and a palette built from the whole clip
+    fn ffmpeg_gif_filter(&self) -> String {
+        let (w, h) = self.gif_size();
+        format!(
+            "fps={},scale={}:{}:flags=lanczos,split[g0][g1];[g0]palettegen[gp];[g1][gp]paletteuse",
+            self.gif_fps, w, h
+        )
+    }
+    
+    /// FFmpeg video codec arguments for `format`; only MP4 uses `encoder`
+    fn ffmpeg_video_args(&self, encoder: VideoEncoder) -> Vec<String> {
+        let (kbps, crf) = self.video_rate();
+        match self.format {
+            OutputFormat::Mp4H264 => encoder.ffmpeg_args(kbps, crf),
+            OutputFormat::WebMVp9 => {
+                let mut args: Vec<String> = ["-c:v", "libvpx-vp9", "-row-mt", "1"].map(String::from).to_vec();
+                match crf {
+                    // Constant quality needs the bitrate cap lifted
+                    Some(crf) => args.extend(["-crf".to_string(), vp9_crf(crf).to_string(), "-b:v".to_string(), "0".to_string()]),
+                    None => args.extend(["-b:v".to_string(), format!("{}k", kbps)]),
+                }
+                args
+            }
+            OutputFormat::Gif => vec!["-c:v".to_string(), "gif".to_string()],
+        }
+    }
+    
+    /// FFmpeg audio codec for `format`, if it has sound
+    fn ffmpeg_audio_codec(&self) -> Option<&'static str> {
+        match self.format {
+            OutputFormat::Mp4H264 => Some("aac"),
+            OutputFormat::WebMVp9 => Some("libopus"),
+            OutputFormat::Gif => None,
+        }
+    }
+    
+    /// GStreamer elements from raw video to what the muxer takes
+    fn gst_video_tail(&self, encoder: VideoEncoder) -> String {
+        match self.format {
+            OutputFormat::Mp4H264 => format!("{} ! h264parse", self.gst_encoder(encoder)),
+            OutputFormat::WebMVp9 => {
+                let (kbps, crf) = self.video_rate();
+                match crf {
+                    Some(crf) => format!("vp9enc end-usage=cq cq-level={} deadline=1 row-mt=true", vp9_crf(crf)),
+                    None => format!("vp9enc target-bitrate={} deadline=1 row-mt=true", kbps * 1000),
+                }
+            }
+            OutputFormat::Gif => {
+                let (w, h) = self.gif_size();
+                format!(
+                    "videorate ! video/x-raw,framerate={}/1 ! videoscale ! video/x-raw,width={},height={} ! \
+                     videoconvert ! gifenc",
+                    self.gif_fps, w, h
+                )
+            }
+        }
+    }
+    
+    /// GStreamer audio encoder for `format`, if it has sound
+    fn gst_audio_encoder(&self) -> Option<String> {
+        let bps = self.audio_bitrate * 1000;
+        match self.format {
+            OutputFormat::Mp4H264 => Some(format!("fdkaacenc bitrate={}", bps)),
+            OutputFormat::WebMVp9 => Some(format!("opusenc bitrate={}", bps)),
+            OutputFormat::Gif => None,
+        }
+    }
+    
+    /// GStreamer element named `mux` that writes the file; a GIF comes out
+    /// of its encoder ready to write
+    fn gst_muxer(&self) -> &'static str {
+        match self.format {
+            OutputFormat::Mp4H264 => "mp4mux",
+            OutputFormat::WebMVp9 => "webmmux",
+            OutputFormat::Gif => "identity",
+        }
+    }
+    
+    /// How long a clip lasts in the export, if known without probing
+    fn clip_duration(&self, clip: &Clip) -> Option<f64> {
+        match clip.media_type {
+            MediaType::Image => Some(clip.duration.unwrap_or(self.still_duration)),
+            _ => clip.duration,
+        }
+    }
+    
+    /// GStreamer elements fitting raw video into the output size
+    fn gst_scale_chain(&self) -> String {
+        let caps = format!(
+            "video/x-raw,width={},height={},pixel-aspect-ratio=1/1",
+            self.width, self.height
+        );
+        match self.aspect_mode {
+            // `aspectratiocrop` trims the sides (or top and bottom) evenly
+            AspectMode::CropToFill => format!(
+                "aspectratiocrop aspect-ratio={}/{} ! videoscale ! {}",
+                self.width, self.height, caps
+            ),
+            AspectMode::Letterbox => format!("videoscale add-borders=true ! {}", caps),
+            AspectMode::Stretch => format!("videoscale add-borders=false ! {}", caps),
+        }
+    }
+}
+
+/// VP9's quality scale runs higher than x264's for a similar look
+fn vp9_crf(x264_crf: u32) -> u32 {
+    (x264_crf + 12).min(63)
+}
+
+/// Clip audio volume that sits well under a voiceover
+pub const DUCKED_CLIP_GAIN: f64 = 0.2;
+
+/// Integrated loudness the mix is normalized to, in LUFS: the level most
+/// streaming platforms play web video at
+pub const LOUDNESS_TARGET_LUFS: f64 = -16.0;
+
+/// Ceiling for true peaks after normalization, in dBTP
+const TRUE_PEAK_LIMIT_DBTP: f64 = -1.5;
+
+/// Clip-audio gain over the timeline, dipping while the voiceover speaks
+#[derive(Clone, Debug)]
+struct DuckEnvelope {
+    /// Speech as (start, end) seconds, merged so no two dips overlap
+    speech: Vec<(f64, f64)>,
+    /// Linear gain at the bottom of a dip
+    floor: f64,
+    attack: f64,
+    release: f64,
+}
+
+impl DuckEnvelope {
+    /// Built from the project transcript, when there's a voiceover to duck
+    /// under, with the speech moved to where it lands once `cuts` are dropped
+    fn for_project(project: &Project, settings: &ExportSettings, cuts: &[(f64, f64)]) -> Option<Self> {
+        if settings.ducking_db <= 0.0 || settings.mute_clip_audio || project.audio.is_none() {
+            return None;
+        }
+        let transcript = project.current_transcript()?.retimed(|t| time_after_cuts(cuts, t));
+        let attack = settings.ducking_attack.max(0.01);
+        let release = settings.ducking_release.max(0.01);
+        
+        let mut speech: Vec<(f64, f64)> = Vec::new();
+        for segment in &transcript.segments {
+            match speech.last_mut() {
+                // Pauses shorter than a release and attack stay ducked
+                Some(last) if segment.start - attack <= last.1 + release => {
+                    last.1 = last.1.max(segment.end);
+                }
+                _ => speech.push((segment.start, segment.end)),
+            }
+        }
+        if speech.is_empty() {
+            return None;
+        }
+        
+        Some(Self {
+            speech,
+            floor: 10_f64.powf(-settings.ducking_db / 20.0),
+            attack,
+            release,
+        })
+    }
+    
+    /// Gain at `t` seconds into the timeline
+    fn gain_at(&self, t: f64) -> f64 {
+        let depth: f64 = self.speech
+            .iter()
+            .map(|&(start, end)| {
+                ((t - (start - self.attack)) / self.attack)
+                    .min((end + self.release - t) / self.release)
+                    .clamp(0.0, 1.0)
+            })
+            .sum();
+        1.0 - (1.0 - self.floor) * depth.min(1.0)
+    }
+    
+    /// The same curve as an FFmpeg `volume` expression of `t`
+    fn ffmpeg_expr(&self) -> String {
+        // Dips never overlap, so their depths can simply be added
+        let depth: Vec<String> = self.speech
+            .iter()
+            .map(|&(start, end)| {
+                format!(
+                    "clip(min((t-{:.3})/{:.3},({:.3}-t)/{:.3}),0,1)",
+                    start - self.attack, self.attack, end + self.release, self.release
+                )
+            })
+            .collect();
+        format!("1-{:.4}*({})", 1.0 - self.floor, depth.join("+"))
     }
 }
 
 /// Export progress callback
-pub type ProgressCallback = Box<dyn Fn(f64) + Send>;
+pub type ProgressCallback = Box<dyn Fn(ExportProgress) + Send>;
+
+/// What the encoders report: the fraction of the output written so far
+type FractionCallback = Box<dyn Fn(f64) + Send>;
+
+/// How far an export has got and how fast it is going
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ExportProgress {
+    /// Share of the output written (0.0 to 1.0)
+    pub fraction: f64,
+    /// Seconds of the output written
+    pub position_secs: f64,
+    /// Output seconds encoded per wall-clock second, e.g. 1.8 for "1.8× realtime"
+    pub speed: Option<f64>,
+    /// Wall-clock seconds until done at the current pace
+    pub eta_secs: Option<f64>,
+}
+
+impl ExportProgress {
+    /// Progress after `elapsed_secs` of encoding an output `total_secs` long
+    fn new(fraction: f64, total_secs: Option<f64>, elapsed_secs: f64) -> Self {
+        let fraction = fraction.clamp(0.0, 1.0);
+        let position_secs = total_secs.map_or(0.0, |total| fraction * total);
+        // The first second is mostly pipeline startup, too noisy to extrapolate
+        let settled = elapsed_secs >= 1.0 && fraction > 0.0;
+        Self {
+            fraction,
+            position_secs,
+            speed: (settled && position_secs > 0.0).then(|| position_secs / elapsed_secs),
+            eta_secs: settled.then(|| elapsed_secs * (1.0 - fraction) / fraction),
+        }
+    }
+}
+
+/// Time the export from now, turning the encoder's fractions into
+/// `ExportProgress` for an output about `total_secs` long
+fn track_progress(on_progress: Option<ProgressCallback>, total_secs: Option<f64>) -> Option<FractionCallback> {
+    let callback = on_progress?;
+    let started = Instant::now();
+    Some(Box::new(move |fraction| {
+        callback(ExportProgress::new(fraction, total_secs, started.elapsed().as_secs_f64()));
+    }))
+}
+
+/// Why an export did not produce a file
+#[derive(Debug, thiserror::Error)]
+pub enum ExportError {
+    /// The cancel flag was set; the partial output has been removed
+    #[error("Export cancelled")]
+    Cancelled,
+    /// The encoder or its setup failed
+    #[error(transparent)]
+    Failed(#[from] anyhow::Error),
+}
+
+/// Read a time like "1:05", "0:01:05" or "65" as seconds
+pub fn parse_timestamp(text: &str) -> Option<f64> {
+    let mut seconds = 0.0;
+    for part in text.trim().split(':') {
+        let value: f64 = part.trim().parse().ok()?;
+        // "NaN" and "inf" parse as floats too
+        if !value.is_finite() || value < 0.0 {
+            return None;
+        }
+        seconds = seconds * 60.0 + value;
+    }
+    Some(seconds)
+}
+
+/// Where `t` seconds into the voiceover lands once `cuts` (ordered, not
+/// overlapping) are left out; a time inside a cut moves to where it was
+fn time_after_cuts(cuts: &[(f64, f64)], t: f64) -> f64 {
+    let removed: f64 = cuts.iter().map(|&(start, end)| (t.min(end) - start).max(0.0)).sum();
+    t - removed
+}
+
+/// Seconds as "m:ss"
+pub fn format_timestamp(seconds: f64) -> String {
+    let seconds = seconds.max(0.0) as u64;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Seconds as an "hh:mm:ss:ff" timecode at the given frame rate
+pub fn format_timecode(seconds: f64, frame_rate: f64) -> String {
+    let frame_rate = if frame_rate > 0.0 { frame_rate } else { 30.0 };
+    let frames = (seconds.max(0.0) * frame_rate).round() as u64;
+    let per_second = (frame_rate.round() as u64).max(1);
+    let whole = frames / per_second;
+    format!("{:02}:{:02}:{:02}:{:02}", whole / 3600, whole / 60 % 60, whole % 60, frames % per_second)
+}
+
+/// Explain what is wrong with an output frame size before the pipeline
+/// trips over it
+pub fn check_frame_size((width, height): (u32, u32), format: OutputFormat) -> Result<(), String> {
+    if width == 0 || height == 0 {
+        return Err(format!("The frame can't be {}x{}: both sides need at least one pixel", width, height));
+    }
+    // 4:2:0 chroma halves each side, so H.264 and VP9 reject odd sizes
+    if format != OutputFormat::Gif && (width % 2 == 1 || height % 2 == 1) {
+        return Err(format!(
+            "{} needs even dimensions; try {}x{} instead of {}x{}",
+            format.label(),
+            width + width % 2,
+            height + height % 2,
+            width,
+            height
+        ));
+    }
+    Ok(())
+}
+
+/// Explain what is wrong with an export range, given how long the video is
+/// (unknown lengths only check the order)
+pub fn check_range((start, end): (f64, f64), duration: Option<f64>) -> Result<(), String> {
+    if !start.is_finite() || !end.is_finite() {
+        return Err(format!("The range needs real times (got {} to {})", start, end));
+    }
+    if start < 0.0 {
+        return Err(format!("The range can't start before 0:00 (got {})", start));
+    }
+    if end <= start {
+        return Err(format!(
+            "The out point ({}) must come after the in point ({})",
+            format_timestamp(end),
+            format_timestamp(start)
+        ));
+    }
+    // Probed lengths and the playhead disagree by a few frames
+    if let Some(duration) = duration
+        && end > duration + 0.5
+    {
+        return Err(format!(
+            "The range ends at {}, but the video is only {} long",
+            format_timestamp(end),
+            format_timestamp(duration)
+        ));
+    }
+    Ok(())
+}
 
 /// Export a project to a video file
+///
+/// `cancel` is polled while the encoder runs; once it is set the pipeline is
+/// torn down and the partial output file is removed. Returns the video
+/// encoder that was used, which differs from the requested one after a
+/// fallback.
 pub fn export_project(
     project: &Project,
     settings: &ExportSettings,
     on_progress: Option<ProgressCallback>,
+    cancel: Arc<AtomicBool>,
+) -> Result<VideoEncoder, ExportError> {
+    let length = settings
+        .range
+        .map(|(start, end)| end - start)
+        .or_else(|| project.video_duration())
+        .or_else(|| project.audio.as_ref().and_then(|a| a.duration));
+    let on_progress = track_progress(on_progress, length);
+    let result = export_project_inner(project, settings, on_progress.as_ref(), &cancel);
+    finish_export(result, &settings.output_path)
+}
+
+/// Export only the project's audio: the main track followed by any audio
+/// clips, encoded as MP3 or WAV depending on the output extension
+pub fn export_audio(
+    project: &Project,
+    settings: &ExportSettings,
+    on_progress: Option<ProgressCallback>,
+    cancel: Arc<AtomicBool>,
+) -> Result<(), ExportError> {
+    let length = settings
+        .range
+        .map(|(start, end)| end - start)
+        .or_else(|| {
+            project.audio
+                .iter()
+                .map(|a| a.duration)
+                .chain(project.audio_clips().map(|c| c.duration))
+                .sum()
+        });
+    let on_progress = track_progress(on_progress, length);
+    let result = export_audio_inner(project, settings, on_progress.as_ref(), &cancel);
+    finish_export(result, &settings.output_path)
+}
+
+/// Where the first edit lands on the record side of an EDL, as NLE
+/// timelines usually start at one hour
+const EDL_RECORD_START: f64 = 3600.0;
+
+/// What went into an EDL
+#[derive(Debug, Default, PartialEq)]
+pub struct EdlReport {
+    /// Events written
+    pub events: usize,
+    /// Video clips left out because their length isn't known yet
+    pub skipped: Vec<String>,
+}
+
+/// Write the cut as a CMX3600 EDL for finishing in another editor: one
+/// event per video or image clip with its source and record in/out, and
+/// the kept stretches of the voiceover. Nothing is rendered.
+pub fn export_edl(project: &Project, path: &Path) -> Result<EdlReport> {
+    let (edl, report) = render_edl(project, f64::from(FRAME_RATE));
+    std::fs::write(path, edl).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(report)
+}
+
+/// The EDL text and what went into it, with timecodes at `frame_rate`
+fn render_edl(project: &Project, frame_rate: f64) -> (String, EdlReport) {
+    let timecode = |seconds: f64| format_timecode(seconds, frame_rate);
+    let mut lines = vec![
+        format!("TITLE: {}", project.metadata.name),
+        "FCM: NON-DROP FRAME".to_string(),
+    ];
+    let mut events = 0;
+    let mut event = |lines: &mut Vec<String>, track: &str, source: (f64, f64), record: f64, path: &Path, comment: &str| {
+        events += 1;
+        let length = source.1 - source.0;
+        lines.push(String::new());
+        lines.push(format!(
+            "{:03}  AX       {:<5} C        {} {} {} {}",
+            events,
+            track,
+            timecode(source.0),
+            timecode(source.1),
+            timecode(EDL_RECORD_START + record),
+            timecode(EDL_RECORD_START + record + length),
+        ));
+        let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+        lines.push(format!("* FROM CLIP NAME: {}", name));
+        lines.push(format!("* SOURCE FILE: {}", path.display()));
+        if !comment.is_empty() {
+            lines.push(format!("* COMMENT: {}", comment));
+        }
+    };
+    
+    let still_duration = project.still_duration();
+    let mut skipped = Vec::new();
+    for clip in project.clips.iter().filter(|c| c.media_type != MediaType::Audio && c.error.is_none()) {
+        let duration = match clip.media_type {
+            MediaType::Image => clip.duration.unwrap_or(still_duration),
+            _ => clip.duration.unwrap_or(0.0),
+        };
+        if duration <= 0.0 {
+            tracing::warn!("Leaving {:?} out of the EDL: its length isn't known", clip.path);
+            skipped.push(clip.description.clone());
+            continue;
+        }
+        let source_in = if clip.media_type == MediaType::Image { 0.0 } else { clip.source_in };
+        event(&mut lines, "V", (source_in, source_in + duration), clip.start_time, &clip.path, &clip.description);
+    }
+    
+    // The voiceover, minus its cut silences, laid end to end
+    if let Some(audio) = &project.audio
+        && let Some(duration) = audio.duration
+    {
+        let mut record = 0.0;
+        let mut start = 0.0;
+        for &(cut_start, cut_end) in audio.cuts.iter().chain(std::iter::once(&(duration, duration))) {
+            if cut_start > start {
+                event(&mut lines, "A", (start, cut_start), record, &audio.path, "");
+                record += cut_start - start;
+            }
+            start = cut_end;
+        }
+    }
+    
+    lines.push(String::new());
+    (lines.join("\n"), EdlReport { events, skipped })
+}
+
+/// Turn an internal result into an `ExportError`, removing the partial file on cancel
+fn finish_export<T>(result: Result<T>, output_path: &Path) -> Result<T, ExportError> {
+    let result = result.map_err(|e| e.downcast::<ExportError>().unwrap_or_else(ExportError::Failed));
+
+    if let Err(ExportError::Cancelled) = result
+        && output_path.exists()
+    {
+        tracing::info!("Removing partial export: {:?}", output_path);
+        let _ = std::fs::remove_file(output_path);
+    }
+
+    result
+}
+
+fn export_audio_inner(
+    project: &Project,
+    settings: &ExportSettings,
+    on_progress: Option<&FractionCallback>,
+    cancel: &AtomicBool,
 ) -> Result<()> {
-    // Get video clips
+    let sources: Vec<&Path> = project.audio
+        .iter()
+        .map(|a| a.path.as_path())
+        .chain(project.audio_clips().map(|c| c.path.as_path()))
+        .collect();
+
+    if sources.is_empty() {
+        anyhow::bail!("Project has no audio: add an audio track or audio clips first");
+    }
+
+    let extension = settings.output_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase());
+    let encoder = match extension.as_deref() {
+        Some("wav") => "wavenc".to_string(),
+        Some("mp3") | None => format!(
+            "lamemp3enc target=bitrate bitrate={} cbr=true ! xingmux",
+            settings.audio_bitrate
+        ),
+        Some(other) => anyhow::bail!("Unsupported audio format '.{}' (use .mp3 or .wav)", other),
+    };
+
+    tracing::info!("Exporting {} audio source(s) to {:?}", sources.len(), settings.output_path);
+
+    let mut pipeline_str = format!(
+        "concat name=acat ! {}audioconvert ! {} ! filesink location=\"{}\"\n",
+        settings.gst_loudness_chain(),
+        encoder,
+        settings.output_path.to_string_lossy()
+    );
+    for (i, source) in sources.iter().enumerate() {
+        let uri = format!("file://{}", source.canonicalize()?.display());
+        pipeline_str.push_str(&format!(
+            "uridecodebin uri=\"{}\" name=src{}\n\
+             src{}. ! queue ! audioconvert ! audioresample ! audio/x-raw,rate=44100,channels=2 ! acat.\n",
+            uri, i, i
+        ));
+    }
+
+    run_gst_pipeline(&pipeline_str, on_progress, cancel, settings.range, None)
+}
+
+fn export_project_inner(
+    project: &Project,
+    settings: &ExportSettings,
+    on_progress: Option<&FractionCallback>,
+    cancel: &AtomicBool,
+) -> Result<VideoEncoder> {
+    // Videos and stills, in timeline order
     let video_clips: Vec<&Clip> = project
         .clips
         .iter()
-        .filter(|c| c.media_type == MediaType::Video)
+        .filter(|c| matches!(c.media_type, MediaType::Video | MediaType::Image))
         .collect();
 
     if video_clips.is_empty() {
-        anyhow::bail!("No video clips to export");
+        anyhow::bail!("No video or image clips to export");
+    }
+    
+    // A file that can't be decoded would fail the whole pipeline halfway through
+    for clip in video_clips.iter().filter(|c| c.error.is_some()) {
+        tracing::warn!("Skipping unusable clip {:?}: {}", clip.path, clip.error.as_deref().unwrap_or_default());
+    }
+    let video_clips: Vec<&Clip> = video_clips.into_iter().filter(|c| c.error.is_none()).collect();
+    if video_clips.is_empty() {
+        anyhow::bail!("None of the video or image clips can be read; replace or remove them first");
     }
 
-    // Get the main audio track (voiceover)
-    let audio_track = project.audio.as_ref().map(|a| &a.path);
+    // Get the main audio track (voiceover); a GIF has nowhere to put it
+    let audio_track = project.audio.as_ref().filter(|_| settings.format.has_audio());
+    
+    // Subtitles, ducking and chapters below follow the voiceover once it is cut
+    let cuts = audio_track.map_or(&[][..], |a| &a.cuts);
+    let ffmpeg = is_ffmpeg_available();
+    if !ffmpeg && !cuts.is_empty() {
+        anyhow::bail!("Cutting silences needs FFmpeg; install it, or undo the cut to export the full voiceover");
+    }
+    
+    let burned_subtitles = prepare_subtitles(project, settings, cuts)?;
+    let burned_subtitles = burned_subtitles.as_deref();
+    
+    if let Some(range) = settings.range {
+        check_range(range, None).map_err(|e| anyhow::anyhow!(e))?;
+    }
+    
+    let chapters = (settings.export_chapters && settings.format != OutputFormat::Gif)
+        .then(|| {
+            let markers: Vec<Marker> = project.markers
+                .iter()
+                .map(|m| Marker { time_seconds: time_after_cuts(cuts, m.time_seconds), ..m.clone() })
+                .collect();
+            markers_in_range(&markers, settings.range)
+        })
+        .filter(|markers| !markers.is_empty());
+    let chapters = chapters.as_deref();
+    
+    let duck = DuckEnvelope::for_project(project, settings, cuts);
 
     tracing::info!(
         "Exporting {} video clips to {:?}, audio: {:?}",
         video_clips.len(),
         settings.output_path,
-        audio_track
+        audio_track.map(|a| &a.path)
     );
 
     // Try FFmpeg first (most reliable for concat)
-    if is_ffmpeg_available() {
-        tracing::info!("Using FFmpeg for export");
-        return export_with_ffmpeg(&video_clips, audio_track, settings);
+    if ffmpeg {
+        let encoder = settings.video_encoder(VideoEncoder::resolve_ffmpeg);
+        tracing::info!("Using FFmpeg for export ({})", encoder.label());
+        export_with_ffmpeg(&video_clips, audio_track, burned_subtitles, chapters, duck.as_ref(), encoder, settings, on_progress, cancel)?;
+        return Ok(encoder);
+    }
+    
+    // mp4mux has no chapter support, so they go next to the video instead
+    if let Some(markers) = chapters {
+        let path = settings.output_path.with_extension("chapters.txt");
+        std::fs::write(&path, chapter_lines(markers))?;
+        tracing::info!("Wrote chapters: {:?}", path);
     }
 
     // Fall back to GStreamer
-    tracing::info!("Using GStreamer for export");
+    let encoder = settings.video_encoder(VideoEncoder::resolve_gst);
+    let audio_track = audio_track.map(|a| &a.path);
+    tracing::info!("Using GStreamer for export ({})", encoder.label());
     
-    if video_clips.len() == 1 {
-        export_single_clip_gst(&video_clips[0].path, audio_track, settings, on_progress)
+    if video_clips.len() == 1
+        && video_clips[0].media_type == MediaType::Video
+        && audio_track.is_none()
+        && burned_subtitles.is_none()
+    {
+        export_single_clip_gst(&video_clips[0].path, encoder, settings, on_progress, cancel)?;
     } else {
-        export_multiple_clips_gst(&video_clips, audio_track, settings, on_progress)
+        export_multiple_clips_gst(&video_clips, audio_track, burned_subtitles, duck.as_ref(), encoder, settings, on_progress, cancel)?;
+    }
+    Ok(encoder)
+}
+
+/// Write the SRT that `settings.subtitles` asks for, transcribing the audio
+/// if the project has no cached transcript and retiming it past `cuts`.
+/// Returns the file to burn in.
+fn prepare_subtitles(project: &Project, settings: &ExportSettings, cuts: &[(f64, f64)]) -> Result<Option<std::path::PathBuf>> {
+    let path = match settings.subtitles {
+        SubtitleMode::None => return Ok(None),
+        SubtitleMode::Burned => std::env::temp_dir().join("montage_export").join("subtitles.srt"),
+        SubtitleMode::Sidecar => settings.output_path.with_extension("srt"),
+    };
+    
+    let transcript = match project.current_transcript() {
+        Some(transcript) => transcript.clone(),
+        None => {
+            let audio = project
+                .audio_source()
+                .context("Subtitles need a transcript, but the project has no audio")?;
+            transcription::transcribe(audio, &settings.whisper)?
+        }
+    };
+    
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let transcript = transcript.retimed(|t| time_after_cuts(cuts, t));
+    // Burned subtitles are drawn before the range is cut; a sidecar has to match the cut
+    let transcript = match settings.range {
+        Some((start, end)) if settings.subtitles == SubtitleMode::Sidecar => transcript.trimmed(start, end),
+        _ => transcript,
+    };
+    std::fs::write(&path, transcription::to_srt(&transcript))?;
+    tracing::info!("Wrote subtitles: {:?}", path);
+    
+    Ok((settings.subtitles == SubtitleMode::Burned).then_some(path))
+}
+
+/// Markers inside `range`, moved so the range starts at zero
+fn markers_in_range(markers: &[Marker], range: Option<(f64, f64)>) -> Vec<Marker> {
+    let Some((start, end)) = range else {
+        return markers.to_vec();
+    };
+    markers
+        .iter()
+        .filter(|m| m.time_seconds >= start && m.time_seconds < end)
+        .map(|m| Marker { time_seconds: m.time_seconds - start, ..m.clone() })
+        .collect()
+}
+
+/// One "HH:MM:SS title" line per marker, the form YouTube descriptions use
+fn chapter_lines(markers: &[Marker]) -> String {
+    markers
+        .iter()
+        .map(|marker| {
+            let seconds = marker.time_seconds.max(0.0) as u64;
+            format!(
+                "{:02}:{:02}:{:02} {}\n",
+                seconds / 3600,
+                (seconds / 60) % 60,
+                seconds % 60,
+                marker.label
+            )
+        })
+        .collect()
+}
+
+/// FFmpeg metadata file with a chapter from each marker to the next (the
+/// last runs to `total` seconds)
+fn ffmetadata_chapters(markers: &[Marker], total: f64) -> String {
+    let escape = |text: &str| {
+        text.chars().fold(String::new(), |mut out, c| {
+            if matches!(c, '=' | ';' | '#' | '\\' | '\n') {
+                out.push('\\');
+            }
+            out.push(c);
+            out
+        })
+    };
+    let millis = |seconds: f64| (seconds.max(0.0) * 1000.0).round() as u64;
+    
+    let mut out = String::from(";FFMETADATA1\n");
+    for (i, marker) in markers.iter().enumerate() {
+        let end = markers.get(i + 1).map_or(total, |next| next.time_seconds);
+        out.push_str(&format!(
+            "[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={}\ntitle={}\n",
+            millis(marker.time_seconds),
+            millis(end.max(marker.time_seconds)),
+            escape(&marker.label)
+        ));
     }
+    out
 }
 
 /// Check if FFmpeg is available
@@ -88,171 +1403,762 @@ fn is_ffmpeg_available() -> bool {
         .unwrap_or(false)
 }
 
+/// Probe a media file's duration in seconds using ffprobe
+fn probe_duration_ffprobe(path: &Path) -> Option<f64> {
+    let output = Command::new("ffprobe")
+        .args(["-v", "error", "-show_entries", "format=duration", "-of", "csv=p=0"])
+        .arg(path)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+/// Check whether a media file has an audio stream, using ffprobe
+fn probe_has_audio_ffprobe(path: &Path) -> bool {
+    Command::new("ffprobe")
+        .args(["-v", "error", "-select_streams", "a", "-show_entries", "stream=index", "-of", "csv=p=0"])
+        .arg(path)
+        .output()
+        .map(|o| o.status.success() && !o.stdout.trim_ascii().is_empty())
+        .unwrap_or(false)
+}
+
+/// Length of every clip, probing those not cached in the project.
+/// The clips set the output length; a longer voiceover is cut off.
+fn clip_durations_ffprobe(video_clips: &[&Clip], settings: &ExportSettings) -> Option<Vec<f64>> {
+    video_clips
+        .iter()
+        .map(|clip| settings.clip_duration(clip).or_else(|| probe_duration_ffprobe(&clip.path)))
+        .collect()
+}
+
+/// `-filter_complex` chain joining separate clip inputs with crossfades
+/// (or hard cuts where `fades` is 0), ending in `[vout]` and, with clip
+/// audio, `[ccat]`
+fn ffmpeg_crossfade_graph(
+    fades: &[f64],
+    durations: &[f64],
+    clip_audio: bool,
+    burned_subtitles: Option<&Path>,
+    settings: &ExportSettings,
+) -> String {
+    let mut graph = Vec::new();
+    
+    // xfade needs matching size, frame rate, format and timebase on both sides
+    for i in 0..durations.len() {
+        graph.push(format!(
+            "[{i}:v]{},fps={},format=yuv420p,settb=AVTB[v{i}]",
+            settings.ffmpeg_scale_filter(),
+            FRAME_RATE
+        ));
+        if clip_audio {
+            graph.push(format!("[{i}:a]aresample=48000,aformat=channel_layouts=stereo[a{i}]"));
+        }
+    }
+    
+    let mut length = durations[0];
+    for (k, &fade) in fades.iter().enumerate() {
+        let next = k + 1;
+        let prev = if k == 0 { "v0".to_string() } else { format!("vx{k}") };
+        if fade > 0.0 {
+            graph.push(format!(
+                "[{prev}][v{next}]xfade=transition=fade:duration={fade:.3}:offset={:.3}[vx{next}]",
+                length - fade
+            ));
+        } else {
+            graph.push(format!("[{prev}][v{next}]concat=n=2:v=1:a=0[vx{next}]"));
+        }
+        length += durations[next] - fade;
+        
+        if clip_audio {
+            let prev = if k == 0 { "a0".to_string() } else { format!("ax{k}") };
+            if fade > 0.0 {
+                graph.push(format!("[{prev}][a{next}]acrossfade=d={fade:.3}[ax{next}]"));
+            } else {
+                graph.push(format!("[{prev}][a{next}]concat=n=2:v=0:a=1[ax{next}]"));
+            }
+        }
+    }
+    
+    let last = fades.len();
+    let subtitles = burned_subtitles.map_or_else(|| "null".to_string(), ffmpeg_subtitles_filter);
+    graph.push(format!("[vx{last}]{subtitles}[vout]"));
+    if clip_audio {
+        graph.push(format!("[ax{last}]anull[ccat]"));
+    }
+    
+    graph.join(";")
+}
+
+/// Bottom-centered white text with a dark outline; libass scales these
+/// sizes with the output height
+fn ffmpeg_subtitles_filter(srt: &Path) -> String {
+    format!(
+        "subtitles='{}':force_style='Alignment=2,FontSize=18,Outline=2,BorderStyle=1,MarginV=20'",
+        ffmpeg_escape_path(srt)
+    )
+}
+
+/// Frame rate of rendered stills (and of crossfaded exports, which need one rate)
+const FRAME_RATE: u32 = 30;
+
+/// How far a still zooms in over its duration
+const KEN_BURNS_ZOOM: f64 = 1.15;
+
+/// Render an image as a slowly zooming clip at the output size, with a
+/// silent audio track so it concatenates with clips that have sound
+fn render_still_ffmpeg(image: &Path, duration: f64, settings: &ExportSettings, output: &Path) -> Result<()> {
+    let frames = ((duration * f64::from(FRAME_RATE)).round() as u32).max(1);
+    let zoom_step = (KEN_BURNS_ZOOM - 1.0) / f64::from(frames);
+    let (w, h) = (settings.width, settings.height);
+    
+    // Upscale before zoompan, which otherwise jitters on small steps
+    let filter = format!(
+        "{},scale={}:{},zoompan=z='min(zoom+{:.6},{})':d={}:\
+         x='iw/2-(iw/zoom/2)':y='ih/2-(ih/zoom/2)':s={}x{}:fps={},format=yuv420p",
+        settings.ffmpeg_scale_filter(),
+        w * 2,
+        h * 2,
+        zoom_step,
+        KEN_BURNS_ZOOM,
+        frames,
+        w,
+        h,
+        FRAME_RATE
+    );
+    
+    let output = Command::new("ffmpeg")
+        .args(["-y", "-v", "error", "-i"])
+        .arg(image)
+        .args(["-f", "lavfi", "-i", "anullsrc=r=48000:cl=stereo"])
+        .args(["-vf", &filter, "-frames:v", &frames.to_string()])
+        .args(["-t", &format!("{:.3}", f64::from(frames) / f64::from(FRAME_RATE))])
+        .args(["-c:v", "libx264", "-preset", "veryfast", "-c:a", "aac"])
+        .arg(output)
+        .output()
+        .context("Failed to run FFmpeg")?;
+    
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!(
+            "Failed to render {:?}: {}",
+            image.file_name().unwrap_or_default(),
+            stderr.lines().last().unwrap_or("unknown error")
+        );
+    }
+    
+    Ok(())
+}
+
+/// FFmpeg audio filter dropping `cuts` (seconds) and closing the gaps
+fn ffmpeg_cut_filter(cuts: &[(f64, f64)]) -> String {
+    let inside: Vec<String> = cuts
+        .iter()
+        .map(|(start, end)| format!("between(t,{:.3},{:.3})", start, end))
+        .collect();
+    format!("aselect='not({})',asetpts=N/SR/TB", inside.join("+"))
+}
+
 /// Export using FFmpeg (more reliable for concatenation)
 fn export_with_ffmpeg(
     video_clips: &[&Clip],
-    audio_track: Option<&std::path::PathBuf>,
+    audio_track: Option<&AudioTrack>,
+    burned_subtitles: Option<&Path>,
+    chapters: Option<&[Marker]>,
+    duck: Option<&DuckEnvelope>,
+    encoder: VideoEncoder,
     settings: &ExportSettings,
+    on_progress: Option<&FractionCallback>,
+    cancel: &AtomicBool,
 ) -> Result<()> {
     let temp_dir = std::env::temp_dir().join("montage_export");
     std::fs::create_dir_all(&temp_dir)?;
 
+    // Stills become short clips first so the concat demuxer can take them
+    let mut segments = Vec::with_capacity(video_clips.len());
+    for (i, clip) in video_clips.iter().enumerate() {
+        if clip.media_type != MediaType::Image {
+            segments.push(clip.path.clone());
+            continue;
+        }
+        if cancel.load(Ordering::Relaxed) {
+            return Err(ExportError::Cancelled.into());
+        }
+        let still = temp_dir.join(format!("still_{:03}.mp4", i));
+        let duration = settings.clip_duration(clip).unwrap_or(settings.still_duration);
+        render_still_ffmpeg(&clip.path, duration, settings, &still)?;
+        segments.push(still);
+    }
+    
     // Create a concat file list
     let concat_file = temp_dir.join("concat.txt");
     let mut concat_content = String::new();
     
-    for clip in video_clips {
-        let path = clip.path.canonicalize()
-            .unwrap_or_else(|_| clip.path.clone());
+    for (segment, clip) in segments.iter().zip(video_clips) {
+        let path = segment.canonicalize()
+            .unwrap_or_else(|_| segment.clone());
         // FFmpeg concat format: file 'path'
         concat_content.push_str(&format!("file '{}'\n", path.display()));
+        
+        // Only the clip's part of the source plays
+        if clip.media_type != MediaType::Image {
+            if clip.source_in > 0.0 {
+                concat_content.push_str(&format!("inpoint {:.3}\n", clip.source_in));
+            }
+            if let Some(duration) = clip.duration {
+                concat_content.push_str(&format!("outpoint {:.3}\n", clip.source_in + duration));
+            }
+        }
     }
     
     std::fs::write(&concat_file, &concat_content)?;
     tracing::debug!("Concat file:\n{}", concat_content);
+    
+    let chapters_file = temp_dir.join("chapters.ffmeta");
+    
+    let clean_up = || {
+        let _ = std::fs::remove_file(&concat_file);
+        let _ = std::fs::remove_file(&chapters_file);
+        for segment in segments.iter().filter(|p| p.starts_with(&temp_dir)) {
+            let _ = std::fs::remove_file(segment);
+        }
+    };
 
     let output_path = settings.output_path.to_string_lossy();
     
+    let durations = clip_durations_ffprobe(video_clips, settings);
+    
+    // Crossfades need every clip as its own input and known lengths
+    let fades = durations
+        .as_deref()
+        .map(|durations| settings.transition.fade_lengths(durations))
+        .filter(|fades| fades.iter().any(|&f| f > 0.0));
+    if settings.transition != Transition::Cut && fades.is_none() {
+        tracing::info!("No room for crossfades (or clip lengths unknown); using hard cuts");
+    }
+    
+    let expected_duration = durations.as_ref().map(|durations| {
+        durations.iter().sum::<f64>() - fades.iter().flatten().sum::<f64>()
+    });
+    if let (Some((_, end)), Some(total)) = (settings.range, expected_duration) {
+        check_range((0.0, end), Some(total)).map_err(|e| anyhow::anyhow!(e))?;
+    }
+    let expected_duration = match settings.range {
+        Some((start, end)) => Some(end - start),
+        None => expected_duration,
+    };
+    
+    let mut video_filter = settings.ffmpeg_scale_filter();
+    if let Some(srt) = burned_subtitles {
+        video_filter.push(',');
+        video_filter.push_str(&ffmpeg_subtitles_filter(srt));
+    }
+    if settings.format == OutputFormat::Gif {
+        video_filter.push(',');
+        video_filter.push_str(&settings.ffmpeg_gif_filter());
+    }
+    let upload = if encoder.ffmpeg_needs_upload() { ",format=nv12,hwupload" } else { "" };
+    video_filter.push_str(upload);
+
     // Build FFmpeg command
     let mut cmd = Command::new("ffmpeg");
     cmd.arg("-y"); // Overwrite output
     
-    // Input: concatenated videos
-    cmd.args(["-f", "concat", "-safe", "0", "-i"]);
-    cmd.arg(&concat_file);
+    // Machine-readable progress on stdout
+    cmd.args(["-progress", "pipe:1", "-nostats"]);
+    
+    if encoder.ffmpeg_needs_upload() {
+        cmd.args(["-vaapi_device", VideoEncoder::VAAPI_DEVICE]);
+    }
+    
+    // Input: one per clip when crossfading, else the concatenated list
+    if fades.is_some() {
+        for (segment, clip) in segments.iter().zip(video_clips) {
+            if clip.media_type != MediaType::Image {
+                if clip.source_in > 0.0 {
+                    cmd.args(["-ss", &format!("{:.3}", clip.source_in)]);
+                }
+                if let Some(duration) = clip.duration {
+                    cmd.args(["-t", &format!("{:.3}", duration)]);
+                }
+            }
+            cmd.arg("-i").arg(segment);
+        }
+    } else {
+        cmd.args(["-f", "concat", "-safe", "0", "-i"]);
+        cmd.arg(&concat_file);
+    }
+    let voice_input = if fades.is_some() { segments.len() } else { 1 };
     
     // Input: audio track (if provided)
-    if let Some(audio_path) = audio_track {
+    if let Some(audio) = audio_track {
         cmd.args(["-i"]);
-        cmd.arg(audio_path);
+        cmd.arg(&audio.path);
+    }
+    
+    // Input: chapters, taken from the markers
+    if let Some(markers) = chapters {
+        let total = expected_duration
+            .or_else(|| markers.last().map(|m| m.time_seconds + 1.0))
+            .unwrap_or_default();
+        std::fs::write(&chapters_file, ffmetadata_chapters(markers, total))?;
+        let chapters_input = voice_input + usize::from(audio_track.is_some());
+        cmd.args(["-f", "ffmetadata", "-i"]);
+        cmd.arg(&chapters_file);
+        cmd.args(["-map_chapters", &chapters_input.to_string()]);
     }
     
     // Video settings
-    cmd.args([
-        "-c:v", "libx264",
-        "-preset", "medium",
-        "-b:v", &format!("{}k", settings.video_bitrate),
-        "-vf", &format!("scale={}:{}:force_original_aspect_ratio=decrease,pad={}:{}:(ow-iw)/2:(oh-ih)/2",
-            settings.width, settings.height, settings.width, settings.height),
-    ]);
+    cmd.args(settings.ffmpeg_video_args(encoder));
     
     // Audio settings
-    if audio_track.is_some() {
-        // Use the separate audio track, not the video's audio
-        cmd.args([
-            "-map", "0:v:0",     // Video from concat
-            "-map", "1:a:0",     // Audio from separate track
-            "-c:a", "aac",
-            "-b:a", &format!("{}k", settings.audio_bitrate),
-            "-shortest",        // End when shortest stream ends
-        ]);
-    } else {
-        // Use audio from videos
+    // The concat demuxer needs every clip to share the same streams, so one
+    // silent clip (common with stock footage) means no clip audio at all
+    let clip_audio = !settings.mute_clip_audio
+        && settings.format.has_audio()
+        && segments.iter().all(|path| probe_has_audio_ffprobe(path));
+    
+    let mut graph = Vec::new();
+    let (video_map, clip_source) = match (&fades, &durations) {
+        (Some(fades), Some(durations)) => {
+            graph.push(ffmpeg_crossfade_graph(fades, durations, clip_audio, burned_subtitles, settings));
+            if settings.format == OutputFormat::Gif {
+                graph.push(format!("[vout]{}[vgif]", settings.ffmpeg_gif_filter()));
+                ("[vgif]", "[ccat]".to_string())
+            } else if upload.is_empty() {
+                ("[vout]", "[ccat]".to_string())
+            } else {
+                graph.push(format!("[vout]{}[vgpu]", &upload[1..]));
+                ("[vgpu]", "[ccat]".to_string())
+            }
+        }
+        _ => {
+            cmd.args(["-vf", &video_filter]);
+            ("0:v:0", "[0:a]".to_string())
+        }
+    };
+    let mut voice_source = format!("[{}:a]", voice_input);
+    if let Some(cuts) = audio_track.map(|a| &a.cuts).filter(|cuts| !cuts.is_empty()) {
+        graph.push(format!("{}{}[vcut]", voice_source, ffmpeg_cut_filter(cuts)));
+        voice_source = "[vcut]".to_string();
+    }
+    
+    match (audio_track.is_some(), clip_audio) {
+        (true, true) => {
+            // Voiceover mixed over the clips' audio; the clips set the length
+            let ducking = duck
+                .map(|duck| format!(",volume='{}':eval=frame", duck.ffmpeg_expr()))
+                .unwrap_or_default();
+            graph.push(format!(
+                "{}volume={}{}[clip];{}volume={}[voice];\
+                 [clip][voice]amix=inputs=2:duration=first:normalize=0[aout]",
+                clip_source, settings.clip_audio_gain, ducking, voice_source, settings.voiceover_gain
+            ));
+        }
+        (true, false) => {
+            // Voiceover only, padded with silence so it never ends the video early
+            graph.push(format!("{}volume={},apad[aout]", voice_source, settings.voiceover_gain));
+            cmd.arg("-shortest");
+        }
+        (false, true) => {
+            graph.push(format!("{}volume={}[aout]", clip_source, settings.clip_audio_gain));
+        }
+        (false, false) => {}
+    }
+    
+    // Normalize the finished mix, not each clip, so relative levels survive
+    let mut audio_map = "[aout]";
+    if (audio_track.is_some() || clip_audio)
+        && let Some(loudness) = settings.ffmpeg_loudness_filter()
+    {
+        graph.push(format!("[aout]{}[anorm]", loudness));
+        audio_map = "[anorm]";
+    }
+    
+    if !graph.is_empty() {
+        cmd.args(["-filter_complex", &graph.join(";")]);
+    }
+    cmd.args(["-map", video_map]);
+    if audio_track.is_some() || clip_audio {
+        cmd.args(["-map", audio_map]);
+    }
+    
+    if (audio_track.is_some() || clip_audio)
+        && let Some(codec) = settings.ffmpeg_audio_codec()
+    {
         cmd.args([
-            "-c:a", "aac",
+            "-c:a", codec,
             "-b:a", &format!("{}k", settings.audio_bitrate),
         ]);
     }
     
+    // Output-side seek: everything above is built for the whole timeline, then cut
+    if let Some((start, end)) = settings.range {
+        cmd.args(["-ss", &format!("{:.3}", start), "-t", &format!("{:.3}", end - start)]);
+    }
+    
     cmd.arg(&*output_path);
     
     tracing::info!("Running FFmpeg: {:?}", cmd);
     
-    let output = cmd.output().context("Failed to run FFmpeg")?;
+    let mut child = cmd
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run FFmpeg")?;
     
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
+    // Drain stderr on its own thread so FFmpeg never blocks on a full pipe
+    let stderr = child.stderr.take();
+    let stderr_reader = std::thread::spawn(move || {
+        let mut content = String::new();
+        if let Some(mut stderr) = stderr {
+            let _ = stderr.read_to_string(&mut content);
+        }
+        content
+    });
+    
+    // FFmpeg writes a progress block roughly every half second
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if cancel.load(Ordering::Relaxed) {
+                let _ = child.kill();
+                let _ = child.wait();
+                clean_up();
+                return Err(ExportError::Cancelled.into());
+            }
+            
+            if let Some(callback) = on_progress
+                && let Some(total) = expected_duration
+                && total > 0.0
+                && let Some(out_time_us) = line.strip_prefix("out_time_us=")
+                && let Ok(out_time_us) = out_time_us.trim().parse::<f64>()
+            {
+                callback((out_time_us / 1_000_000.0 / total).clamp(0.0, 1.0));
+            }
+        }
+    }
+    
+    let status = child.wait().context("Failed to wait for FFmpeg")?;
+    let stderr = stderr_reader.join().unwrap_or_default();
+    
+    if !status.success() {
         tracing::error!("FFmpeg stderr: {}", stderr);
+        clean_up();
         anyhow::bail!("FFmpeg failed: {}", stderr.lines().last().unwrap_or("unknown error"));
     }
     
-    // Clean up
-    let _ = std::fs::remove_file(&concat_file);
+    if let Some(callback) = on_progress {
+        callback(1.0);
+    }
+    
+    clean_up();
     
     tracing::info!("Export complete: {}", output_path);
     Ok(())
 }
 
-/// Export a single clip with optional audio overlay using GStreamer
+/// Subtitle font size for GStreamer's `textoverlay`, scaled to the output
+fn subtitle_font_size(settings: &ExportSettings) -> u32 {
+    (settings.height.min(settings.width) / 36).max(12)
+}
+
+/// Escape a path for use inside a quoted FFmpeg filter argument
+fn ffmpeg_escape_path(path: &Path) -> String {
+    path.to_string_lossy()
+        .replace('\\', "\\\\")
+        .replace(':', "\\:")
+        .replace('\'', "\\'")
+}
+
+/// Export a single clip (with its own audio) using GStreamer
 fn export_single_clip_gst(
     video_path: &Path,
-    audio_track: Option<&std::path::PathBuf>,
+    encoder: VideoEncoder,
     settings: &ExportSettings,
-    _on_progress: Option<ProgressCallback>,
+    on_progress: Option<&FractionCallback>,
+    cancel: &AtomicBool,
 ) -> Result<()> {
     let video_uri = format!("file://{}", video_path.canonicalize()?.display());
     let output_path = settings.output_path.to_string_lossy();
 
-    let pipeline_str = if let Some(audio_path) = audio_track {
-        let audio_uri = format!("file://{}", audio_path.canonicalize()?.display());
-        format!(
-            r#"
-            uridecodebin uri="{}" name=vdec
-            uridecodebin uri="{}" name=adec
-            vdec. ! queue ! videoconvert ! videoscale ! 
-                video/x-raw,width={},height={} ! 
-                x264enc bitrate={} ! h264parse ! queue ! mux.
-            adec. ! queue ! audioconvert ! audioresample ! 
-                audio/x-raw,rate=48000,channels=2 !
-                fdkaacenc bitrate={} ! queue ! mux.
-            mp4mux name=mux ! filesink location="{}"
-            "#,
-            video_uri,
-            audio_uri,
-            settings.width,
-            settings.height,
-            settings.video_bitrate,
-            settings.audio_bitrate * 1000,
-            output_path
-        )
-    } else {
-        format!(
-            r#"
-            uridecodebin uri="{}" name=demux
-            demux. ! queue ! videoconvert ! videoscale ! 
-                video/x-raw,width={},height={} ! 
-                x264enc bitrate={} ! h264parse ! queue ! mux.
-            demux. ! queue ! audioconvert ! audioresample ! 
-                audio/x-raw,rate=48000,channels=2 !
-                fdkaacenc bitrate={} ! queue ! mux.
-            mp4mux name=mux ! filesink location="{}"
-            "#,
-            video_uri,
-            settings.width,
-            settings.height,
-            settings.video_bitrate,
-            settings.audio_bitrate * 1000,
-            output_path
-        )
+    let audio_branch = match settings.gst_audio_encoder() {
+        Some(audio_encoder) if !settings.mute_clip_audio => format!(
+            "demux. ! queue ! audioconvert ! volume volume={} ! {}audioresample ! \
+             audio/x-raw,rate=48000,channels=2 ! {} ! queue ! mux.",
+            settings.clip_audio_gain,
+            settings.gst_loudness_chain(),
+            audio_encoder
+        ),
+        _ => String::new(),
     };
 
-    run_gst_pipeline(&pipeline_str)
+    let pipeline_str = format!(
+        r#"
+        uridecodebin uri="{}" name=demux
+        demux. ! queue ! videoconvert ! {} ! 
+            {} ! queue ! mux.
+        {}
+        {} name=mux ! filesink location="{}"
+        "#,
+        video_uri,
+        settings.gst_scale_chain(),
+        settings.gst_video_tail(encoder),
+        audio_branch,
+        settings.gst_muxer(),
+        output_path
+    );
+
+    run_gst_pipeline(&pipeline_str, on_progress, cancel, settings.range, None)
 }
 
 /// Export multiple clips using GStreamer (fallback)
 fn export_multiple_clips_gst(
     clips: &[&Clip],
     audio_track: Option<&std::path::PathBuf>,
+    burned_subtitles: Option<&Path>,
+    duck: Option<&DuckEnvelope>,
+    encoder: VideoEncoder,
     settings: &ExportSettings,
-    _on_progress: Option<ProgressCallback>,
+    on_progress: Option<&FractionCallback>,
+    cancel: &AtomicBool,
 ) -> Result<()> {
-    // For GStreamer, we'll use splitmuxsink approach or manual concat
-    // This is complex and error-prone, so we really want FFmpeg
-    
     tracing::warn!("GStreamer multi-clip export is experimental. Install FFmpeg for better results.");
+    if settings.transition != Transition::Cut {
+        tracing::warn!("Crossfades need FFmpeg; exporting with hard cuts");
+    }
     
-    // Create a temporary script to concat with GStreamer
-    // For now, just export the first clip as a fallback
     if clips.is_empty() {
         anyhow::bail!("No clips to export");
     }
     
-    tracing::warn!("Exporting only first clip (install FFmpeg for full concat support)");
-    export_single_clip_gst(&clips[0].path, audio_track, settings, None)
+    let output_path = settings.output_path.to_string_lossy();
+    let mut pipeline_str = String::new();
+    
+    // Every clip is scaled to the output size before `concat`, which plays
+    // its sink pads in the order they were requested (i.e. clip order)
+    let overlay = match burned_subtitles {
+        Some(srt) => {
+            // Subtitles are drawn after concat so timing follows the whole video
+            pipeline_str.push_str(&format!(
+                "filesrc location=\"{}\" ! subparse ! subs.text_sink\n",
+                srt.to_string_lossy()
+            ));
+            format!(
+                "textoverlay name=subs valignment=bottom halignment=center ypad={} \
+                 wrap-mode=wordchar draw-outline=true draw-shadow=false \
+                 font-desc=\"Sans Bold {}\" ! videoconvert ! ",
+                settings.height / 20,
+                subtitle_font_size(settings)
+            )
+        }
+        None => String::new(),
+    };
+    pipeline_str.push_str(&format!(
+        "concat name=vcat ! videoconvert ! {}{} ! queue ! mux.\n",
+        overlay,
+        settings.gst_video_tail(encoder)
+    ));
+    
+    let audio_encoder = settings.gst_audio_encoder();
+    let clip_audio = !settings.mute_clip_audio && audio_encoder.is_some();
+    
+    // Voiceover and clip audio meet in a mixer; either side may be absent.
+    // Loudness is normalized after the mixer, on the combined stream.
+    if let Some(audio_encoder) = audio_encoder {
+        pipeline_str.push_str(&format!(
+            "audiomixer name=amix ! {}audioconvert ! audioresample ! audio/x-raw,rate=48000,channels=2 ! \
+             {} ! queue ! mux.\n",
+            settings.gst_loudness_chain(),
+            audio_encoder
+        ));
+    }
+    
+    if let Some(audio_path) = audio_track {
+        let audio_uri = format!("file://{}", audio_path.canonicalize()?.display());
+        pipeline_str.push_str(&format!(
+            "uridecodebin uri=\"{}\" name=voice\n\
+             voice. ! queue ! audioconvert ! audioresample ! volume volume={} ! amix.\n",
+            audio_uri,
+            settings.voiceover_gain
+        ));
+    }
+    
+    // Speech ducking is driven per buffer by `run_gst_pipeline`
+    let duck = duck.filter(|_| clip_audio && audio_track.is_some());
+    if clip_audio {
+        pipeline_str.push_str(&format!(
+            "concat name=acat ! audioconvert ! audioresample ! volume volume={} ! {}amix.\n",
+            settings.clip_audio_gain,
+            if duck.is_some() { "volume name=clip_duck ! " } else { "" }
+        ));
+    }
+    
+    for (i, clip) in clips.iter().enumerate() {
+        if clip.media_type == MediaType::Image {
+            // Stills are held on screen without the Ken Burns zoom (FFmpeg only)
+            let duration = settings.clip_duration(clip).unwrap_or(settings.still_duration);
+            pipeline_str.push_str(&format!(
+                "filesrc location=\"{}\" ! decodebin ! imagefreeze num-buffers={} ! \
+                 video/x-raw,framerate={}/1 ! queue ! videoconvert ! {} ! vcat.\n",
+                clip.path.to_string_lossy(),
+                (duration * f64::from(FRAME_RATE)).round() as u32,
+                FRAME_RATE,
+                settings.gst_scale_chain()
+            ));
+            
+            if clip_audio {
+                // 10ms buffers of silence keep `acat` in step with `vcat`
+                pipeline_str.push_str(&format!(
+                    "audiotestsrc wave=silence samplesperbuffer=480 num-buffers={} ! \
+                     audio/x-raw,rate=48000,channels=2 ! acat.\n",
+                    (duration * 100.0).round() as u32
+                ));
+            }
+            continue;
+        }
+        
+        if clip.source_in > 0.0 {
+            tracing::warn!("Clip trims need FFmpeg; {:?} plays from its start", clip.path);
+        }
+        let uri = format!("file://{}", clip.path.canonicalize()?.display());
+        pipeline_str.push_str(&format!(
+            "uridecodebin uri=\"{}\" name=clip{}\n\
+             clip{}. ! queue ! videoconvert ! {} ! vcat.\n",
+            uri, i, i, settings.gst_scale_chain()
+        ));
+        
+        if clip_audio {
+            pipeline_str.push_str(&format!(
+                "clip{}. ! queue ! audioconvert ! audioresample ! audio/x-raw,rate=48000,channels=2 ! acat.\n",
+                i
+            ));
+        }
+    }
+    
+    // A voiceover longer than the video is cut where the clips end
+    let stop_at = if audio_track.is_some() {
+        clips
+            .iter()
+            .map(|c| settings.clip_duration(c).or_else(|| probe_duration_gst(&c.path)))
+            .sum::<Option<f64>>()
+    } else {
+        None
+    };
+    let range = match (settings.range, stop_at) {
+        (Some((start, end)), Some(stop_at)) => Some((start, end.min(stop_at))),
+        (Some(range), None) => Some(range),
+        (None, Some(stop_at)) => Some((0.0, stop_at)),
+        (None, None) => None,
+    };
+    
+    pipeline_str.push_str(&format!("{} name=mux ! filesink location=\"{}\"", settings.gst_muxer(), output_path));
+    
+    run_gst_pipeline(&pipeline_str, on_progress, cancel, range, duck)
+}
+
+/// Set `volume`'s gain from the envelope as each buffer passes. Concat
+/// restarts timestamps per clip, so the timeline position comes from the
+/// running time, counted from `offset` seconds when exporting a range.
+fn follow_duck_envelope(volume: &gst::Element, duck: DuckEnvelope, offset: f64) {
+    let Some(pad) = volume.static_pad("sink") else {
+        return;
+    };
+    let weak = volume.downgrade();
+    let segment = std::sync::Mutex::new(gst::FormattedSegment::<gst::ClockTime>::new());
+    
+    pad.add_probe(
+        gst::PadProbeType::BUFFER | gst::PadProbeType::EVENT_DOWNSTREAM,
+        move |_, info| {
+            match info.data {
+                Some(gst::PadProbeData::Event(ref event)) => {
+                    if let gst::EventView::Segment(e) = event.view()
+                        && let Some(new) = e.segment().downcast_ref::<gst::ClockTime>()
+                    {
+                        *segment.lock().unwrap() = new.clone();
+                    }
+                }
+                Some(gst::PadProbeData::Buffer(ref buffer)) => {
+                    if let Some(running) = buffer.pts().and_then(|pts| segment.lock().unwrap().to_running_time(pts))
+                        && let Some(volume) = weak.upgrade()
+                    {
+                        let t = offset + running.nseconds() as f64 / 1_000_000_000.0;
+                        volume.set_property("volume", duck.gain_at(t));
+                    }
+                }
+                _ => {}
+            }
+            gst::PadProbeReturn::Ok
+        },
+    );
+}
+
+/// Read a media file's duration by prerolling it with GStreamer
+fn probe_duration_gst(path: &Path) -> Option<f64> {
+    let uri = format!("file://{}", path.canonicalize().ok()?.display());
+    let pipeline = gst::parse::launch(&format!("uridecodebin uri=\"{}\" ! fakesink", uri))
+        .ok()?
+        .downcast::<gst::Pipeline>()
+        .ok()?;
+    
+    pipeline.set_state(gst::State::Paused).ok()?;
+    let _ = pipeline.state(gst::ClockTime::from_seconds(5));
+    let duration = pipeline.query_duration::<gst::ClockTime>();
+    let _ = pipeline.set_state(gst::State::Null);
+    
+    duration.map(|d| d.nseconds() as f64 / 1_000_000_000.0)
+}
+
+/// Fraction of the concatenated clips already passed, taken from the
+/// `concat` element's active pad (`sink_N` is clip N)
+fn concat_progress(pipeline: &gst::Pipeline) -> Option<f64> {
+    let concat = pipeline.by_name("vcat").or_else(|| pipeline.by_name("acat"))?;
+    let clip_count = concat.num_sink_pads();
+    if clip_count == 0 {
+        return None;
+    }
+    
+    let active_pad = concat.property::<Option<gst::Pad>>("active-pad")?;
+    let index: u16 = active_pad.name().strip_prefix("sink_")?.parse().ok()?;
+    
+    Some(f64::from(index) / f64::from(clip_count))
+}
+
+/// Report how far the pipeline has got through `range` (or the whole input)
+///
+/// Duration queries on freshly started multi-clip pipelines often fail,
+/// so those fall back to counting finished clips.
+fn report_gst_progress(pipeline: &gst::Pipeline, range: Option<(f64, f64)>, on_progress: Option<&FractionCallback>) {
+    let Some(callback) = on_progress else {
+        return;
+    };
+    let seconds = |time: gst::ClockTime| time.nseconds() as f64 / 1_000_000_000.0;
+    
+    if let Some(position) = pipeline.query_position::<gst::ClockTime>()
+        && let Some((start, end)) = range.or_else(|| {
+            pipeline.query_duration::<gst::ClockTime>().map(|duration| (0.0, seconds(duration)))
+        })
+        && end > start
+    {
+        callback(((seconds(position) - start) / (end - start)).clamp(0.0, 1.0));
+    } else if let Some(progress) = concat_progress(pipeline) {
+        callback(progress);
+    }
 }
 
-/// Run a GStreamer pipeline from string
-fn run_gst_pipeline(pipeline_str: &str) -> Result<()> {
+/// Run a GStreamer pipeline from string, optionally only over `range`
+/// (start, end) seconds
+fn run_gst_pipeline(
+    pipeline_str: &str,
+    on_progress: Option<&FractionCallback>,
+    cancel: &AtomicBool,
+    range: Option<(f64, f64)>,
+    duck: Option<&DuckEnvelope>,
+) -> Result<()> {
     tracing::debug!("GStreamer pipeline:\n{}", pipeline_str);
     
     let pipeline = gst::parse::launch(pipeline_str)
@@ -260,13 +2166,50 @@ fn run_gst_pipeline(pipeline_str: &str) -> Result<()> {
         .downcast::<gst::Pipeline>()
         .map_err(|_| anyhow::anyhow!("Not a pipeline"))?;
     
+    if let Some(duck) = duck
+        && let Some(volume) = pipeline.by_name("clip_duck")
+    {
+        follow_duck_envelope(&volume, duck.clone(), range.map_or(0.0, |(start, _)| start));
+    }
+    
+    if let Some((start, end)) = range {
+        // A segment seek makes every source start and end there; the muxer
+        // works in running time, so the file still starts at zero
+        let clock_time = |seconds: f64| gst::ClockTime::from_nseconds((seconds * 1_000_000_000.0) as u64);
+        pipeline.set_state(gst::State::Paused)?;
+        let _ = pipeline.state(gst::ClockTime::from_seconds(10));
+        pipeline
+            .seek(
+                1.0,
+                gst::SeekFlags::FLUSH | gst::SeekFlags::ACCURATE,
+                gst::SeekType::Set,
+                clock_time(start),
+                gst::SeekType::Set,
+                clock_time(end),
+            )
+            .context("Failed to set export range")?;
+    }
+    
     pipeline.set_state(gst::State::Playing)?;
     
-    let bus = pipeline.bus().unwrap();
+    let bus = pipeline.bus().context("Pipeline has no bus")?;
     
-    for msg in bus.iter_timed(gst::ClockTime::NONE) {
+    loop {
         use gst::MessageView;
         
+        if cancel.load(Ordering::Relaxed) {
+            tracing::info!("GStreamer: export cancelled");
+            pipeline.set_state(gst::State::Null)?;
+            return Err(ExportError::Cancelled.into());
+        }
+        
+        report_gst_progress(&pipeline, range, on_progress);
+        
+        // Wake up regularly so cancellation and progress stay responsive
+        let Some(msg) = bus.timed_pop(gst::ClockTime::from_mseconds(100)) else {
+            continue;
+        };
+        
         match msg.view() {
             MessageView::Eos(..) => {
                 tracing::info!("GStreamer: End of stream");
@@ -297,5 +2240,122 @@ fn run_gst_pipeline(pipeline_str: &str) -> Result<()> {
     }
     
     pipeline.set_state(gst::State::Null)?;
+    
+    if let Some(callback) = on_progress {
+        callback(1.0);
+    }
+    
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    
+    #[test]
+    fn test_times_after_cuts() {
+        let cuts = [(2.0, 3.0), (5.0, 7.5)];
+        
+        assert_eq!(time_after_cuts(&cuts, 1.0), 1.0);
+        assert_eq!(time_after_cuts(&cuts, 4.0), 3.0);
+        assert_eq!(time_after_cuts(&cuts, 10.0), 6.5);
+        // Inside a cut, a time lands where the cut was
+        assert_eq!(time_after_cuts(&cuts, 6.0), 4.0);
+        assert_eq!(time_after_cuts(&[], 6.0), 6.0);
+    }
+    
+    #[test]
+    fn test_edl_events_and_record_times() {
+        let mut project = Project::new("Cut");
+        project.add_clip("intro".to_string(), PathBuf::from("/clips/intro.mp4"));
+        project.add_clip("photo".to_string(), PathBuf::from("/clips/photo.jpg"));
+        project.add_clip("pending".to_string(), PathBuf::from("/clips/pending.mp4"));
+        project.clips[0].duration = Some(2.5);
+        project.clips[0].source_in = 1.0;
+        project.update_start_times();
+        project.set_audio(PathBuf::from("/audio/voice.mp3"), 10.0, 44100);
+        project.audio.as_mut().unwrap().cuts = vec![(2.0, 3.0)];
+        
+        let (edl, report) = render_edl(&project, 30.0);
+        let events: Vec<&str> = edl.lines().filter(|l| l.starts_with(|c: char| c.is_ascii_digit())).collect();
+        assert_eq!(events, [
+            "001  AX       V     C        00:00:01:00 00:00:03:15 01:00:00:00 01:00:02:15",
+            "002  AX       V     C        00:00:00:00 00:00:05:00 01:00:02:15 01:00:07:15",
+            "003  AX       A     C        00:00:00:00 00:00:02:00 01:00:00:00 01:00:02:00",
+            "004  AX       A     C        00:00:03:00 00:00:10:00 01:00:02:00 01:00:09:00",
+        ]);
+        assert!(edl.starts_with("TITLE: Cut\nFCM: NON-DROP FRAME\n"));
+        assert!(edl.contains("* FROM CLIP NAME: intro.mp4\n* SOURCE FILE: /clips/intro.mp4\n* COMMENT: intro\n"));
+        assert_eq!(report, EdlReport { events: 4, skipped: vec!["pending".to_string()] });
+    }
+    
+    fn voiceover(segments: &[(f64, f64)]) -> Project {
+        let mut project = Project::new("Duck");
+        project.set_audio(PathBuf::from("/audio/voice.mp3"), 10.0, 44100);
+        project.transcript = Some(transcription::Transcript {
+            text: String::new(),
+            segments: segments
+                .iter()
+                .map(|&(start, end)| transcription::TranscriptSegment { start, end, text: "words".to_string(), words: Vec::new() })
+                .collect(),
+            language: None,
+            duration: 10.0,
+        });
+        project
+    }
+    
+    #[test]
+    fn test_music_dips_during_speech() {
+        // The short pause inside the first sentence stays ducked
+        let project = voiceover(&[(1.0, 2.0), (2.5, 3.0), (6.0, 7.0)]);
+        let duck = DuckEnvelope::for_project(&project, &ExportSettings::default(), &[]).unwrap();
+        assert_eq!(duck.speech, [(1.0, 3.0), (6.0, 7.0)]);
+        
+        let floor = 10_f64.powf(-0.5);
+        let close = |t: f64, gain: f64| (duck.gain_at(t) - gain).abs() < 1e-9;
+        assert!(close(0.0, 1.0));
+        // Halfway through the attack ramp before the first word
+        assert!(close(0.85, 1.0 - (1.0 - floor) * 0.5));
+        assert!(close(1.5, floor));
+        assert!(close(2.2, floor));
+        assert!(close(4.0, 1.0));
+        assert!(close(6.5, floor));
+        
+        assert_eq!(
+            duck.ffmpeg_expr(),
+            "1-0.6838*(clip(min((t-0.700)/0.300,(3.600-t)/0.600),0,1)+clip(min((t-5.700)/0.300,(7.600-t)/0.600),0,1))"
+        );
+    }
+    
+    #[test]
+    fn test_no_ducking_without_speech_or_clip_audio() {
+        let project = voiceover(&[(1.0, 2.0)]);
+        let off = ExportSettings { ducking_db: 0.0, ..ExportSettings::default() };
+        assert!(DuckEnvelope::for_project(&project, &off, &[]).is_none());
+        let muted = ExportSettings { mute_clip_audio: true, ..ExportSettings::default() };
+        assert!(DuckEnvelope::for_project(&project, &muted, &[]).is_none());
+        assert!(DuckEnvelope::for_project(&voiceover(&[]), &ExportSettings::default(), &[]).is_none());
+    }
+    
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("65"), Some(65.0));
+        assert_eq!(parse_timestamp(" 1:05 "), Some(65.0));
+        assert_eq!(parse_timestamp("0:01:05.5"), Some(65.5));
+        for bad in ["", "-3", "1:-5", "abc", "NaN", "inf", "1:NaN", "-inf"] {
+            assert_eq!(parse_timestamp(bad), None, "{:?}", bad);
+        }
+    }
+    
+    #[test]
+    fn test_check_range() {
+        assert!(check_range((5.0, 10.0), Some(10.2)).is_ok());
+        assert!(check_range((5.0, 10.0), None).is_ok());
+        assert!(check_range((-1.0, 10.0), None).is_err());
+        assert!(check_range((10.0, 10.0), None).is_err());
+        assert!(check_range((5.0, 12.0), Some(10.0)).is_err());
+        assert!(check_range((f64::NAN, f64::NAN), None).is_err());
+        assert!(check_range((0.0, f64::INFINITY), None).is_err());
+    }
+}
@@ -4,16 +4,23 @@ mod auto_video;
 mod clips_panel;
 mod config;
 mod export;
+mod media_probe;
+mod offline;
 mod pexels;
 mod player;
 mod project;
 mod prompt;
 mod startup;
+mod text_buffer;
+mod thumbnails;
 mod transcription;
+mod undo;
 mod video;
 mod waveform;
 
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 use audio::AudioData;
 use clips_panel::{ClipsPanel, ClipsPanelEvent};
@@ -22,8 +29,34 @@ use gpui::*;
 use player::{ProjectPlayer, PlayerState};
 use project::Project;
 use prompt::{PromptEvent, PromptInput};
+use undo::UndoStack;
 use waveform::{Timeline, TimelineEvent};
 
+actions!(
+    montage,
+    [Export, Open, Redo, Save, SaveAs, SetInPoint, SetOutPoint, SplitAtPlayhead, StepBackward, StepForward, ToggleHelp, ToggleLoop, TogglePlayback, Undo]
+);
+
+/// Key bindings listed in the help overlay
+const SHORTCUTS: &[(&str, &str)] = &[
+    ("Cmd/Ctrl+S", "Save"),
+    ("Cmd/Ctrl+Shift+S", "Save as"),
+    ("Cmd/Ctrl+O", "Open project"),
+    ("Cmd/Ctrl+E", "Export options"),
+    ("Cmd/Ctrl+Z", "Undo"),
+    ("Cmd/Ctrl+Shift+Z", "Redo"),
+    ("Space", "Play / pause"),
+    ("L", "Loop the preview on / off"),
+    (", / .", "Step one frame back / forward"),
+    ("I / O", "Export from / to the playhead"),
+    ("S", "Split the clip at the playhead"),
+    ("Delete", "Remove selected clips"),
+    ("?", "Show / hide this list"),
+];
+
+/// Quiet time after the last edit before it's autosaved
+const AUTOSAVE_DELAY: Duration = Duration::from_secs(5);
+
 fn main() {
     tracing_subscriber::fmt::init();
     
@@ -37,6 +70,26 @@ fn main() {
     tracing::info!("GStreamer initialized");
 
     Application::new().run(|cx| {
+        // Single keys only apply outside text fields, which type them instead
+        cx.bind_keys([
+            KeyBinding::new("?", ToggleHelp, Some("MainView && !TextInput")),
+            KeyBinding::new(",", StepBackward, Some("MainView && !TextInput")),
+            KeyBinding::new(".", StepForward, Some("MainView && !TextInput")),
+            KeyBinding::new("i", SetInPoint, Some("MainView && !TextInput")),
+            KeyBinding::new("l", ToggleLoop, Some("MainView && !TextInput")),
+            KeyBinding::new("o", SetOutPoint, Some("MainView && !TextInput")),
+            KeyBinding::new("s", SplitAtPlayhead, Some("MainView && !TextInput")),
+            // File shortcuts stay out of the way while typing a command
+            KeyBinding::new("secondary-e", Export, Some("MainView && !TextInput")),
+            KeyBinding::new("secondary-o", Open, Some("MainView && !TextInput")),
+            KeyBinding::new("secondary-s", Save, Some("MainView && !TextInput")),
+            KeyBinding::new("secondary-shift-s", SaveAs, Some("MainView && !TextInput")),
+            KeyBinding::new("secondary-shift-z", Redo, Some("MainView")),
+            KeyBinding::new("secondary-z", Undo, Some("MainView")),
+            KeyBinding::new("shift-/", ToggleHelp, Some("MainView && !TextInput")),
+            KeyBinding::new("space", TogglePlayback, Some("MainView && !TextInput")),
+        ]);
+        
         cx.open_window(
             WindowOptions {
                 titlebar: Some(TitlebarOptions {
@@ -64,10 +117,26 @@ struct MainView {
     project: Project,
     /// Path to the current project file (if saved)
     project_path: Option<std::path::PathBuf>,
+    /// Whether the project changed since it was last saved or autosaved
+    dirty: bool,
+    /// Pending autosave; replaced on every edit so it fires once things settle
+    autosave_task: Option<Task<()>>,
+    /// Where this session autosaves while the project has no path
+    untitled_autosave: Option<std::path::PathBuf>,
+    /// Autosave from an earlier session the user can restore or discard
+    recovery: Option<std::path::PathBuf>,
+    /// Agent edits held back until the user applies or discards them
+    pending_edits: Option<PendingEdits>,
     /// Clips panel showing all clips
     clips_panel: Entity<ClipsPanel>,
     /// Prompt input for agentic interactions
     prompt: Entity<PromptInput>,
+    /// Agent request in flight; dropping it cancels the request
+    agent_task: Option<Task<()>>,
+    /// Runs agent HTTP calls; started by the first request
+    agent_runtime: Option<agent::AgentRuntime>,
+    /// The pending reply's message so far, as it streams in
+    agent_stream: Option<std::sync::mpsc::Receiver<String>>,
     /// App state
     state: AppState,
     /// Unified project player (preview + export use same pipeline)
@@ -78,6 +147,90 @@ struct MainView {
     last_agent_results: Vec<String>,
     /// Service status
     service_status: startup::ServiceStatus,
+    /// Background service check; replacing it cancels the previous one
+    service_check: Option<Task<()>>,
+    /// Validation of a newly set Pexels key, running in the background
+    pexels_check: Option<Task<()>>,
+    /// Focus for the preview's arrow-key scrubbing
+    preview_focus: FocusHandle,
+    /// Selected image clip, shown in the preview while nothing plays
+    still_preview: Option<Arc<RenderImage>>,
+    /// Pixels of `still_preview`, for saving it
+    still_frame: Option<video::VideoFrame>,
+    /// Whether the export options are showing
+    export_dialog_open: bool,
+    /// Export currently running in the background (if any)
+    export_job: Option<ExportJob>,
+    /// Where the last finished export was written, for files that go beside it
+    last_export: Option<std::path::PathBuf>,
+    /// Auto-video generation in progress, if any
+    auto_video_job: Option<AutoVideoJob>,
+    /// Frame size for the next export
+    export_preset: export::ExportPreset,
+    /// How clips are fitted into that frame
+    export_aspect_mode: export::AspectMode,
+    /// File type of the next export
+    export_format: export::OutputFormat,
+    /// Scale of that frame (custom sizes keep their own)
+    export_resolution: export::Resolution,
+    /// Named quality tier, or the default bitrate when unset
+    export_quality: Option<export::Quality>,
+    /// Whether the next export includes subtitles
+    export_subtitles: export::SubtitleMode,
+    /// How the next export moves between clips
+    export_transition: export::Transition,
+    /// H.264 encoder for the next export
+    export_encoder: export::VideoEncoder,
+    /// Whether the next export evens out loudness
+    export_normalize_audio: bool,
+    /// Clip audio volume under the voiceover (0 mutes it)
+    export_clip_audio_gain: f64,
+    /// Whether the next export turns markers into chapters
+    export_chapters: bool,
+    /// Part of the timeline the next export covers, in seconds (None: all of it)
+    export_range: Option<(f64, f64)>,
+    /// Focus handle so app-wide shortcuts have somewhere to land
+    focus_handle: FocusHandle,
+    /// Whether the keyboard shortcut list is showing
+    help_open: bool,
+    /// Project snapshots for undo/redo
+    undo_stack: UndoStack,
+}
+
+/// A running export, as seen by the UI
+struct ExportJob {
+    /// Flipped by the Cancel button; polled by the export thread
+    cancel: Arc<AtomicBool>,
+    /// Last reported progress
+    progress: export::ExportProgress,
+    /// When the encoder was started
+    started_at: Instant,
+}
+
+/// A running auto-video generation, as seen by the UI
+struct AutoVideoJob {
+    /// Flipped by the Cancel button; checked between segments and download chunks
+    cancel: Arc<AtomicBool>,
+    /// Latest progress of each stage reached so far, in order
+    stages: Vec<auto_video::AutoVideoProgress>,
+}
+
+impl AutoVideoJob {
+    /// Update the current stage, or start the next one
+    fn record(&mut self, progress: auto_video::AutoVideoProgress) {
+        match self.stages.last_mut() {
+            Some(last) if last.same_stage(&progress) => *last = progress,
+            _ => self.stages.push(progress),
+        }
+    }
+}
+
+/// Agent edits that remove or split clips, waiting for Apply
+struct PendingEdits {
+    modifications: Vec<agent::Modification>,
+    /// What they would do, in words
+    summary: String,
+    undo_label: String,
 }
 
 enum AppState {
@@ -88,20 +241,27 @@ enum AppState {
 }
 
 impl MainView {
-    fn new(_window: &mut Window, cx: &mut Context<Self>) -> Self {
+    fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
         let config = AppConfig::load();
-        let clips_panel = cx.new(|_cx| ClipsPanel::new());
+        let clips_panel = cx.new(ClipsPanel::new);
         let prompt = cx.new(PromptInput::new);
+        prompt.update(cx, |prompt, _cx| prompt.set_history(config.prompt_history.clone()));
         
         // Subscribe to clips panel events
         cx.subscribe(&clips_panel, |this, _panel, event: &ClipsPanelEvent, cx| {
             match event {
                 ClipsPanelEvent::SelectClip(id) => {
                     tracing::info!("Selected clip: {}", id);
-                    // TODO: Load clip into preview
+                    this.preview_still(id, cx);
+                    this.seek_to_clip(id, cx);
+                    if let AppState::Loaded { ref timeline } = this.state {
+                        timeline.update(cx, |timeline, cx| timeline.set_selected_clip(Some(id.clone()), cx));
+                    }
                 }
                 ClipsPanelEvent::DeleteClip(id) => {
-                    this.project.clips.retain(|c| c.id != *id);
+                    this.undo_stack.record("delete clip", &this.project);
+                    this.mark_dirty(cx);
+                    this.project.remove_clips(std::slice::from_ref(id));
                     this.sync_clips_panel(cx);
                     this.last_agent_message = Some("Clip deleted".to_string());
                     this.last_agent_results = vec![];
@@ -111,7 +271,9 @@ impl MainView {
                     if let Some(idx) = this.project.clips.iter().position(|c| c.id == *id)
                         && idx > 0
                     {
-                        this.project.clips.swap(idx, idx - 1);
+                        this.undo_stack.record("move clip up", &this.project);
+                        this.mark_dirty(cx);
+                        this.project.swap_clips(idx, idx - 1);
                         this.sync_clips_panel(cx);
                         cx.notify();
                     }
@@ -120,11 +282,74 @@ impl MainView {
                     if let Some(idx) = this.project.clips.iter().position(|c| c.id == *id)
                         && idx < this.project.clips.len() - 1
                     {
-                        this.project.clips.swap(idx, idx + 1);
+                        this.undo_stack.record("move clip down", &this.project);
+                        this.mark_dirty(cx);
+                        this.project.swap_clips(idx, idx + 1);
+                        this.sync_clips_panel(cx);
+                        cx.notify();
+                    }
+                }
+                ClipsPanelEvent::DeleteMany(ids) => {
+                    this.undo_stack.record(format!("delete {} clips", ids.len()), &this.project);
+                    this.mark_dirty(cx);
+                    this.project.remove_clips(ids);
+                    this.sync_clips_panel(cx);
+                    this.last_agent_message = Some(format!("{} clips deleted", ids.len()));
+                    this.last_agent_results = vec![];
+                    cx.notify();
+                }
+                ClipsPanelEvent::MoveMany { indices, slot } => {
+                    this.undo_stack.record("move clips", &this.project);
+                    this.mark_dirty(cx);
+                    this.project.move_clips(indices, *slot);
+                    this.sync_clips_panel(cx);
+                    cx.notify();
+                }
+                ClipsPanelEvent::Rename { id, new_description } => {
+                    if let Some(index) = this.project.clips.iter().position(|c| c.id == *id) {
+                        this.undo_stack.record("rename clip", &this.project);
+                        this.mark_dirty(cx);
+                        this.project.clips[index].description = new_description.clone();
+                        this.sync_clips_panel(cx);
+                        this.last_agent_message = Some(format!("Renamed clip to '{}'", new_description));
+                        this.last_agent_results = vec![];
+                        cx.notify();
+                    }
+                }
+                ClipsPanelEvent::DuplicateClip(id) => {
+                    if let Some(index) = this.project.clips.iter().position(|c| c.id == *id) {
+                        this.undo_stack.record("duplicate clip", &this.project);
+                        this.mark_dirty(cx);
+                        let copy = this.project.duplicate_clip(index);
+                        this.last_agent_message = Some(format!("Duplicated '{}'", copy.description));
+                        this.last_agent_results = vec![];
                         this.sync_clips_panel(cx);
                         cx.notify();
                     }
                 }
+                ClipsPanelEvent::SetLabel { ids, label } => {
+                    this.undo_stack.record("label clips", &this.project);
+                    this.mark_dirty(cx);
+                    for clip in this.project.clips.iter_mut().filter(|c| ids.contains(&c.id)) {
+                        clip.label = label.clone();
+                    }
+                    this.sync_clips_panel(cx);
+                    cx.notify();
+                }
+                ClipsPanelEvent::Reorder { from, to } => {
+                    let (from, to) = (*from, *to);
+                    let len = this.project.clips.len();
+                    if from != to && from < len && to < len {
+                        this.undo_stack.record("reorder clips", &this.project);
+                        this.mark_dirty(cx);
+                        this.project.move_clip(from, to);
+                        this.sync_clips_panel(cx);
+                        cx.notify();
+                    }
+                }
+                ClipsPanelEvent::InsertFiles { index, paths } => {
+                    this.add_dropped_files(paths.clone(), Some(*index), cx);
+                }
             }
         })
         .detach();
@@ -132,30 +357,84 @@ impl MainView {
         // Subscribe to prompt events
         cx.subscribe(&prompt, |this, _prompt, event: &PromptEvent, cx| {
             match event {
+                PromptEvent::Cancel => this.cancel_agent(cx),
                 PromptEvent::Submit { text, attachments } => {
+                    if !text.trim().is_empty() {
+                        this.config.push_prompt_history(text.clone());
+                    }
                     this.handle_prompt(text.clone(), attachments.clone(), cx);
                 }
             }
         })
         .detach();
         
-        // Check service status
-        let service_status = startup::ServiceStatus::check(&config.pexels_api_key);
+        // Services are checked in the background once the window is up
+        let service_status = startup::ServiceStatus::checking(&config.pexels_api_key, config.agent_backend);
         let greeting = service_status.greeting_message();
         
+        let focus_handle = cx.focus_handle();
+        focus_handle.focus(window, cx);
+        
+        // Flush any pending autosave on the way out
+        let view = cx.weak_entity();
+        window.on_window_should_close(cx, move |_window, cx| {
+            let _ = view.update(cx, |this, _cx| this.autosave());
+            true
+        });
+        cx.on_app_quit(|this, _cx| {
+            this.autosave();
+            async {}
+        })
+        .detach();
+        
         let mut view = Self {
             config,
             project: Project::new("Untitled"),
             project_path: None,
+            dirty: false,
+            autosave_task: None,
+            untitled_autosave: None,
+            recovery: None,
+            pending_edits: None,
             clips_panel,
             prompt,
+            agent_task: None,
+            agent_runtime: None,
+            agent_stream: None,
             state: AppState::Empty,
             player: ProjectPlayer::new(),
             last_agent_message: Some(greeting),
             last_agent_results: vec![],
             service_status,
+            service_check: None,
+            pexels_check: None,
+            preview_focus: cx.focus_handle(),
+            still_preview: None,
+            still_frame: None,
+            export_dialog_open: false,
+            export_job: None,
+            last_export: None,
+            auto_video_job: None,
+            export_preset: export::ExportPreset::Landscape1080p,
+            export_aspect_mode: export::AspectMode::Letterbox,
+            export_format: export::OutputFormat::default(),
+            export_resolution: export::Resolution::default(),
+            export_quality: None,
+            export_subtitles: export::SubtitleMode::None,
+            export_transition: export::Transition::Cut,
+            export_encoder: export::VideoEncoder::Software,
+            export_normalize_audio: false,
+            export_clip_audio_gain: 1.0,
+            export_chapters: false,
+            export_range: None,
+            focus_handle,
+            help_open: false,
+            undo_stack: UndoStack::new(),
         };
         
+        view.apply_export_preferences();
+        view.sync_theme(cx);
+        
         // Auto-load last project if exists
         if let Some(ref last_project) = view.config.last_project.clone()
             && last_project.exists()
@@ -163,17 +442,80 @@ impl MainView {
             tracing::info!("Auto-loading last project: {:?}", last_project);
             view.load_project_from_path(last_project.clone(), cx);
         }
+        if view.project_path.is_none() {
+            view.find_recovery();
+        }
+        view.check_services(cx);
+        view.start_saved_clock(cx);
         
         view
     }
     
+    /// Re-check the agent backend, Whisper and Pexels off the UI thread,
+    /// refreshing the welcome message if it is still what's showing
+    fn check_services(&mut self, cx: &mut Context<Self>) {
+        let pexels_key = self.config.pexels_api_key.clone();
+        let backend = self.config.agent_backend();
+        let whisper = self.config.transcribe_options();
+        
+        let greeting_showing = self.last_agent_message.as_ref() == Some(&self.service_status.greeting_message());
+        self.service_status = startup::ServiceStatus::checking(&pexels_key, backend.kind());
+        if greeting_showing {
+            self.last_agent_message = Some(self.service_status.greeting_message());
+        }
+        
+        let task = cx.background_executor().spawn(async move {
+            startup::ServiceStatus::check(&pexels_key, &*backend, &whisper)
+        });
+        self.service_check = Some(cx.spawn(async move |this, cx| {
+            let status = task.await;
+            let _ = this.update(cx, |this, cx| {
+                if this.last_agent_message.as_ref() == Some(&this.service_status.greeting_message()) {
+                    this.last_agent_message = Some(status.greeting_message());
+                } else if let startup::AgentStatus::NoModel(ref missing) = status.agent
+                    && status.backend == agent::AgentBackendKind::Ollama
+                {
+                    this.last_agent_results.push(format!("⚠ Model not installed, run `ollama pull {}`", missing));
+                }
+                this.service_status = status;
+                this.service_check = None;
+                cx.notify();
+            });
+        }));
+        cx.notify();
+    }
+    
+    /// Validate the configured Pexels key and say whether it works
+    fn check_pexels_key(&mut self, cx: &mut Context<Self>) {
+        let pexels_key = self.config.pexels_api_key.clone();
+        self.service_status.pexels = startup::PexelsStatus::checking(&pexels_key);
+        
+        let task = cx.background_executor().spawn(async move {
+            startup::check_pexels(&pexels_key)
+        });
+        self.pexels_check = Some(cx.spawn(async move |this, cx| {
+            let status = task.await;
+            let _ = this.update(cx, |this, cx| {
+                this.last_agent_results.push(status.summary());
+                this.service_status.pexels = status;
+                this.pexels_check = None;
+                cx.notify();
+            });
+        }));
+        cx.notify();
+    }
+    
     /// Load a project from a specific path
     fn load_project_from_path(&mut self, path: std::path::PathBuf, cx: &mut Context<Self>) {
         match Project::load(&path) {
             Ok(project) => {
                 self.project = project;
                 self.project_path = Some(path.clone());
+                self.dirty = false;
+                self.untitled_autosave = None;
                 self.state = AppState::Empty;
+                self.export_range = None;
+                self.apply_export_preferences();
                 
                 // Update config with this project
                 self.config.set_last_project(path);
@@ -196,6 +538,7 @@ impl MainView {
                 self.sync_clips_panel(cx);
                 
                 tracing::info!("Loaded project: {}", self.project.metadata.name);
+                self.find_recovery();
             }
             Err(e) => {
                 tracing::error!("Failed to load project: {}", e);
@@ -234,14 +577,17 @@ impl MainView {
                         self.load_video(file.clone(), cx);
                     }
                     project::MediaType::Image => {
-                        tracing::info!("Image support coming soon");
+                        // Stills need no decoding; export holds them on screen
+                        tracing::info!("Added still image: {:?}", file);
                     }
                 }
             }
             
+            self.mark_dirty(cx);
             self.last_agent_message = Some(format!("Added {} file(s) to project", attachments.len()));
             self.last_agent_results = vec![];
             self.sync_clips_panel(cx);
+            self.probe_clip_durations(cx);
             cx.notify();
             return;
         }
@@ -256,9 +602,189 @@ impl MainView {
     fn sync_clips_panel(&mut self, cx: &mut Context<Self>) {
         let clips = self.project.clips.clone();
         self.clips_panel.update(cx, |panel, cx| {
-            panel.set_clips(clips);
+            panel.set_clips(clips, cx);
+            cx.notify();
+        });
+        self.sync_timeline_clips(cx);
+        self.sync_player(cx);
+    }
+    
+    /// Lay the clips out in the timeline's clip lane
+    fn sync_timeline_clips(&mut self, cx: &mut Context<Self>) {
+        let clips = &self.project.clips;
+        if let AppState::Loaded { ref timeline } = self.state {
+            timeline.update(cx, |timeline, cx| timeline.set_clips(clips, cx));
+        }
+    }
+    
+    /// Fill in the length of clips that don't have one yet, off the UI thread.
+    /// A file that can't be probed just keeps an unknown length.
+    fn probe_clip_durations(&mut self, cx: &mut Context<Self>) {
+        // Stills already have a length, but still need to open
+        let pending: Vec<(String, std::path::PathBuf, project::MediaType)> = self.project.clips
+            .iter()
+            .filter(|c| c.error.is_none() && (c.duration.is_none() || c.media_type == project::MediaType::Image))
+            .map(|c| (c.id.clone(), c.path.clone(), c.media_type.clone()))
+            .collect();
+        if pending.is_empty() {
+            return;
+        }
+        
+        let task = cx.background_executor().spawn(async move {
+            pending
+                .into_iter()
+                .filter_map(|(id, path, media_type)| match media_probe::check(&path, &media_type) {
+                    Ok(duration) => duration.map(|seconds| (id, Ok(seconds))),
+                    Err(e) => {
+                        tracing::warn!("Unusable clip {:?}: {:#}", path, e);
+                        Some((id, Err(e.to_string())))
+                    }
+                })
+                .collect::<Vec<_>>()
+        });
+        
+        cx.spawn(async move |this, cx| {
+            let probed = task.await;
+            if probed.is_empty() {
+                return;
+            }
+            let _ = this.update(cx, |this, cx| {
+                let mut broken = Vec::new();
+                for (id, result) in probed {
+                    match result {
+                        Ok(seconds) => {
+                            this.project.set_clip_duration(&id, seconds);
+                            this.mark_dirty(cx);
+                        }
+                        Err(e) => {
+                            this.project.set_clip_error(&id, e);
+                            broken.push(id);
+                        }
+                    }
+                }
+                if !broken.is_empty() {
+                    let names: Vec<String> = this.project.clips
+                        .iter()
+                        .filter(|c| broken.contains(&c.id))
+                        .map(|c| format!("⚠ \"{}\": {}", c.description, c.error.as_deref().unwrap_or_default()))
+                        .collect();
+                    this.last_agent_results.extend(names);
+                }
+                this.sync_clips_panel(cx);
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+    
+    /// Push the project's markers onto the timeline
+    fn sync_markers(&mut self, cx: &mut Context<Self>) {
+        let markers = &self.project.markers;
+        if let AppState::Loaded { ref timeline } = self.state {
+            timeline.update(cx, |timeline, cx| timeline.set_markers(markers, cx));
+        }
+    }
+    
+    /// Shade the voiceover's cut stretches on the timeline
+    fn sync_cuts(&mut self, cx: &mut Context<Self>) {
+        let cuts = self.project.audio.as_ref().map_or(&[][..], |a| &a.cuts);
+        if let AppState::Loaded { ref timeline } = self.state {
+            timeline.update(cx, |timeline, cx| timeline.set_cuts(cuts, cx));
+        }
+    }
+    
+    /// Leave the voiceover's silences out of the export, found again with
+    /// `settings` (threshold dB, minimum seconds) or as the timeline shows
+    /// them; returns what happened
+    fn cut_silences(&mut self, settings: Option<(f64, f64)>, cx: &mut Context<Self>) -> String {
+        let AppState::Loaded { ref timeline } = self.state else {
+            return "⚠ Load a voiceover first".to_string();
+        };
+        let silences = match settings {
+            Some((threshold_db, min_duration)) => {
+                timeline.update(cx, |timeline, cx| timeline.detect_silence(threshold_db, min_duration, cx))
+            }
+            None => timeline.read(cx).silences().to_vec(),
+        };
+        if silences.is_empty() {
+            return "No silences long enough to cut".to_string();
+        }
+        
+        let total: f64 = silences.iter().map(|(start, end)| end - start).sum();
+        let summary = format!("✂ {} silence(s), {:.1}s in all, will be left out of the export", silences.len(), total);
+        self.undo_stack.record("cut silences", &self.project);
+        if let Some(ref mut audio) = self.project.audio {
+            audio.cuts = silences;
+        }
+        self.mark_dirty(cx);
+        self.sync_cuts(cx);
+        summary
+    }
+    
+    /// Hand the configured palette to the views that draw themselves
+    fn sync_theme(&mut self, cx: &mut Context<Self>) {
+        let theme = self.config.theme.theme();
+        self.clips_panel.update(cx, |panel, cx| {
+            panel.set_theme(theme);
             cx.notify();
         });
+        if let AppState::Loaded { ref timeline } = self.state {
+            timeline.update(cx, |timeline, cx| timeline.set_theme(theme, cx));
+        }
+        cx.notify();
+    }
+    
+    /// Show the export range on the timeline
+    fn sync_export_range(&mut self, cx: &mut Context<Self>) {
+        let range = self.export_range;
+        if let AppState::Loaded { ref timeline } = self.state {
+            timeline.update(cx, |timeline, cx| timeline.set_range(range, cx));
+        }
+    }
+    
+    /// How long the exported video will be, once every clip's length is known
+    fn export_duration(&self) -> Option<f64> {
+        self.project.video_duration().filter(|&duration| duration > 0.0)
+    }
+    
+    /// Change the export range, unless it doesn't fit the video
+    fn set_export_range(&mut self, range: Option<(f64, f64)>, cx: &mut Context<Self>) -> Result<(), String> {
+        if let Some(range) = range {
+            export::check_range(range, self.export_duration())?;
+        }
+        self.export_range = range;
+        self.sync_export_range(cx);
+        cx.notify();
+        Ok(())
+    }
+    
+    /// Change the export range from the UI, saying what happened
+    fn pick_export_range(&mut self, range: Option<(f64, f64)>, cx: &mut Context<Self>) {
+        self.last_agent_message = Some(match self.set_export_range(range, cx) {
+            Ok(()) => describe_export_range(range),
+            Err(e) => format!("❌ {}", e),
+        });
+        self.last_agent_results = vec![];
+        cx.notify();
+    }
+    
+    /// Rebuild the player if the project's media changed since it was loaded
+    fn sync_player(&mut self, cx: &mut Context<Self>) {
+        if !self.player.matches_project(&self.project) {
+            self.reload_player(cx);
+        }
+    }
+    
+    /// Redraw now and then so "Last saved 5 min ago" keeps counting
+    fn start_saved_clock(&mut self, cx: &mut Context<Self>) {
+        cx.spawn(async move |this, cx| {
+            loop {
+                cx.background_executor().timer(Duration::from_secs(30)).await;
+                if this.update(cx, |_this, cx| cx.notify()).is_err() {
+                    break;
+                }
+            }
+        }).detach();
     }
     
     /// Start the thinking dots animation
@@ -287,28 +813,109 @@ impl MainView {
         }).detach();
     }
     
+    /// Show the agent's message while it is being written
+    fn start_stream_updates(&mut self, cx: &mut Context<Self>) {
+        cx.spawn(async move |this, cx| {
+            loop {
+                cx.background_executor().timer(Duration::from_millis(100)).await;
+                
+                let should_continue = this.update(cx, |this, cx| {
+                    let Some(ref messages) = this.agent_stream else {
+                        return false;
+                    };
+                    if let Some(message) = messages.try_iter().last() {
+                        this.last_agent_message = Some(message);
+                        this.last_agent_results = vec![];
+                        this.prompt.update(cx, |prompt, cx| {
+                            prompt.set_streaming();
+                            cx.notify();
+                        });
+                        cx.notify();
+                    }
+                    true
+                }).unwrap_or(false);
+                
+                if !should_continue {
+                    break;
+                }
+            }
+        }).detach();
+    }
+    
+    /// Drop the pending agent request, which aborts it
+    fn cancel_agent(&mut self, cx: &mut Context<Self>) {
+        // The prompt's Cancel also stops auto-video generation
+        self.cancel_auto_video(cx);
+        if self.agent_task.take().is_some() {
+            self.agent_stream = None;
+            tracing::info!("Agent request cancelled");
+            self.last_agent_message = Some("Cancelled".to_string());
+            self.last_agent_results = vec![];
+            cx.notify();
+        }
+    }
+    
     fn process_with_agent(&mut self, text: String, has_attachments: bool, cx: &mut Context<Self>) {
-        // Set processing state
+        // A new command replaces whatever is still pending
+        self.agent_task = None;
+        self.agent_stream = None;
+        self.pending_edits = None;
+        
+        let (message_tx, message_rx) = std::sync::mpsc::channel::<String>();
+        // Started with the first request, then kept for the next ones
+        let runtime = match self.agent_runtime.take() {
+            Some(runtime) => Ok(runtime),
+            None => agent::AgentRuntime::new(),
+        };
+        let request = runtime.and_then(|runtime| {
+            let request = agent::AgentRequest::spawn(
+                &self.project,
+                &self.project.conversation,
+                &text,
+                has_attachments,
+                self.config.agent_backend(),
+                Box::new(move |message| {
+                    let _ = message_tx.send(message);
+                }),
+                &runtime,
+            );
+            self.agent_runtime = Some(runtime);
+            request
+        });
+        let request = match request {
+            Ok(request) => request,
+            Err(e) => {
+                tracing::error!("Agent error: {}", e);
+                self.last_agent_message = Some(format!("Error: {}", e));
+                self.last_agent_results = vec![];
+                cx.notify();
+                return;
+            }
+        };
+        
+        // Set processing state, animating only if not already running
+        let already_processing = self.prompt.read(cx).is_processing();
         self.prompt.update(cx, |prompt, cx| {
             prompt.set_processing(true);
             cx.notify();
         });
-        
-        // Start thinking animation
-        self.start_thinking_animation(cx);
+        self.agent_stream = Some(message_rx);
+        if !already_processing {
+            self.start_thinking_animation(cx);
+            self.start_stream_updates(cx);
+        }
         
         tracing::info!("Sending to agent: {}", text);
+        let undo_label = format!("agent edit \"{}\"", text);
+        let prompt_text = text.clone();
         
-        // Clone project for the blocking task
-        let project_clone = self.project.clone();
-        
-        cx.spawn(async move |this, cx| {
-            // Run blocking HTTP request in a separate thread
-            let result = std::thread::spawn(move || {
-                agent::process_command_blocking(&project_clone, &text, has_attachments)
-            }).join();
+        self.agent_task = Some(cx.spawn(async move |this, cx| {
+            let result = request.await;
             
             let _ = this.update(cx, |this, cx| {
+                this.agent_task = None;
+                this.agent_stream = None;
+                
                 // Clear processing state
                 this.prompt.update(cx, |prompt, cx| {
                     prompt.set_processing(false);
@@ -316,31 +923,166 @@ impl MainView {
                 });
                 
                 match result {
-                    Ok(Ok(response)) => {
+                    Ok(response) => {
                         tracing::info!("Agent response: {}", response.message);
                         tracing::info!("Agent modifications: {:?}", response.modifications);
                         
+                        // Risky edits wait for the user; the rest apply right away
+                        let (held, modifications): (Vec<_>, Vec<_>) = response
+                            .modifications
+                            .into_iter()
+                            .partition(|m| this.config.confirm_destructive() && m.is_destructive(&this.project));
+                        let pending = (!held.is_empty()).then(|| PendingEdits {
+                            summary: agent::describe_destructive(&this.project, &held),
+                            modifications: held,
+                            undo_label: undo_label.clone(),
+                        });
+                        
                         // Apply modifications to project
-                        let results = agent::apply_modifications(&mut this.project, &response.modifications);
+                        if !modifications.is_empty() {
+                            this.undo_stack.record(undo_label, &this.project);
+                            this.mark_dirty(cx);
+                        }
+                        let results = agent::apply_modifications(&mut this.project, &modifications);
                         
                         // Process special commands from results
                         let mut display_results = Vec::new();
                         for result in &results {
                             if let Some(key) = result.strip_prefix("🔑 PEXELS_KEY:") {
                                 this.config.set_pexels_api_key(key.to_string());
-                                this.service_status = startup::ServiceStatus::check(&this.config.pexels_api_key);
-                                display_results.push("✓ Pexels API key saved".to_string());
+                                this.check_pexels_key(cx);
+                                display_results.push("🔑 Pexels API key saved, checking it…".to_string());
+                            } else if let Some(model) = result.strip_prefix("🧠 SET_MODEL:") {
+                                this.config.set_agent_model(model.to_string());
+                                // Warns once the check finds the model missing
+                                this.check_services(cx);
+                                display_results.push(format!("✓ Now using model {}", model));
+                            } else if let Some(settings) = result.strip_prefix("✂ CUT_SILENCES:") {
+                                let (threshold_db, min_duration) = settings.split_once('|').unwrap_or((settings, ""));
+                                let settings = match (threshold_db.parse::<f64>(), min_duration.parse::<f64>()) {
+                                    (Err(_), Err(_)) => None,
+                                    (threshold_db, min_duration) => Some((
+                                        threshold_db.unwrap_or(audio::DEFAULT_SILENCE_DB),
+                                        min_duration.unwrap_or(audio::DEFAULT_MIN_SILENCE_SECONDS),
+                                    )),
+                                };
+                                display_results.push(this.cut_silences(settings, cx));
+                            } else if let Some(name) = result.strip_prefix("🎨 THEME:") {
+                                match config::ThemeName::parse(name) {
+                                    Some(theme) => {
+                                        this.config.set_theme(theme);
+                                        this.sync_theme(cx);
+                                        display_results.push(format!("🎨 Switched to the {} theme", theme.label()));
+                                    }
+                                    None => display_results.push(format!("⚠ Unknown theme '{}' (dark, high contrast or light)", name)),
+                                }
+                            } else if result == "📜 EXPORT_CREDITS" {
+                                display_results.push(this.export_credits());
+                            } else if let Some(format) = result.strip_prefix("💬 EXPORT_SUBTITLES:") {
+                                match transcription::SubtitleFormat::parse(format) {
+                                    Some(format) => {
+                                        display_results.push("💬 Exporting subtitles...".to_string());
+                                        this.export_subtitles(format, cx);
+                                    }
+                                    None => display_results.push(format!("⚠ Unknown subtitle format '{}'", format)),
+                                }
+                            } else if let Some(format) = result.strip_prefix("📤 EXPORT:") {
+                                let mut parts = format.split('|');
+                                let preset = parts.next().unwrap_or_default();
+                                let aspect = parts.next().unwrap_or_default();
+                                let subtitles = parts.next().unwrap_or_default();
+                                let transition = parts.next().unwrap_or_default();
+                                let encoder = parts.next().unwrap_or_default();
+                                let from = parts.next().unwrap_or_default();
+                                let to = parts.next().unwrap_or_default();
+                                let resolution = parts.next().unwrap_or_default();
+                                let quality = parts.next().unwrap_or_default();
+                                let file_format = parts.next().unwrap_or_default();
+                                if let Some(preset) = export::ExportPreset::parse(preset) {
+                                    this.export_preset = preset;
+                                    this.export_aspect_mode = preset.default_aspect_mode();
+                                } else if let Some(resolution) = export::Resolution::parse(preset) {
+                                    // "export in 4k" often lands here
+                                    this.export_resolution = resolution;
+                                }
+                                if let Some(mode) = export::AspectMode::parse(aspect) {
+                                    this.export_aspect_mode = mode;
+                                }
+                                if let Some(mode) = export::SubtitleMode::parse(subtitles) {
+                                    this.export_subtitles = mode;
+                                }
+                                if let Some(transition) = export::Transition::parse(transition) {
+                                    this.export_transition = transition;
+                                }
+                                if let Some(encoder) = export::VideoEncoder::parse(encoder) {
+                                    this.export_encoder = encoder;
+                                }
+                                if let Some(resolution) = export::Resolution::parse(resolution) {
+                                    this.export_resolution = resolution;
+                                }
+                                if let Some(quality) = export::Quality::parse(quality) {
+                                    this.export_quality = Some(quality);
+                                }
+                                if let Some(file_format) = export::OutputFormat::parse(file_format) {
+                                    this.export_format = file_format;
+                                }
+                                if !from.is_empty() || !to.is_empty() {
+                                    let range = match (export::parse_timestamp(from), export::parse_timestamp(to)) {
+                                        (Some(start), Some(end)) => Ok((start, end)),
+                                        (Some(start), None) if to.is_empty() => this.export_duration()
+                                            .map(|end| (start, end))
+                                            .ok_or_else(|| "Some clip lengths aren't known yet, so give an end time too".to_string()),
+                                        (None, Some(end)) if from.is_empty() => Ok((0.0, end)),
+                                        _ => Err(format!("Couldn't read \"{}\" to \"{}\" as times (use m:ss or seconds)", from, to)),
+                                    };
+                                    let result = range.and_then(|range| this.set_export_range(Some(range), cx));
+                                    if let Err(e) = result {
+                                        display_results.push(format!("❌ {}", e));
+                                        continue;
+                                    }
+                                    display_results.push(describe_export_range(this.export_range));
+                                }
+                                display_results.push(format!(
+                                    "📤 Exporting {} {} ({}{})",
+                                    this.export_format.label(),
+                                    this.export_preset.label(),
+                                    this.export_aspect_mode.label(),
+                                    this.export_size_label()
+                                ));
+                                this.start_export(cx);
+                            } else if let Some(path) = result.strip_prefix("🔊 EXPORT_AUDIO:") {
+                                display_results.push("🔊 Exporting audio...".to_string());
+                                let path = (!path.is_empty()).then(|| expand_home(path));
+                                this.start_audio_export(path, cx);
+                            } else if let Some(path) = result.strip_prefix("🎞 EXPORT_EDL:") {
+                                let path = (!path.is_empty()).then(|| expand_home(path));
+                                display_results.push(this.export_edl(path, cx));
+                            } else if let Some(path) = result.strip_prefix("🖼 SAVE_FRAME:") {
+                                let path = (!path.is_empty()).then(|| expand_home(path));
+                                this.save_frame(path, cx);
+                            } else if let Some(language) = result.strip_prefix("📝 TRANSCRIBE:") {
+                                display_results.push("📝 Transcribing...".to_string());
+                                this.transcribe_audio((!language.is_empty()).then(|| language.to_string()), cx);
+                            } else if result == "📝 SHOW_TRANSCRIPT" {
+                                match this.project.current_transcript() {
+                                    Some(transcript) => display_results.extend(transcription::to_lines(transcript)),
+                                    None => display_results.push("No transcript yet: export subtitles or generate from audio first".to_string()),
+                                }
                             } else if result.starts_with("🎬 GENERATE_FROM_AUDIO:") {
                                 // Queue auto-video generation
                                 display_results.push("🎬 Starting auto-video generation...".to_string());
-                                this.start_auto_video_generation(cx);
+                                this.start_auto_video_generation(None, cx);
+                            } else if let Some(query) = result.strip_prefix("🥁 BEAT_MONTAGE:") {
+                                display_results.push("🥁 Starting a beat-synced montage...".to_string());
+                                this.start_auto_video_generation(Some(query.to_string()), cx);
                             } else if let Some(info) = result.strip_prefix("🔍 SEARCH_PEXELS:") {
                                 let parts: Vec<&str> = info.split(':').collect();
                                 if parts.len() >= 2 {
                                     let query = parts[0];
                                     let count = parts[1].parse().unwrap_or(5);
+                                    let photos = parts.get(2) == Some(&"photos");
                                     display_results.push(format!("🔍 Searching Pexels for '{}'...", query));
-                                    this.search_pexels(query.to_string(), count, cx);
+                                    this.search_pexels(query.to_string(), count, photos, cx);
                                 }
                             } else {
                                 display_results.push(result.clone());
@@ -348,89 +1090,428 @@ impl MainView {
                             tracing::info!("{}", result);
                         }
                         
-                        // Store agent message for display
+                        // Store agent message for display, and for the next prompt's context
+                        this.project.push_conversation(prompt_text, response.message.clone());
+                        this.mark_dirty(cx);
                         this.last_agent_message = Some(response.message);
+                        if let Some(ref pending) = pending {
+                            display_results.push(format!("⏸ Waiting for your OK to {}", pending.summary));
+                        }
                         this.last_agent_results = display_results;
+                        this.pending_edits = pending;
                         
-                        // Sync clips panel
+                        // Sync clips panel and timeline markers
                         this.sync_clips_panel(cx);
+                        this.sync_markers(cx);
+                        this.probe_clip_durations(cx);
                     }
-                    Ok(Err(e)) => {
+                    Err(e) => {
                         tracing::error!("Agent error: {}", e);
                         this.last_agent_message = Some(format!("Error: {}", e));
                         this.last_agent_results = vec![];
                     }
-                    Err(_) => {
-                        tracing::error!("Agent thread panicked");
-                        this.last_agent_message = Some("Error: Agent crashed".to_string());
-                        this.last_agent_results = vec![];
-                    }
                 }
                 cx.notify();
             });
-        })
-        .detach();
+        }));
     }
     
-    fn save_project(&mut self, cx: &mut Context<Self>) {
-        if let Some(ref path) = self.project_path {
-            // Save to existing path
-            if let Err(e) = self.project.save(path) {
-                tracing::error!("Failed to save project: {}", e);
-                self.state = AppState::Error(format!("Failed to save: {}", e));
-                cx.notify();
-            }
-        } else {
-            // Prompt for save location
-            self.save_project_as(cx);
+    fn export(&mut self, _: &Export, _window: &mut Window, cx: &mut Context<Self>) {
+        if self.export_job.is_none() {
+            self.export_dialog_open = true;
+            cx.notify();
         }
     }
     
-    fn save_project_as(&mut self, cx: &mut Context<Self>) {
-        let suggested_name = format!(
-            "{}.{}",
-            self.project.metadata.name,
-            Project::EXTENSION
-        );
-        
-        // Use home directory as default save location
-        let home_dir = std::env::var("HOME")
-            .map(std::path::PathBuf::from)
-            .unwrap_or_else(|_| std::path::PathBuf::from("."));
-        
-        let future = cx.prompt_for_new_path(&home_dir, Some(&suggested_name));
-        
-        cx.spawn(async move |this, cx| {
-            if let Ok(Ok(Some(path))) = future.await {
-                let _ = this.update(cx, |this, cx| {
-                    this.project_path = Some(path.clone());
-                    if let Err(e) = this.project.save(&path) {
-                        tracing::error!("Failed to save project: {}", e);
-                        this.state = AppState::Error(format!("Failed to save: {}", e));
-                    } else {
-                        // Update config with saved project
-                        this.config.set_last_project(path);
-                    }
-                    cx.notify();
-                });
-            }
-        })
-        .detach();
+    fn open(&mut self, _: &Open, _window: &mut Window, cx: &mut Context<Self>) {
+        self.open_project(cx);
     }
     
-    fn open_project(&mut self, cx: &mut Context<Self>) {
-        let future = cx.prompt_for_paths(PathPromptOptions {
-            files: true,
-            directories: false,
-            multiple: false,
-            prompt: Some("Open Project".into()),
-        });
-        
-        cx.spawn(async move |this, cx| {
-            if let Ok(Ok(Some(paths))) = future.await
-                && let Some(path) = paths.into_iter().next()
-            {
-                let _ = this.update(cx, |this, cx| {
+    fn save(&mut self, _: &Save, _window: &mut Window, cx: &mut Context<Self>) {
+        self.save_project(cx);
+    }
+    
+    fn save_as(&mut self, _: &SaveAs, _window: &mut Window, cx: &mut Context<Self>) {
+        self.save_project_as(cx);
+    }
+    
+    /// Start the export range at the playhead, keeping the out point if it still comes after
+    fn set_in_point(&mut self, _: &SetInPoint, _window: &mut Window, cx: &mut Context<Self>) {
+        let AppState::Loaded { ref timeline } = self.state else {
+            return;
+        };
+        let (time, duration) = {
+            let timeline = timeline.read(cx);
+            (timeline.time(), timeline.duration())
+        };
+        let end = self.export_range
+            .map(|(_, end)| end)
+            .filter(|&end| end > time)
+            .unwrap_or_else(|| self.export_duration().unwrap_or(duration));
+        self.pick_export_range(Some((time, end)), cx);
+    }
+    
+    /// End the export range at the playhead
+    fn set_out_point(&mut self, _: &SetOutPoint, _window: &mut Window, cx: &mut Context<Self>) {
+        let AppState::Loaded { ref timeline } = self.state else {
+            return;
+        };
+        let time = timeline.read(cx).time();
+        let start = self.export_range.map_or(0.0, |(start, _)| start);
+        self.pick_export_range(Some((start, time)), cx);
+    }
+    
+    /// Cut the clip under the playhead in two
+    fn split_at_playhead(&mut self, _: &SplitAtPlayhead, _window: &mut Window, cx: &mut Context<Self>) {
+        let AppState::Loaded { ref timeline } = self.state else {
+            return;
+        };
+        let time = timeline.read(cx).time();
+        let Some(index) = self.project.clip_at(time) else {
+            self.last_agent_message = Some(format!("No clip at {:.1}s to split", time));
+            self.last_agent_results = vec![];
+            cx.notify();
+            return;
+        };
+        
+        let snapshot = self.project.clone();
+        match self.project.split_clip(index, time) {
+            Ok(_) => {
+                self.undo_stack.record("split clip", &snapshot);
+                self.mark_dirty(cx);
+                self.last_agent_message = Some(format!("✂ Split '{}' at {:.1}s", self.project.clips[index].description, time));
+                self.sync_clips_panel(cx);
+            }
+            Err(e) => self.last_agent_message = Some(format!("Can't split: {}", e)),
+        }
+        self.last_agent_results = vec![];
+        cx.notify();
+    }
+    
+    fn step_backward(&mut self, _: &StepBackward, _window: &mut Window, cx: &mut Context<Self>) {
+        self.step_preview(-1, false, cx);
+    }
+    
+    fn step_forward(&mut self, _: &StepForward, _window: &mut Window, cx: &mut Context<Self>) {
+        self.step_preview(1, false, cx);
+    }
+    
+    fn toggle_help(&mut self, _: &ToggleHelp, _window: &mut Window, cx: &mut Context<Self>) {
+        self.help_open = !self.help_open;
+        cx.notify();
+    }
+    
+    fn toggle_loop(&mut self, _: &ToggleLoop, _window: &mut Window, cx: &mut Context<Self>) {
+        self.toggle_preview_loop(cx);
+    }
+    
+    fn toggle_playback_action(&mut self, _: &TogglePlayback, _window: &mut Window, cx: &mut Context<Self>) {
+        self.toggle_playback(cx);
+    }
+    
+    fn undo(&mut self, _: &Undo, _window: &mut Window, cx: &mut Context<Self>) {
+        match self.undo_stack.undo(&self.project) {
+            Some((mut project, label)) => {
+                // Undo reverts edits, not what was said
+                project.conversation = std::mem::take(&mut self.project.conversation);
+                self.restore_project(project, cx);
+                self.last_agent_message = Some(format!("↶ Undid {}", label));
+            }
+            None => {
+                self.last_agent_message = Some("Nothing to undo".to_string());
+            }
+        }
+        self.last_agent_results = vec![];
+        cx.notify();
+    }
+    
+    fn redo(&mut self, _: &Redo, _window: &mut Window, cx: &mut Context<Self>) {
+        match self.undo_stack.redo(&self.project) {
+            Some((mut project, label)) => {
+                project.conversation = std::mem::take(&mut self.project.conversation);
+                self.restore_project(project, cx);
+                self.last_agent_message = Some(format!("↷ Redid {}", label));
+            }
+            None => {
+                self.last_agent_message = Some("Nothing to redo".to_string());
+            }
+        }
+        self.last_agent_results = vec![];
+        cx.notify();
+    }
+    
+    /// Swap in a project snapshot and bring the panels back in line with it
+    fn restore_project(&mut self, project: Project, cx: &mut Context<Self>) {
+        let old_audio = self.project.audio.as_ref().map(|a| a.path.clone());
+        self.project = project;
+        self.mark_dirty(cx);
+        
+        let new_audio = self.project.audio.as_ref().map(|a| a.path.clone());
+        if new_audio != old_audio {
+            match new_audio {
+                Some(path) if path.exists() => self.load_audio(path, cx),
+                _ => self.state = AppState::Empty,
+            }
+        }
+        
+        self.sync_clips_panel(cx);
+        self.sync_markers(cx);
+        self.sync_cuts(cx);
+    }
+    
+    fn save_project(&mut self, cx: &mut Context<Self>) {
+        if let Some(path) = self.project_path.clone() {
+            // Save to existing path
+            self.copy_media_beside(&path, cx);
+            match self.project.save(&path) {
+                Ok(()) => self.remove_autosaves(),
+                Err(e) => {
+                    tracing::error!("Failed to save project: {}", e);
+                    self.state = AppState::Error(format!("Failed to save: {}", e));
+                    cx.notify();
+                }
+            }
+        } else {
+            // Prompt for save location
+            self.save_project_as(cx);
+        }
+    }
+    
+    fn save_project_as(&mut self, cx: &mut Context<Self>) {
+        let suggested_name = format!(
+            "{}.{}",
+            self.project.metadata.name,
+            Project::EXTENSION
+        );
+        
+        // Use home directory as default save location
+        let home_dir = std::env::var("HOME")
+            .map(std::path::PathBuf::from)
+            .unwrap_or_else(|_| std::path::PathBuf::from("."));
+        
+        let future = cx.prompt_for_new_path(&home_dir, Some(&suggested_name));
+        
+        cx.spawn(async move |this, cx| {
+            if let Ok(Ok(Some(path))) = future.await {
+                let _ = this.update(cx, |this, cx| {
+                    // Unsaved edits of the old file are in the new one now
+                    this.remove_autosaves();
+                    this.project_path = Some(path.clone());
+                    this.copy_media_beside(&path, cx);
+                    if let Err(e) = this.project.save(&path) {
+                        tracing::error!("Failed to save project: {}", e);
+                        this.state = AppState::Error(format!("Failed to save: {}", e));
+                    } else {
+                        this.remove_autosaves();
+                        // Update config with saved project
+                        this.config.set_last_project(path);
+                    }
+                    cx.notify();
+                });
+            }
+        })
+        .detach();
+    }
+    
+    /// Add dropped files as clips, in the order given: into the gap
+    /// before `slot` when dropped on the clips panel, else at the end
+    fn add_dropped_files(&mut self, files: Vec<std::path::PathBuf>, slot: Option<usize>, cx: &mut Context<Self>) {
+        if files.is_empty() {
+            return;
+        }
+        
+        tracing::info!("Dropped {} file(s)", files.len());
+        let count = files.len();
+        let first_new = self.project.clips.len();
+        
+        for file in files {
+            let description = file
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "Dropped file".to_string());
+            
+            let clip = self.project.add_clip(description, file.clone());
+            let media_type = clip.media_type.clone();
+            self.mark_dirty(cx);
+            
+            match media_type {
+                project::MediaType::Audio => {
+                    self.load_audio(file, cx);
+                }
+                project::MediaType::Video => {
+                    self.load_video(file, cx);
+                }
+                project::MediaType::Image => {
+                    tracing::info!("Added still image: {:?}", file);
+                }
+            }
+        }
+        if let Some(slot) = slot {
+            let added: Vec<usize> = (first_new..first_new + count).collect();
+            self.project.move_clips(&added, slot);
+        }
+        
+        self.sync_clips_panel(cx);
+        self.probe_clip_durations(cx);
+        self.last_agent_message = Some(format!("Added {} file(s) via drag & drop", count));
+        self.last_agent_results = vec![];
+        cx.notify();
+    }
+    
+    /// Bring outside media next to the project before saving, when the
+    /// config asks for it; a failed copy keeps the original paths
+    fn copy_media_beside(&mut self, path: &std::path::Path, cx: &mut Context<Self>) {
+        if !self.config.copy_media_into_project {
+            return;
+        }
+        match self.project.copy_media_beside(path) {
+            Ok(0) => {}
+            Ok(copied) => {
+                self.last_agent_message = Some(format!("📁 Copied {} media file(s) into the project folder", copied));
+                self.sync_clips_panel(cx);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to copy media into the project folder: {}", e);
+                self.last_agent_message = Some(format!("⚠ Media left in place: {}", e));
+            }
+        }
+    }
+    
+    /// Note an edit and autosave once there's been a few seconds without another
+    fn mark_dirty(&mut self, cx: &mut Context<Self>) {
+        self.dirty = true;
+        self.autosave_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(AUTOSAVE_DELAY).await;
+            let _ = this.update(cx, |this, _cx| this.autosave());
+        }));
+    }
+    
+    /// Write unsaved edits beside the project file (or to the untitled
+    /// autosave folder), leaving the real save untouched
+    fn autosave(&mut self) {
+        self.autosave_task = None;
+        if !self.dirty {
+            return;
+        }
+        
+        let path = match &self.project_path {
+            Some(path) => project::autosave_path(path),
+            None => match self.untitled_autosave.clone().or_else(new_untitled_autosave_path) {
+                Some(path) => path,
+                None => {
+                    tracing::warn!("Nowhere to autosave an untitled project");
+                    return;
+                }
+            },
+        };
+        if self.project_path.is_none() {
+            self.untitled_autosave = Some(path.clone());
+        }
+        
+        // The header's "last saved" is about the real file, not the sidecar
+        let modified_at = self.project.metadata.modified_at.clone();
+        match self.project.save(&path) {
+            Ok(()) => {
+                self.dirty = false;
+                tracing::info!("Autosaved project to {:?}", path);
+            }
+            Err(e) => tracing::error!("Autosave failed: {}", e),
+        }
+        self.project.metadata.modified_at = modified_at;
+    }
+    
+    /// Drop autosaves made redundant by a real save
+    fn remove_autosaves(&mut self) {
+        self.autosave_task = None;
+        self.dirty = false;
+        let sidecar = self.project_path.as_deref().map(project::autosave_path);
+        for path in sidecar.into_iter().chain(self.untitled_autosave.take()) {
+            if path.exists()
+                && let Err(e) = std::fs::remove_file(&path)
+            {
+                tracing::warn!("Failed to remove autosave {:?}: {}", path, e);
+            }
+        }
+    }
+    
+    /// Offer to bring back edits an earlier session autosaved but never saved
+    fn find_recovery(&mut self) {
+        self.recovery = match &self.project_path {
+            Some(path) => project::recoverable_autosave(path),
+            None => project::newest_untitled_autosave(),
+        };
+        if self.recovery.is_some() {
+            self.last_agent_message = Some("Found unsaved changes from a previous session. Restore them?".to_string());
+            self.last_agent_results = vec![];
+        }
+    }
+    
+    fn restore_recovery(&mut self, cx: &mut Context<Self>) {
+        let Some(path) = self.recovery.take() else {
+            return;
+        };
+        match Project::load(&path) {
+            Ok(project) => {
+                if self.project_path.is_none() {
+                    // Keep autosaving over the file we restored from
+                    self.untitled_autosave = Some(path);
+                }
+                self.restore_project(project, cx);
+                self.last_agent_message = Some("Restored unsaved changes".to_string());
+            }
+            Err(e) => {
+                tracing::error!("Failed to restore autosave: {}", e);
+                self.last_agent_message = Some(format!("Couldn't restore: {}", e));
+            }
+        }
+        self.last_agent_results = vec![];
+        cx.notify();
+    }
+    
+    /// Apply the agent edits that were waiting for confirmation
+    fn apply_pending_edits(&mut self, cx: &mut Context<Self>) {
+        let Some(pending) = self.pending_edits.take() else {
+            return;
+        };
+        self.undo_stack.record(pending.undo_label, &self.project);
+        self.mark_dirty(cx);
+        self.last_agent_results = agent::apply_modifications(&mut self.project, &pending.modifications);
+        self.sync_clips_panel(cx);
+        self.sync_markers(cx);
+        cx.notify();
+    }
+    
+    fn discard_pending_edits(&mut self, cx: &mut Context<Self>) {
+        if let Some(pending) = self.pending_edits.take() {
+            self.last_agent_results = vec![format!("✗ Didn't {}", pending.summary)];
+            cx.notify();
+        }
+    }
+    
+    fn discard_recovery(&mut self, cx: &mut Context<Self>) {
+        if let Some(path) = self.recovery.take()
+            && let Err(e) = std::fs::remove_file(&path)
+        {
+            tracing::warn!("Failed to remove autosave {:?}: {}", path, e);
+        }
+        self.last_agent_message = Some("Discarded unsaved changes".to_string());
+        self.last_agent_results = vec![];
+        cx.notify();
+    }
+    
+    fn open_project(&mut self, cx: &mut Context<Self>) {
+        let future = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: Some("Open Project".into()),
+        });
+        
+        cx.spawn(async move |this, cx| {
+            if let Ok(Ok(Some(paths))) = future.await
+                && let Some(path) = paths.into_iter().next()
+            {
+                let _ = this.update(cx, |this, cx| {
                     this.load_project_from_path(path, cx);
                 });
             }
@@ -438,7 +1519,52 @@ impl MainView {
         .detach();
     }
     
+    /// The export dialog's current choices, in the form projects store them
+    fn export_preferences(&self) -> project::ExportPreferences {
+        project::ExportPreferences {
+            preset: Some(self.export_preset.label()),
+            format: Some(self.export_format.label().to_string()),
+            resolution: Some(self.export_resolution.label().to_string()),
+            quality: self.export_quality.map(|q| q.label().to_string()),
+            aspect_mode: Some(self.export_aspect_mode.label().to_string()),
+            subtitles: Some(self.export_subtitles.label().to_string()),
+            transition: Some(self.export_transition.label()),
+            encoder: Some(self.export_encoder.label().to_string()),
+            normalize_audio: Some(self.export_normalize_audio),
+            clip_audio_gain: Some(self.export_clip_audio_gain),
+            chapters: Some(self.export_chapters),
+        }
+    }
+    
+    /// Fill the export dialog from the project's last export, else the
+    /// configured defaults; unreadable values keep the built-in default
+    fn apply_export_preferences(&mut self) {
+        let preferences = self.project.export.clone()
+            .or_else(|| self.config.default_export_settings.clone())
+            .unwrap_or_default();
+        
+        self.export_preset = preferences.preset.as_deref().and_then(export::ExportPreset::parse)
+            .unwrap_or(export::ExportPreset::Landscape1080p);
+        self.export_aspect_mode = preferences.aspect_mode.as_deref().and_then(export::AspectMode::parse)
+            .unwrap_or_else(|| self.export_preset.default_aspect_mode());
+        self.export_format = preferences.format.as_deref().and_then(export::OutputFormat::parse).unwrap_or_default();
+        self.export_resolution = preferences.resolution.as_deref().and_then(export::Resolution::parse).unwrap_or_default();
+        self.export_quality = preferences.quality.as_deref().and_then(export::Quality::parse);
+        self.export_subtitles = preferences.subtitles.as_deref().and_then(export::SubtitleMode::parse).unwrap_or_default();
+        self.export_transition = preferences.transition.as_deref().and_then(export::Transition::parse).unwrap_or_default();
+        self.export_encoder = preferences.encoder.as_deref().and_then(export::VideoEncoder::parse)
+            .unwrap_or(export::VideoEncoder::Software);
+        self.export_normalize_audio = preferences.normalize_audio.unwrap_or(false);
+        self.export_clip_audio_gain = preferences.clip_audio_gain.unwrap_or(1.0).clamp(0.0, 1.0);
+        self.export_chapters = preferences.chapters.unwrap_or(false);
+        self.player.set_clip_audio_gain(self.export_clip_audio_gain);
+    }
+    
     fn start_export(&mut self, cx: &mut Context<Self>) {
+        if self.export_job.is_some() {
+            return;
+        }
+        
         // Check if we have clips to export
         let video_clips: Vec<_> = self.project.clips
             .iter()
@@ -452,65 +1578,392 @@ impl MainView {
             return;
         }
         
-        // Prompt for output location
-        let default_name = format!("{}.mp4", self.project.metadata.name);
-        let home_dir = std::env::var("HOME")
-            .map(std::path::PathBuf::from)
-            .unwrap_or_else(|_| std::path::PathBuf::from("."));
-        
-        let future = cx.prompt_for_new_path(&home_dir, Some(&default_name));
-        let project_clone = self.project.clone();
-        
-        self.last_agent_message = Some("Starting export...".to_string());
-        self.last_agent_results = vec![];
-        cx.notify();
-        
+        // Clips may have changed length since the range was set
+        if let Some(range) = self.export_range
+            && let Err(e) = export::check_range(range, self.export_duration())
+        {
+            self.last_agent_message = Some("❌ Export range no longer fits".to_string());
+            self.last_agent_results = vec![e];
+            cx.notify();
+            return;
+        }
+        
+        let frame = match self.export_preset {
+            export::ExportPreset::Custom { .. } => self.export_preset.dimensions(),
+            preset => {
+                let settings = export::ExportSettings::default().with_preset(preset).with_resolution(self.export_resolution);
+                (settings.width, settings.height)
+            }
+        };
+        if let Err(e) = export::check_frame_size(frame, self.export_format) {
+            self.last_agent_message = Some("❌ Can't export at that size".to_string());
+            self.last_agent_results = vec![e];
+            cx.notify();
+            return;
+        }
+        
+        // Next time this project opens, the dialog starts from these choices
+        let preferences = self.export_preferences();
+        if self.project.export.as_ref() != Some(&preferences) {
+            self.project.export = Some(preferences);
+            self.mark_dirty(cx);
+        }
+        
+        // Prompt for output location
+        let default_name = format!("{}.{}", self.project.metadata.name, self.export_format.extension());
+        let home_dir = std::env::var("HOME")
+            .map(std::path::PathBuf::from)
+            .unwrap_or_else(|_| std::path::PathBuf::from("."));
+        
+        let future = cx.prompt_for_new_path(&home_dir, Some(&default_name));
+        let project_clone = self.project.clone();
+        let preset = self.export_preset;
+        let format = self.export_format;
+        let resolution = self.export_resolution;
+        let quality = self.export_quality;
+        let aspect_mode = self.export_aspect_mode;
+        let subtitles = self.export_subtitles;
+        let transition = self.export_transition;
+        let encoder = self.export_encoder;
+        let normalize_audio = self.export_normalize_audio;
+        let export_chapters = self.export_chapters;
+        let range = self.export_range;
+        let still_duration = self.project.still_duration();
+        let whisper = self.config.transcribe_options();
+        // Without a voiceover there is nothing to duck under
+        let clip_audio_gain = if self.project.audio.is_some() { self.export_clip_audio_gain } else { 1.0 };
+        self.export_dialog_open = false;
+        
+        self.last_agent_message = Some("Starting export...".to_string());
+        // Export leaves unreadable files out rather than failing on them
+        self.last_agent_results = self.project
+            .broken_clips()
+            .map(|c| format!("⚠ Skipping \"{}\": {}", c.description, c.error.as_deref().unwrap_or_default()))
+            .collect();
+        cx.notify();
+        
+        cx.spawn(async move |this, cx| {
+            let Ok(Ok(Some(output_path))) = future.await else {
+                return;
+            };
+            
+            let _ = this.update(cx, |this, cx| {
+                this.run_export(output_path, move |output_path, on_progress, cancel| {
+                    let settings = export::ExportSettings {
+                        output_path,
+                        format,
+                        quality,
+                        aspect_mode,
+                        subtitles,
+                        transition,
+                        encoder,
+                        normalize_audio,
+                        clip_audio_gain,
+                        mute_clip_audio: clip_audio_gain == 0.0,
+                        export_chapters,
+                        range,
+                        still_duration,
+                        whisper,
+                        ..Default::default()
+                    }
+                    .with_preset(preset);
+                    let settings = match preset {
+                        export::ExportPreset::Custom { .. } => settings,
+                        _ => settings.with_resolution(resolution),
+                    };
+                    export::export_project(&project_clone, &settings, Some(on_progress), cancel)
+                        .map(|encoder| vec![format!("Encoder: {}", encoder.label())])
+                }, cx);
+            });
+        })
+        .detach();
+    }
+    
+    /// ", 4K, High quality" for whatever differs from the defaults
+    fn export_size_label(&self) -> String {
+        let mut label = String::new();
+        if self.export_resolution != export::Resolution::default()
+            && !matches!(self.export_preset, export::ExportPreset::Custom { .. })
+        {
+            label.push_str(&format!(", {}", self.export_resolution.label()));
+        }
+        if let Some(quality) = self.export_quality {
+            label.push_str(&format!(", {} quality", quality.label()));
+        }
+        label
+    }
+    
+    /// Export just the audio (voiceover + audio clips); asks where to save
+    /// when no path is given
+    fn start_audio_export(&mut self, output_path: Option<std::path::PathBuf>, cx: &mut Context<Self>) {
+        if self.export_job.is_some() {
+            return;
+        }
+        
+        if self.project.audio.is_none() && self.project.audio_clips().next().is_none() {
+            self.last_agent_message = Some("❌ No audio to export".to_string());
+            self.last_agent_results = vec!["Add an audio track or audio clips first".to_string()];
+            cx.notify();
+            return;
+        }
+        
+        let project_clone = self.project.clone();
+        let export_audio = move |output_path, on_progress, cancel| {
+            let settings = export::ExportSettings {
+                output_path,
+                ..Default::default()
+            };
+            export::export_audio(&project_clone, &settings, Some(on_progress), cancel).map(|()| vec![])
+        };
+        
+        if let Some(output_path) = output_path {
+            self.run_export(output_path, export_audio, cx);
+            return;
+        }
+        
+        let default_name = format!("{}.mp3", self.project.metadata.name);
+        let home_dir = std::env::var("HOME")
+            .map(std::path::PathBuf::from)
+            .unwrap_or_else(|_| std::path::PathBuf::from("."));
+        let future = cx.prompt_for_new_path(&home_dir, Some(&default_name));
+        
+        cx.spawn(async move |this, cx| {
+            let Ok(Ok(Some(output_path))) = future.await else {
+                return;
+            };
+            let _ = this.update(cx, |this, cx| this.run_export(output_path, export_audio, cx));
+        })
+        .detach();
+    }
+    
+    /// Run an export on its own thread, showing progress and allowing cancel.
+    /// `export_fn` returns extra lines to show under "Saved to".
+    fn run_export<F>(&mut self, output_path: std::path::PathBuf, export_fn: F, cx: &mut Context<Self>)
+    where
+        F: FnOnce(std::path::PathBuf, export::ProgressCallback, Arc<AtomicBool>) -> Result<Vec<String>, export::ExportError>
+            + Send
+            + 'static,
+    {
+        if self.export_job.is_some() {
+            return;
+        }
+        
+        let cancel = Arc::new(AtomicBool::new(false));
+        let cancel_for_export = cancel.clone();
+        let (progress_tx, progress_rx) = std::sync::mpsc::channel::<export::ExportProgress>();
+        
+        // Run export in a separate thread
+        let export_thread = std::thread::spawn(move || {
+            let on_progress: export::ProgressCallback = Box::new(move |progress| {
+                let _ = progress_tx.send(progress);
+            });
+            
+            export_fn(output_path.clone(), on_progress, cancel_for_export).map(|details| (output_path, details))
+        });
+        
+        self.export_job = Some(ExportJob {
+            cancel,
+            progress: export::ExportProgress::default(),
+            started_at: Instant::now(),
+        });
+        self.last_agent_message = Some("Exporting...".to_string());
+        self.last_agent_results = vec![];
+        cx.notify();
+        
+        cx.spawn(async move |this, cx| {
+            // Poll instead of joining so the UI keeps rendering
+            while !export_thread.is_finished() {
+                cx.background_executor().timer(Duration::from_millis(100)).await;
+                
+                let latest_progress = progress_rx.try_iter().last();
+                let _ = this.update(cx, |this, cx| {
+                    if let Some(job) = this.export_job.as_mut()
+                        && let Some(progress) = latest_progress
+                    {
+                        job.progress = progress;
+                        this.last_agent_results = vec![format!("Exporting… {:.0}%", progress.fraction * 100.0)];
+                    }
+                    // Elapsed time changes even when progress doesn't
+                    cx.notify();
+                });
+            }
+            
+            let export_result = export_thread.join();
+            
+            let _ = this.update(cx, |this, cx| {
+                this.export_job = None;
+                
+                match export_result {
+                    Ok(Ok((path, details))) => {
+                        tracing::info!("Export complete: {:?}", path);
+                        this.last_export = Some(path.clone());
+                        this.last_agent_message = Some("✅ Export complete!".to_string());
+                        this.last_agent_results = vec![format!("Saved to: {}", path.display())];
+                        this.last_agent_results.extend(details);
+                    }
+                    Ok(Err(export::ExportError::Cancelled)) => {
+                        tracing::info!("Export cancelled");
+                        this.last_agent_message = Some("⏹ Export cancelled".to_string());
+                        this.last_agent_results = vec!["Partial output removed".to_string()];
+                    }
+                    Ok(Err(e)) => {
+                        tracing::error!("Export failed: {}", e);
+                        this.last_agent_message = Some("❌ Export failed".to_string());
+                        this.last_agent_results = vec![format!("Error: {}", e)];
+                    }
+                    Err(e) => {
+                        let panic_msg = if let Some(s) = e.downcast_ref::<&str>() {
+                            s.to_string()
+                        } else if let Some(s) = e.downcast_ref::<String>() {
+                            s.clone()
+                        } else {
+                            "Unknown panic".to_string()
+                        };
+                        tracing::error!("Export crashed: {}", panic_msg);
+                        this.last_agent_message = Some("❌ Export crashed".to_string());
+                        this.last_agent_results = vec![format!("Panic: {}", panic_msg)];
+                    }
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+    
+    /// Ask the running export to stop
+    fn cancel_export(&mut self, cx: &mut Context<Self>) {
+        if let Some(ref job) = self.export_job {
+            job.cancel.store(true, Ordering::Relaxed);
+            self.last_agent_message = Some("Cancelling export...".to_string());
+            cx.notify();
+        }
+    }
+    
+    /// Write credits.txt for the downloaded clips next to the last export,
+    /// or next to the project when nothing was exported yet
+    fn export_credits(&self) -> String {
+        let Some(credits) = self.project.credits() else {
+            return "No downloaded clips to credit".to_string();
+        };
+        let Some(dir) = self.last_export.as_ref().or(self.project_path.as_ref()).and_then(|p| p.parent()) else {
+            return "❌ Export or save the project first; credits.txt goes next to it".to_string();
+        };
+        let path = dir.join("credits.txt");
+        match std::fs::write(&path, credits) {
+            Ok(()) => format!("📜 Credits saved to {}", path.display()),
+            Err(e) => format!("❌ Couldn't write {}: {}", path.display(), e),
+        }
+    }
+    
+    /// Write subtitles next to the project file, transcribing the audio first
+    /// if there is no cached transcript
+    fn export_subtitles(&mut self, format: transcription::SubtitleFormat, cx: &mut Context<Self>) {
+        let Some(output_path) = self.project_path.as_ref().map(|p| p.with_extension(format.extension())) else {
+            self.last_agent_message = Some("❌ Save the project first".to_string());
+            self.last_agent_results = vec!["Subtitles are written next to the project file".to_string()];
+            cx.notify();
+            return;
+        };
+        
+        let cached = self.project.current_transcript().cloned();
+        let audio_path = self.project.audio_source().map(|p| p.to_path_buf());
+        if cached.is_none() && audio_path.is_none() {
+            self.last_agent_message = Some("❌ No audio to transcribe".to_string());
+            self.last_agent_results = vec!["Add an audio file first, then try again".to_string()];
+            cx.notify();
+            return;
+        }
+        
+        let saved_to = output_path.display().to_string();
+        let whisper = self.config.transcribe_options();
+        // The audio path comes back only when a fresh transcript was made from it
+        let task = cx.background_executor().spawn(async move {
+            let (transcript, fresh) = match (cached, audio_path) {
+                (Some(transcript), _) => (transcript, None),
+                (None, Some(audio_path)) => (transcription::transcribe(&audio_path, &whisper)?, Some(audio_path)),
+                (None, None) => anyhow::bail!("No audio to transcribe"),
+            };
+            std::fs::write(&output_path, format.render(&transcript))?;
+            anyhow::Ok((transcript, fresh))
+        });
+        cx.spawn(async move |this, cx| {
+            let result = task.await;
+            
+            let _ = this.update(cx, |this, cx| {
+                match result {
+                    Ok((transcript, fresh)) => {
+                        let count = transcript.segments.len();
+                        if let Some(audio_path) = fresh {
+                            this.project.set_transcript(transcript, &audio_path);
+                            this.mark_dirty(cx);
+                        }
+                        this.last_agent_message = Some("✅ Subtitles exported".to_string());
+                        this.last_agent_results = vec![
+                            format!("{} cues", count),
+                            format!("Saved to {}", saved_to),
+                        ];
+                    }
+                    Err(e) => {
+                        tracing::error!("Subtitle export failed: {}", e);
+                        this.last_agent_message = Some("❌ Subtitle export failed".to_string());
+                        this.last_agent_results = vec![format!("Error: {}", e)];
+                    }
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+    
+    /// Transcribe the project audio afresh, in `language` when given (for
+    /// this run only), replacing the cached transcript
+    fn transcribe_audio(&mut self, language: Option<String>, cx: &mut Context<Self>) {
+        let Some(audio_path) = self.project.audio_source().map(|p| p.to_path_buf()) else {
+            self.last_agent_results.push("❌ No audio to transcribe".to_string());
+            cx.notify();
+            return;
+        };
+        let mut whisper = self.config.transcribe_options();
+        if language.is_some() {
+            whisper.language = language;
+        }
+        
+        let task_audio = audio_path.clone();
+        let task = cx.background_executor().spawn(async move {
+            transcription::transcribe(&task_audio, &whisper)
+        });
         cx.spawn(async move |this, cx| {
-            if let Ok(Ok(Some(output_path))) = future.await {
-                // Run export in a separate thread
-                let export_result = std::thread::spawn(move || {
-                    let settings = export::ExportSettings {
-                        output_path: output_path.clone(),
-                        ..Default::default()
-                    };
-                    
-                    export::export_project(&project_clone, &settings, None)
-                        .map(|_| output_path)
-                }).join();
-                
-                let _ = this.update(cx, |this, cx| {
-                    match export_result {
-                        Ok(Ok(path)) => {
-                            tracing::info!("Export complete: {:?}", path);
-                            this.last_agent_message = Some("✅ Export complete!".to_string());
-                            this.last_agent_results = vec![format!("Saved to: {}", path.display())];
-                        }
-                        Ok(Err(e)) => {
-                            tracing::error!("Export failed: {}", e);
-                            this.last_agent_message = Some("❌ Export failed".to_string());
-                            this.last_agent_results = vec![format!("Error: {}", e)];
-                        }
-                        Err(e) => {
-                            let panic_msg = if let Some(s) = e.downcast_ref::<&str>() {
-                                s.to_string()
-                            } else if let Some(s) = e.downcast_ref::<String>() {
-                                s.clone()
-                            } else {
-                                "Unknown panic".to_string()
-                            };
-                            tracing::error!("Export crashed: {}", panic_msg);
-                            this.last_agent_message = Some("❌ Export crashed".to_string());
-                            this.last_agent_results = vec![format!("Panic: {}", panic_msg)];
-                        }
+            let result = task.await;
+            let _ = this.update(cx, |this, cx| {
+                match result {
+                    Ok(transcript) => {
+                        this.last_agent_message = Some("📝 Transcribed".to_string());
+                        this.last_agent_results = transcription::to_lines(&transcript);
+                        this.project.set_transcript(transcript, &audio_path);
+                        this.mark_dirty(cx);
                     }
-                    cx.notify();
-                });
-            }
+                    Err(e) => {
+                        tracing::error!("Transcription failed: {}", e);
+                        this.last_agent_message = Some("❌ Transcription failed".to_string());
+                        this.last_agent_results = vec![format!("Error: {}", e)];
+                    }
+                }
+                cx.notify();
+            });
         })
         .detach();
     }
     
-    fn start_auto_video_generation(&mut self, cx: &mut Context<Self>) {
+    /// Fill the timeline with stock footage for the first audio clip: one
+    /// clip per transcript segment, or cut on the beat to `beat_query`
+    /// footage for a music montage
+    fn start_auto_video_generation(&mut self, beat_query: Option<String>, cx: &mut Context<Self>) {
+        if self.auto_video_job.is_some() {
+            self.last_agent_results.push("⚠ Auto-video generation is already running".to_string());
+            cx.notify();
+            return;
+        }
+        
         // Find the first audio clip
         let audio_clip = self.project.clips
             .iter()
@@ -532,62 +1985,139 @@ impl MainView {
         };
         
         let audio_path = audio_clip.path.clone();
-        let output_dir = std::env::temp_dir().join("montage_auto_video");
+        let source = audio_path.clone();
+        let cached = self.project.cached_transcript(&audio_path).cloned();
+        let reused = cached.is_some();
+        let ollama = self.config.ollama_endpoint();
+        let whisper = self.config.transcribe_options();
+        let parallel_downloads = self.config.parallel_downloads();
+        let keyword_batch = self.config.keyword_batch_size();
+        // Match stock footage to the frame it will be exported into
+        let orientation = self.export_preset.orientation();
+        let output_dir = self.config.media_dir();
+        
+        let cancel = Arc::new(AtomicBool::new(false));
+        let cancel_for_worker = cancel.clone();
+        let (progress_tx, progress_rx) = std::sync::mpsc::channel::<auto_video::AutoVideoProgress>();
+        
+        // Search and download in one thread, reporting each stage as it goes
+        let worker = std::thread::spawn(move || {
+            let on_progress: auto_video::ProgressCallback = Box::new(move |progress| {
+                let _ = progress_tx.send(progress);
+            });
+            let cancel = &*cancel_for_worker;
+            let mut result = match beat_query {
+                Some(query) => auto_video::generate_beat_synced(&audio_path, &query, &api_key, orientation, &on_progress, cancel),
+                None => auto_video::generate_from_audio(
+                    &audio_path, cached, &whisper, &ollama, keyword_batch, &api_key, orientation, &output_dir, &on_progress, cancel,
+                ),
+            }?;
+            auto_video::download_clips(&mut result, &output_dir, parallel_downloads, &on_progress, cancel)?;
+            anyhow::Ok(result)
+        });
         
+        self.auto_video_job = Some(AutoVideoJob { cancel, stages: Vec::new() });
         self.last_agent_message = Some("🎬 Generating video from audio...".to_string());
-        self.last_agent_results = vec![
-            "Step 1: Transcribing audio...".to_string(),
-        ];
+        self.last_agent_results = vec![];
+        self.prompt.update(cx, |prompt, cx| {
+            prompt.set_processing(true);
+            cx.notify();
+        });
         cx.notify();
         
         cx.spawn(async move |this, cx| {
-            let result = std::thread::spawn(move || {
-                auto_video::generate_from_audio(&audio_path, &api_key, &output_dir)
-            }).join();
+            // Poll instead of joining so the progress list keeps updating
+            while !worker.is_finished() {
+                cx.background_executor().timer(Duration::from_millis(100)).await;
+                
+                let updates: Vec<_> = progress_rx.try_iter().collect();
+                if updates.is_empty() {
+                    continue;
+                }
+                let _ = this.update(cx, |this, cx| {
+                    if let Some(job) = this.auto_video_job.as_mut() {
+                        for progress in updates {
+                            job.record(progress);
+                        }
+                    }
+                    cx.notify();
+                });
+            }
+            
+            let result = worker.join();
             
             let _ = this.update(cx, |this, cx| {
+                // Whatever happened, the prompt is usable again
+                this.auto_video_job = None;
+                this.prompt.update(cx, |prompt, cx| {
+                    prompt.set_processing(false);
+                    cx.notify();
+                });
+                
                 match result {
-                    Ok(Ok(mut auto_result)) => {
-                        // Download the clips
-                        let api_key = this.config.pexels_api_key.clone().unwrap_or_default();
-                        let output_dir = std::env::temp_dir().join("montage_auto_video");
-                        
-                        let download_result = std::thread::spawn(move || {
-                            auto_video::download_clips(&mut auto_result, &output_dir, &api_key)
-                                .map(|_| auto_result)
-                        }).join();
-                        
-                        match download_result {
-                            Ok(Ok(auto_result)) => {
-                                // Add downloaded clips to project
-                                let mut added = 0;
-                                for clip in &auto_result.clips {
-                                    if let Some(ref path) = clip.local_path {
-                                        this.project.add_clip(
-                                            format!("{} ({})", clip.query, clip.segment.text.chars().take(30).collect::<String>()),
-                                            path.clone(),
-                                        );
-                                        added += 1;
-                                    }
+                    Ok(Ok(auto_result)) => {
+                        // Add downloaded clips to project
+                        let mut added = 0;
+                        let mut stills = 0;
+                        for clip in &auto_result.clips {
+                            if let Some(ref path) = clip.local_path {
+                                let description = if clip.segment.text.is_empty() {
+                                    format!("{} ({}, {:.1}s)", clip.query, export::format_timestamp(clip.start), clip.duration)
+                                } else {
+                                    format!(
+                                        "{} ({}, {:.1}s)",
+                                        clip.query,
+                                        clip.segment.text.chars().take(30).collect::<String>(),
+                                        clip.duration
+                                    )
+                                };
+                                let id = this.project.add_clip(description, path.clone()).id.clone();
+                                if let Some(ref source) = clip.source {
+                                    this.project.set_clip_source(&id, source.clone());
+                                }
+                                added += 1;
+                                if clip.video.is_none() {
+                                    stills += 1;
                                 }
                                 
-                                this.sync_clips_panel(cx);
-                                this.last_agent_message = Some("✅ Auto-video generation complete!".to_string());
-                                this.last_agent_results = vec![
-                                    format!("Transcribed: {} segments", auto_result.transcript.segments.len()),
-                                    format!("Added: {} video clips", added),
-                                    format!("Duration: {:.1}s", auto_result.transcript.duration),
-                                ];
+                                // Each clip covers exactly its stretch of the voiceover
+                                if let Some(added_clip) = this.project.clips.last_mut() {
+                                    added_clip.source_in = 0.0;
+                                    added_clip.duration = Some(clip.duration);
+                                }
                             }
-                            Ok(Err(e)) => {
-                                this.last_agent_message = Some("❌ Failed to download clips".to_string());
-                                this.last_agent_results = vec![format!("Error: {}", e)];
+                        }
+                        this.project.update_start_times();
+                        
+                        let timing = match auto_result.timing {
+                            auto_video::CutTiming::Transcript(ref transcript) => {
+                                this.project.set_transcript(transcript.clone(), &source);
+                                format!(
+                                    "{}: {} segments",
+                                    if reused { "Reused transcript" } else { "Transcribed" },
+                                    transcript.segments.len()
+                                )
                             }
-                            Err(_) => {
-                                this.last_agent_message = Some("❌ Download crashed".to_string());
-                                this.last_agent_results = vec![];
+                            auto_video::CutTiming::Beats(cuts) => format!("Cut on the beat: {} cuts", cuts),
+                            auto_video::CutTiming::FixedInterval(seconds) => {
+                                format!("No clear beat, so a cut every {}s", seconds)
                             }
-                        }
+                        };
+                        this.mark_dirty(cx);
+                        this.sync_clips_panel(cx);
+                        this.probe_clip_durations(cx);
+                        this.last_agent_message = Some("✅ Auto-video generation complete!".to_string());
+                        this.last_agent_results = vec![
+                            timing,
+                            format!("Added: {} clips ({} stills)", added, stills),
+                            format!("Duration: {:.1}s", auto_result.duration),
+                        ];
+                        this.last_agent_results.extend(auto_result.warnings.iter().cloned());
+                    }
+                    Ok(Err(e)) if e.is::<auto_video::Cancelled>() => {
+                        tracing::info!("Auto-video generation cancelled");
+                        this.last_agent_message = Some("⏹ Auto-video generation cancelled".to_string());
+                        this.last_agent_results = vec!["Partial downloads removed".to_string()];
                     }
                     Ok(Err(e)) => {
                         this.last_agent_message = Some("❌ Auto-video generation failed".to_string());
@@ -604,7 +2134,16 @@ impl MainView {
         .detach();
     }
     
-    fn search_pexels(&mut self, query: String, count: u32, cx: &mut Context<Self>) {
+    /// Ask the running auto-video generation to stop
+    fn cancel_auto_video(&mut self, cx: &mut Context<Self>) {
+        if let Some(ref job) = self.auto_video_job {
+            job.cancel.store(true, Ordering::Relaxed);
+            self.last_agent_message = Some("Cancelling auto-video generation...".to_string());
+            cx.notify();
+        }
+    }
+    
+    fn search_pexels(&mut self, query: String, count: u32, photos: bool, cx: &mut Context<Self>) {
         let Some(api_key) = self.config.pexels_api_key.clone() else {
             self.last_agent_message = Some("❌ Pexels API key not set".to_string());
             self.last_agent_results = vec!["Say: 'set pexels key YOUR_API_KEY'".to_string()];
@@ -612,39 +2151,63 @@ impl MainView {
             return;
         };
         
-        self.last_agent_message = Some(format!("🔍 Searching Pexels for '{}'...", query));
+        let kind = if photos { "photos" } else { "videos" };
+        let orientation = self.export_preset.orientation();
+        let output_dir = self.config.media_dir();
+        self.last_agent_message = Some(format!("🔍 Searching Pexels {} for '{}'...", kind, query));
         self.last_agent_results = vec![];
         cx.notify();
         
         let query_clone = query.clone();
         cx.spawn(async move |this, cx| {
             let query_for_search = query_clone.clone();
-            let result = std::thread::spawn(move || {
-                pexels::search_videos(&api_key, &query_for_search, count)
+            // Search and download the first result off the UI thread
+            let result = std::thread::spawn(move || -> anyhow::Result<(Vec<String>, Option<(std::path::PathBuf, project::ClipSource)>)> {
+                std::fs::create_dir_all(&output_dir)?;
+                
+                if photos {
+                    let photos = pexels::PexelsClient::new(&api_key).search_photos(&query_for_search, count, orientation, &AtomicBool::new(false))?;
+                    let lines = photos.iter()
+                        .take(5)
+                        .map(|p| format!("• {}x{} - {} (by {})", p.width, p.height, p.url, p.photographer))
+                        .collect();
+                    let downloaded = photos.first().and_then(|photo| {
+                        let output_path = output_dir.join(format!("{}.jpg", photo.id));
+                        pexels::download_photo(photo, &output_path, &AtomicBool::new(false)).ok().map(|_| (output_path, photo.source()))
+                    });
+                    Ok((lines, downloaded))
+                } else {
+                    let videos = pexels::PexelsClient::new(&api_key).search_videos(&query_for_search, count, orientation, &AtomicBool::new(false))?;
+                    let lines = videos.iter()
+                        .take(5)
+                        .map(|v| format!("• {}s - {} (by {})", v.duration, v.url, v.user))
+                        .collect();
+                    let downloaded = videos.first().and_then(|video| {
+                        pexels::download_video(video, &output_dir, &AtomicBool::new(false)).ok().map(|path| (path, video.source()))
+                    });
+                    Ok((lines, downloaded))
+                }
             }).join();
             
             let _ = this.update(cx, |this, cx| {
                 match result {
-                    Ok(Ok(videos)) => {
-                        if videos.is_empty() {
-                            this.last_agent_message = Some(format!("No videos found for '{}'", query_clone));
+                    Ok(Ok((lines, downloaded))) => {
+                        if lines.is_empty() {
+                            this.last_agent_message = Some(format!("No {} found for '{}'", kind, query_clone));
                             this.last_agent_results = vec![];
                         } else {
-                            this.last_agent_message = Some(format!("Found {} videos for '{}'", videos.len(), query_clone));
-                            this.last_agent_results = videos.iter()
-                                .take(5)
-                                .map(|v| format!("• {}s - {} (by {})", v.duration, v.url, v.user))
-                                .collect();
+                            this.last_agent_message = Some(format!("Found {} {} for '{}'", lines.len(), kind, query_clone));
+                            this.last_agent_results = lines;
                             
-                            // Download and add the first video
-                            if let Some(video) = videos.first() {
-                                let output_dir = std::env::temp_dir().join("montage_pexels");
-                                let _ = std::fs::create_dir_all(&output_dir);
-                                let output_path = output_dir.join(format!("{}.mp4", video.id));
-                                
-                                if pexels::download_video(video, &output_path).is_ok() {
-                                    this.project.add_clip(query_clone.clone(), output_path.clone());
+                            if let Some((output_path, source)) = downloaded {
+                                if this.project.clips.iter().any(|c| c.path == output_path) {
+                                    this.last_agent_results.push("First result is already in the project".to_string());
+                                } else {
+                                    let id = this.project.add_clip(query_clone.clone(), output_path).id.clone();
+                                    this.project.set_clip_source(&id, source);
+                                    this.mark_dirty(cx);
                                     this.sync_clips_panel(cx);
+                                    this.probe_clip_durations(cx);
                                     this.last_agent_results.push("✓ Added first result to project".to_string());
                                 }
                             }
@@ -670,6 +2233,7 @@ impl MainView {
         cx.notify();
 
         let path_for_project = path.clone();
+        let path_for_timeline = path.clone();
         let path_clone = path.clone();
         cx.spawn(async move |this, cx| {
             let result = std::thread::spawn(move || AudioData::load(&path_clone)).join();
@@ -677,27 +2241,73 @@ impl MainView {
             let _ = this.update(cx, |this, cx| {
                 match result {
                     Ok(Ok(audio)) => {
-                        // Update project with audio info
+                        // Update project with audio info (reloading the same file isn't an edit)
+                        if this.project.audio.as_ref().map(|a| &a.path) != Some(&path_for_project) {
+                            this.mark_dirty(cx);
+                        }
                         this.project.set_audio(
                             path_for_project,
                             audio.duration,
                             audio.sample_rate,
                         );
                         
-                        let timeline = cx.new(|cx| Timeline::new(audio, cx));
+                        let audio_duration = audio.duration;
+                        let zoom = this.project.timeline.zoom;
+                        let timeline = cx.new(|cx| Timeline::new(audio, &path_for_timeline, zoom, cx));
                         
                         // Subscribe to timeline position changes to sync video
-                        cx.subscribe(&timeline, |this, _timeline, event: &TimelineEvent, _cx| {
+                        cx.subscribe(&timeline, move |this, _timeline, event: &TimelineEvent, cx| {
                             match event {
+                                TimelineEvent::PlaybackStarted => {
+                                    // Only one thing should be making sound
+                                    if this.player.state() == PlayerState::Playing {
+                                        this.player.pause();
+                                        cx.notify();
+                                    }
+                                }
                                 TimelineEvent::PositionChanged(position) => {
-                                    this.project.timeline.position = *position;
+                                    this.project.timeline.position = *position * audio_duration;
                                     this.player.seek(*position);
+                                    
+                                    // Paused, the sought frame prerolls a moment later; show it while scrubbing
+                                    if this.player.state() != PlayerState::Playing {
+                                        cx.spawn(async move |this, cx| {
+                                            cx.background_executor().timer(Duration::from_millis(30)).await;
+                                            let _ = this.update(cx, |_, cx| cx.notify());
+                                        })
+                                        .detach();
+                                    }
+                                }
+                                TimelineEvent::PlayheadMoved(seconds) => {
+                                    this.project.timeline.position = *seconds;
+                                }
+                                TimelineEvent::ClipSelected(id) => {
+                                    this.clips_panel.update(cx, |panel, cx| {
+                                        panel.set_selected(Some(id.clone()));
+                                        cx.notify();
+                                    });
+                                    this.preview_still(id, cx);
+                                }
+                                TimelineEvent::ZoomChanged(zoom) => {
+                                    this.project.timeline.zoom = *zoom;
+                                    this.mark_dirty(cx);
+                                }
+                                TimelineEvent::CutSilences => {
+                                    this.last_agent_message = Some(this.cut_silences(None, cx));
+                                    this.last_agent_results = vec![];
+                                    cx.notify();
                                 }
                             }
                         })
                         .detach();
                         
                         this.state = AppState::Loaded { timeline };
+                        this.sync_timeline_clips(cx);
+                        this.sync_markers(cx);
+                        this.sync_export_range(cx);
+                        this.sync_cuts(cx);
+                        this.sync_theme(cx);
+                        this.restore_playhead(cx);
                     }
                     Ok(Err(e)) => {
                         this.state = AppState::Error(format!("Failed to load audio: {}", e));
@@ -712,14 +2322,287 @@ impl MainView {
         .detach();
     }
 
+    /// Move the playhead and the player to where a video clip starts
+    fn seek_to_clip(&mut self, id: &str, cx: &mut Context<Self>) {
+        let Some(start) = self.project.clips
+            .iter()
+            .find(|c| c.id == id && c.media_type == project::MediaType::Video)
+            .map(|c| c.start_time)
+        else {
+            return;
+        };
+        
+        self.project.timeline.position = start;
+        let duration = self.player.duration();
+        if duration > 0.0 {
+            self.player.seek(start / duration);
+        }
+        if let AppState::Loaded { ref timeline } = self.state {
+            timeline.update(cx, |timeline, cx| timeline.set_time(start, cx));
+        }
+        cx.notify();
+    }
+    
+    /// Show an image clip in the preview; selecting anything else clears it
+    fn preview_still(&mut self, id: &str, cx: &mut Context<Self>) {
+        self.still_preview = None;
+        self.still_frame = None;
+        cx.notify();
+        
+        let Some(path) = self.project.clips
+            .iter()
+            .find(|c| c.id == id && c.media_type == project::MediaType::Image)
+            .map(|c| c.path.clone())
+        else {
+            return;
+        };
+        
+        cx.spawn(async move |this, cx| {
+            let result = std::thread::spawn(move || video::VideoFrame::from_image(&path)).join();
+            
+            let _ = this.update(cx, |this, cx| {
+                match result {
+                    Ok(Ok(frame)) => {
+                        this.still_preview = frame.to_render_image();
+                        this.still_frame = Some(frame);
+                    }
+                    Ok(Err(e)) => tracing::warn!("Couldn't preview image: {}", e),
+                    Err(_) => tracing::warn!("Image decoding panicked"),
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+    
+    /// The picture in the preview right now: the selected still, or the player's frame
+    fn preview_frame(&self) -> Option<video::VideoFrame> {
+        if let Some(ref frame) = self.still_frame
+            && self.player.state() != PlayerState::Playing
+        {
+            return Some(frame.clone());
+        }
+        self.player.current_frame().map(|frame| video::VideoFrame {
+            data: frame.data,
+            height: frame.height,
+            width: frame.width,
+        })
+    }
+    
+    /// Write the cut as an EDL, asking where when no path is given; returns
+    /// what happened, or a note that the file dialog is open
+    fn export_edl(&mut self, output_path: Option<std::path::PathBuf>, cx: &mut Context<Self>) -> String {
+        if let Some(output_path) = output_path {
+            return match export::export_edl(&self.project, &output_path) {
+                Ok(report) if report.skipped.is_empty() => {
+                    format!("🎞 EDL with {} events saved to {}", report.events, output_path.display())
+                }
+                Ok(report) => format!(
+                    "🎞 EDL with {} events saved to {}; left out {} clip(s) whose length isn't known yet: {}",
+                    report.events,
+                    output_path.display(),
+                    report.skipped.len(),
+                    report.skipped.join(", ")
+                ),
+                Err(e) => format!("❌ Couldn't export the EDL: {:#}", e),
+            };
+        }
+        
+        let default_name = format!("{}.edl", self.project.metadata.name);
+        let home_dir = std::env::var("HOME")
+            .map(std::path::PathBuf::from)
+            .unwrap_or_else(|_| std::path::PathBuf::from("."));
+        let future = cx.prompt_for_new_path(&home_dir, Some(&default_name));
+        cx.spawn(async move |this, cx| {
+            let Ok(Ok(Some(output_path))) = future.await else {
+                return;
+            };
+            let _ = this.update(cx, |this, cx| {
+                this.last_agent_results = vec![this.export_edl(Some(output_path), cx)];
+                cx.notify();
+            });
+        })
+        .detach();
+        "🎞 Choose where to save the EDL".to_string()
+    }
+    
+    /// Save the preview as a PNG, asking where when no path is given
+    fn save_frame(&mut self, output_path: Option<std::path::PathBuf>, cx: &mut Context<Self>) {
+        let Some(frame) = self.preview_frame() else {
+            self.last_agent_message = Some("❌ No frame to save yet".to_string());
+            self.last_agent_results = vec!["Play or step through the video first".to_string()];
+            cx.notify();
+            return;
+        };
+        
+        let Some(output_path) = output_path else {
+            let seconds = (self.player.get_position() * self.player.duration()) as u64;
+            let default_name = format!("{} {}-{:02}.png", self.project.metadata.name, seconds / 60, seconds % 60);
+            let home_dir = std::env::var("HOME")
+                .map(std::path::PathBuf::from)
+                .unwrap_or_else(|_| std::path::PathBuf::from("."));
+            let future = cx.prompt_for_new_path(&home_dir, Some(&default_name));
+            cx.spawn(async move |this, cx| {
+                let Ok(Ok(Some(output_path))) = future.await else {
+                    return;
+                };
+                let _ = this.update(cx, |this, cx| this.save_frame(Some(output_path), cx));
+            })
+            .detach();
+            return;
+        };
+        
+        let task = cx.background_executor().spawn({
+            let output_path = output_path.clone();
+            async move { frame.save_png(&output_path) }
+        });
+        cx.spawn(async move |this, cx| {
+            let result = task.await;
+            let _ = this.update(cx, |this, cx| {
+                match result {
+                    Ok(()) => {
+                        this.last_agent_message = Some("🖼 Frame saved".to_string());
+                        this.last_agent_results = vec![format!("Saved to {}", output_path.display())];
+                    }
+                    Err(e) => {
+                        this.last_agent_message = Some("❌ Couldn't save the frame".to_string());
+                        this.last_agent_results = vec![format!("{:#}", e)];
+                    }
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+    
+    /// Arrow keys in the preview: ±1 frame, or ±1 second with Shift
+    fn handle_preview_key(&mut self, event: &KeyDownEvent, cx: &mut Context<Self>) {
+        let direction = match event.keystroke.key.as_str() {
+            "left" => -1,
+            "right" => 1,
+            _ => return,
+        };
+        if self.step_preview(direction, event.keystroke.modifiers.shift, cx) {
+            cx.stop_propagation();
+        }
+    }
+    
+    /// Pause and move the preview by whole frames, or by seconds when
+    /// `by_second` is set; false if there's nothing to step through
+    fn step_preview(&mut self, direction: i32, by_second: bool, cx: &mut Context<Self>) -> bool {
+        if !self.player.is_loaded() {
+            return false;
+        }
+        
+        self.player.pause();
+        if by_second {
+            self.player.step_seconds(direction as f64);
+        } else {
+            self.player.step_frame(direction);
+        }
+        self.pause_timeline_audio(cx);
+        
+        // The stepped-to frame (and position) settle in the pipeline a moment later
+        cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(Duration::from_millis(50)).await;
+            let _ = this.update(cx, |this, cx| {
+                this.sync_timeline_to_player(cx);
+                cx.notify();
+            });
+        })
+        .detach();
+        true
+    }
+    
     fn load_video(&mut self, path: std::path::PathBuf, cx: &mut Context<Self>) {
         tracing::info!("Video clip added: {:?}", path);
         
         // Reload the player to include the new clip
-        self.reload_player(cx);
+        self.sync_player(cx);
+        
+        cx.notify();
+    }
+    
+    fn toggle_playback(&mut self, cx: &mut Context<Self>) {
+        if !self.player.is_loaded() {
+            return;
+        }
         
+        if self.player.state() == PlayerState::Playing {
+            self.player.pause();
+        } else {
+            self.pause_timeline_audio(cx);
+            self.player.play();
+            self.start_playback_sync(cx);
+        }
+        cx.notify();
+    }
+    
+    /// Play the whole sequence on repeat, e.g. to watch the montage a few
+    /// times over before exporting
+    fn toggle_preview_loop(&mut self, cx: &mut Context<Self>) {
+        let looping = !self.player.is_looping();
+        self.player.set_looping(looping);
+        self.last_agent_message = Some(if looping { "🔁 Looping the preview" } else { "Loop off" }.to_string());
+        self.last_agent_results = vec![];
+        cx.notify();
+    }
+    
+    fn stop_playback(&mut self, cx: &mut Context<Self>) {
+        self.pause_timeline_audio(cx);
+        self.player.rewind();
+        self.sync_timeline_to_player(cx);
         cx.notify();
     }
+    
+    /// Silence the timeline's own audio playback
+    fn pause_timeline_audio(&mut self, cx: &mut Context<Self>) {
+        if let AppState::Loaded { ref timeline } = self.state {
+            timeline.update(cx, |timeline, cx| timeline.pause(cx));
+        }
+    }
+    
+    /// Move the timeline playhead to the player's real position
+    fn sync_timeline_to_player(&mut self, cx: &mut Context<Self>) {
+        let seconds = self.player.get_position() * self.player.duration();
+        self.project.timeline.position = seconds;
+        if let AppState::Loaded { ref timeline } = self.state {
+            timeline.update(cx, |timeline, cx| timeline.set_time(seconds, cx));
+        }
+    }
+    
+    /// Put the playhead (and the preview) back where the project was saved
+    fn restore_playhead(&mut self, cx: &mut Context<Self>) {
+        let AppState::Loaded { ref timeline } = self.state else {
+            return;
+        };
+        let seconds = self.project.timeline.position;
+        let duration = timeline.read(cx).duration();
+        timeline.update(cx, |timeline, cx| timeline.set_time(seconds, cx));
+        if duration > 0.0 {
+            self.player.seek(seconds / duration);
+        }
+    }
+    
+    /// Repaint the preview (~30fps) and follow the pipeline position while playing
+    fn start_playback_sync(&mut self, cx: &mut Context<Self>) {
+        cx.spawn(async move |this, cx| {
+            loop {
+                cx.background_executor().timer(Duration::from_millis(33)).await;
+                
+                let should_continue = this.update(cx, |this, cx| {
+                    this.player.poll_bus();
+                    this.sync_timeline_to_player(cx);
+                    cx.notify();
+                    this.player.state() == PlayerState::Playing
+                }).unwrap_or(false);
+                
+                if !should_continue {
+                    break;
+                }
+            }
+        }).detach();
+    }
 
     fn open_audio_picker(&mut self, cx: &mut Context<Self>) {
         let future = cx.prompt_for_paths(PathPromptOptions {
@@ -745,49 +2628,34 @@ impl MainView {
 
 impl Render for MainView {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = self.config.theme.theme();
         div()
             .id("main-view")
+            .key_context("MainView")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::export))
+            .on_action(cx.listener(Self::open))
+            .on_action(cx.listener(Self::redo))
+            .on_action(cx.listener(Self::save))
+            .on_action(cx.listener(Self::save_as))
+            .on_action(cx.listener(Self::set_in_point))
+            .on_action(cx.listener(Self::set_out_point))
+            .on_action(cx.listener(Self::split_at_playhead))
+            .on_action(cx.listener(Self::step_backward))
+            .on_action(cx.listener(Self::step_forward))
+            .on_action(cx.listener(Self::toggle_help))
+            .on_action(cx.listener(Self::toggle_loop))
+            .on_action(cx.listener(Self::toggle_playback_action))
+            .on_action(cx.listener(Self::undo))
             .flex()
             .flex_col()
             .size_full()
-            .bg(rgb(0x1a1a1a))
+            .bg(rgb(theme.background))
             // Drag & drop support
             .on_drop(cx.listener(|this, paths: &ExternalPaths, _window, cx| {
-                let files: Vec<_> = paths.paths().to_vec();
-                if files.is_empty() {
-                    return;
-                }
-                
-                tracing::info!("Dropped {} file(s)", files.len());
-                
-                for file in files {
-                    let description = file
-                        .file_name()
-                        .map(|n| n.to_string_lossy().to_string())
-                        .unwrap_or_else(|| "Dropped file".to_string());
-                    
-                    let clip = this.project.add_clip(description, file.clone());
-                    let media_type = clip.media_type.clone();
-                    
-                    match media_type {
-                        project::MediaType::Audio => {
-                            this.load_audio(file, cx);
-                        }
-                        project::MediaType::Video => {
-                            this.load_video(file, cx);
-                        }
-                        project::MediaType::Image => {
-                            tracing::info!("Image support coming soon");
-                        }
-                    }
-                }
-                
-                this.sync_clips_panel(cx);
-                this.last_agent_message = Some(format!("Added {} file(s) via drag & drop", paths.paths().len()));
-                this.last_agent_results = vec![];
-                cx.notify();
+                this.add_dropped_files(paths.paths().to_vec(), None, cx);
             }))
-            .text_color(rgb(0xffffff))
+            .text_color(rgb(theme.text))
             // Header
             .child(
                 div()
@@ -796,7 +2664,7 @@ impl Render for MainView {
                     .justify_between()
                     .p_4()
                     .border_b_1()
-                    .border_color(rgb(0x333333))
+                    .border_color(rgb(theme.control))
                     .child(
                         div()
                             .flex()
@@ -814,9 +2682,21 @@ impl Render for MainView {
                             .child(
                                 div()
                                     .text_sm()
-                                    .text_color(rgb(0x888888))
+                                    .text_color(rgb(theme.text_muted))
                                     .child(format!("— {}", self.project.metadata.name)),
                             )
+                            .when_some(
+                                self.project_path.as_ref().and(self.project.metadata.modified()),
+                                |header, modified| {
+                                    let seconds = (chrono::Utc::now() - modified).num_seconds();
+                                    header.child(
+                                        div()
+                                            .text_xs()
+                                            .text_color(rgb(theme.text_dim))
+                                            .child(format!("Last saved {}", project::time_ago(seconds))),
+                                    )
+                                },
+                            )
                             // Status indicators
                             .child(
                                 div()
@@ -826,14 +2706,25 @@ impl Render for MainView {
                                     .ml_4()
                                     .children(
                                         self.service_status.status_indicators().into_iter().map(|(name, ok)| {
+                                            let ready = ok == Some(true);
+                                            let label = if ok.is_none() { format!("{}…", name) } else { name.clone() };
                                             div()
+                                                .id(SharedString::from(format!("status-{}", name)))
                                                 .px_2()
                                                 .py_1()
                                                 .rounded_sm()
                                                 .text_xs()
-                                                .bg(if ok { rgb(0x2e7d32) } else { rgb(0x424242) })
-                                                .text_color(if ok { rgb(0xffffff) } else { rgb(0x888888) })
-                                                .child(name)
+                                                .cursor_pointer()
+                                                .bg(if ready { rgb(0x2e7d32) } else { rgb(theme.control) })
+                                                .text_color(if ready { rgb(0xffffff) } else { rgb(theme.text_muted) })
+                                                .hover(|s| s.opacity(0.8))
+                                                .child(label)
+                                                // e.g. after starting `ollama serve`
+                                                .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                                                    if this.service_check.is_none() {
+                                                        this.check_services(cx);
+                                                    }
+                                                }))
                                         })
                                     ),
                             ),
@@ -848,12 +2739,16 @@ impl Render for MainView {
                                     .id("open-project-btn")
                                     .px_3()
                                     .py_2()
-                                    .bg(rgb(0x333333))
-                                    .text_color(rgb(0xcccccc))
+                                    .bg(rgb(theme.control))
+                                    .text_color(rgb(theme.text_dim))
                                     .rounded_md()
                                     .cursor_pointer()
-                                    .hover(|s| s.bg(rgb(0x444444)))
+                                    .hover(|s| s.bg(rgb(theme.control_hover)))
+                                    .flex()
+                                    .items_center()
+                                    .gap_2()
                                     .child("Open")
+                                    .child(shortcut_hint("O"))
                                     .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
                                         this.open_project(cx);
                                     })),
@@ -863,18 +2758,38 @@ impl Render for MainView {
                                     .id("save-project-btn")
                                     .px_3()
                                     .py_2()
-                                    .bg(rgb(0x333333))
-                                    .text_color(rgb(0xcccccc))
+                                    .bg(rgb(theme.control))
+                                    .text_color(rgb(theme.text_dim))
+                                    .rounded_md()
+                                    .cursor_pointer()
+                                    .hover(|s| s.bg(rgb(theme.control_hover)))
+                                    .flex()
+                                    .items_center()
+                                    .gap_2()
+                                    .child("Save")
+                                    .child(shortcut_hint("S"))
+                                    .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                                        this.save_project(cx);
+                                    })),
+                            )
+                            .child(
+                                div()
+                                    .id("shortcuts-btn")
+                                    .px_3()
+                                    .py_2()
+                                    .bg(if self.help_open { rgb(theme.selected) } else { rgb(theme.control) })
+                                    .text_color(rgb(theme.text_dim))
                                     .rounded_md()
                                     .cursor_pointer()
-                                    .hover(|s| s.bg(rgb(0x444444)))
-                                    .child("Save")
+                                    .hover(|s| s.bg(rgb(theme.control_hover)))
+                                    .child("?")
                                     .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
-                                        this.save_project(cx);
+                                        this.help_open = !this.help_open;
+                                        cx.notify();
                                     })),
                             )
                             // Separator
-                            .child(div().w_px().h_6().bg(rgb(0x444444)))
+                            .child(div().w_px().h_6().bg(rgb(theme.control_hover)))
                             // Export button
                             .child(
                                 div()
@@ -887,9 +2802,14 @@ impl Render for MainView {
                                     .rounded_md()
                                     .cursor_pointer()
                                     .hover(|s| s.bg(rgb(0x66bb6a)))
+                                    .flex()
+                                    .items_center()
+                                    .gap_2()
                                     .child("Export")
+                                    .child(shortcut_hint("E"))
                                     .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
-                                        this.start_export(cx);
+                                        this.export_dialog_open = !this.export_dialog_open;
+                                        cx.notify();
                                     })),
                             ),
                     ),
@@ -916,7 +2836,7 @@ impl Render for MainView {
                                 div()
                                     .h(px(200.0))
                                     .border_t_1()
-                                    .border_color(rgb(0x333333))
+                                    .border_color(rgb(theme.control))
                                     .child(match &self.state {
                                         AppState::Empty => self.render_empty(cx).into_any_element(),
                                         AppState::Error(msg) => self.render_error(msg).into_any_element(),
@@ -931,10 +2851,20 @@ impl Render for MainView {
                 div()
                     .p_4()
                     .border_t_1()
-                    .border_color(rgb(0x333333))
+                    .border_color(rgb(theme.control))
                     .flex()
                     .flex_col()
                     .gap_2()
+                    // Keyboard shortcuts (toggled with ?)
+                    .child(self.render_shortcuts_help())
+                    // Export options (when the Export button was clicked)
+                    .child(self.render_export_dialog(cx))
+                    // Export progress (while exporting)
+                    .child(self.render_export_progress(cx))
+                    // Auto-video stages (while generating)
+                    .child(self.render_auto_video_progress(cx))
+                    // Earlier exchanges with the agent
+                    .child(self.render_conversation(cx))
                     // Agent response (if any)
                     .child(if let Some(ref msg) = self.last_agent_message {
                         let msg_for_copy = msg.clone();
@@ -943,10 +2873,10 @@ impl Render for MainView {
                             .flex_col()
                             .gap_1()
                             .p_3()
-                            .bg(rgb(0x252525))
+                            .bg(rgb(theme.surface))
                             .rounded_md()
                             .border_l_2()
-                            .border_color(rgb(0x4fc3f7))
+                            .border_color(rgb(theme.accent))
                             .child(
                                 div()
                                     .flex()
@@ -958,7 +2888,7 @@ impl Render for MainView {
                                         div()
                                             .flex_1()
                                             .text_sm()
-                                            .text_color(rgb(0xdddddd))
+                                            .text_color(rgb(theme.text))
                                             .children(render_markdown_text(&format!("🤖 {}", msg)))
                                     )
                                     .child(
@@ -968,9 +2898,9 @@ impl Render for MainView {
                                             .px_2()
                                             .py_1()
                                             .text_xs()
-                                            .text_color(rgb(0x888888))
+                                            .text_color(rgb(theme.text_muted))
                                             .cursor_pointer()
-                                            .hover(|s| s.text_color(rgb(0x4fc3f7)).bg(rgb(0x333333)))
+                                            .hover(|s| s.text_color(rgb(theme.accent)).bg(rgb(theme.control)))
                                             .rounded(px(4.0))
                                             .child("📋")
                                             .on_click(cx.listener(move |_this, _event: &ClickEvent, _window, cx| {
@@ -978,150 +2908,889 @@ impl Render for MainView {
                                             }))
                                     )
                             )
-                            .children(
-                                self.last_agent_results.iter().map(|r| {
-                                    div()
-                                        .text_xs()
-                                        .text_color(rgb(0x888888))
-                                        .child(r.clone())
-                                })
+                            .children(
+                                self.last_agent_results.iter().map(|r| {
+                                    div()
+                                        .text_xs()
+                                        .text_color(rgb(theme.text_muted))
+                                        .child(r.clone())
+                                })
+                            )
+                            .children(self.recovery.is_some().then(|| self.render_recovery_buttons(cx)))
+                            .children(self.pending_edits.is_some().then(|| self.render_pending_edit_buttons(cx)))
+                            .into_any_element()
+                    } else {
+                        div().into_any_element()
+                    })
+                    // Clips indicator
+                    .child(if !self.project.clips.is_empty() {
+                        div()
+                            .text_xs()
+                            .text_color(rgb(theme.text_muted))
+                            .child(format!("📁 {} clip(s) in project", self.project.clips.len()))
+                            .into_any_element()
+                    } else {
+                        div().into_any_element()
+                    })
+                    .child(self.prompt.clone()),
+            )
+    }
+}
+
+impl MainView {
+    fn render_video_preview(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = self.config.theme.theme();
+        let is_loaded = self.player.is_loaded();
+        let is_playing = self.player.state() == PlayerState::Playing;
+        let is_looping = self.player.is_looping();
+        let duration = self.player.duration();
+        let position = self.player.get_position();
+        let still = self.still_preview.clone().filter(|_| !is_playing);
+        let timestamp = is_loaded.then(|| {
+            let seconds = position * duration;
+            let frame = (seconds * self.player.frame_rate()).round() as u64;
+            format!("{} · frame {}", export::format_timecode(seconds, self.player.frame_rate()), frame)
+        });
+        
+        div()
+            .flex_1()
+            .flex()
+            .flex_col()
+            .bg(rgb(0x0d0d0d))
+            // Video display area (click to focus, then arrows scrub)
+            .child(
+                div()
+                    .id("preview-area")
+                    .track_focus(&self.preview_focus)
+                    .on_click(cx.listener(|this, _event: &ClickEvent, window, cx| {
+                        this.preview_focus.focus(window, cx);
+                    }))
+                    .on_key_down(cx.listener(|this, event: &KeyDownEvent, _window, cx| {
+                        this.handle_preview_key(event, cx);
+                    }))
+                    .relative()
+                    .flex_1()
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    // Current time, over the picture
+                    .children(timestamp.map(|timestamp| {
+                        div()
+                            .absolute()
+                            .bottom_2()
+                            .right_2()
+                            .px_2()
+                            .py_1()
+                            .rounded_sm()
+                            .bg(rgba(0x000000aa))
+                            .text_xs()
+                            .text_color(rgb(theme.text))
+                            .child(timestamp)
+                    }))
+                    .child(if let Some(still) = still {
+                        div()
+                            .child(
+                                img(still)
+                                    .max_w(px(800.0))
+                                    .max_h(px(400.0))
+                                    .rounded_md(),
+                            )
+                            .into_any_element()
+                    } else if is_loaded {
+                        if let Some(frame) = self.player.current_frame() {
+                            // Convert frame to image
+                            let img_buffer: Option<image::ImageBuffer<image::Rgba<u8>, Vec<u8>>> = 
+                                image::ImageBuffer::from_raw(frame.width, frame.height, frame.data.clone());
+                            
+                            if let Some(buffer) = img_buffer {
+                                let img_frame = image::Frame::new(buffer);
+                                let render_image = Arc::new(RenderImage::new(vec![img_frame]));
+                                
+                                div()
+                                    .child(
+                                        img(render_image)
+                                            .max_w(px(800.0))
+                                            .max_h(px(400.0))
+                                            .rounded_md(),
+                                    )
+                                    .into_any_element()
+                            } else {
+                                div()
+                                    .text_color(rgb(theme.accent))
+                                    .child("🎬 Video ready")
+                                    .into_any_element()
+                            }
+                        } else {
+                            div()
+                                .text_color(rgb(theme.accent))
+                                .child("🎬 Video loaded - press Play")
+                                .into_any_element()
+                        }
+                    } else {
+                        div()
+                            .flex()
+                            .flex_col()
+                            .items_center()
+                            .gap_4()
+                            .child(div().text_3xl().text_color(rgb(theme.control)).child("📹"))
+                            .child(div().text_color(rgb(theme.text_muted)).child("Add video clips to preview"))
+                            .into_any_element()
+                    }),
+            )
+            // Playback controls
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .gap_4()
+                    .p_4()
+                    .border_t_1()
+                    .border_color(rgb(theme.control))
+                    // Previous frame
+                    .child(
+                        div()
+                            .id("step-back-btn")
+                            .px_3()
+                            .py_2()
+                            .bg(rgb(theme.control))
+                            .text_color(if is_loaded { rgb(theme.text_dim) } else { rgb(theme.text_muted) })
+                            .rounded_md()
+                            .cursor(if is_loaded { CursorStyle::PointingHand } else { CursorStyle::default() })
+                            .child("◀▮")
+                            .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                                this.step_preview(-1, false, cx);
+                            })),
+                    )
+                    // Play/Pause button
+                    .child(
+                        div()
+                            .id("play-pause-btn")
+                            .px_4()
+                            .py_2()
+                            .bg(if is_loaded { rgb(theme.accent) } else { rgb(theme.control) })
+                            .text_color(if is_loaded { rgb(0x000000) } else { rgb(theme.text_muted) })
+                            .font_weight(FontWeight::MEDIUM)
+                            .rounded_md()
+                            .cursor(if is_loaded { CursorStyle::PointingHand } else { CursorStyle::default() })
+                            .child(if is_playing { "⏸ Pause" } else { "▶ Play" })
+                            .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                                this.toggle_playback(cx);
+                            })),
+                    )
+                    // Next frame
+                    .child(
+                        div()
+                            .id("step-forward-btn")
+                            .px_3()
+                            .py_2()
+                            .bg(rgb(theme.control))
+                            .text_color(if is_loaded { rgb(theme.text_dim) } else { rgb(theme.text_muted) })
+                            .rounded_md()
+                            .cursor(if is_loaded { CursorStyle::PointingHand } else { CursorStyle::default() })
+                            .child("▮▶")
+                            .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                                this.step_preview(1, false, cx);
+                            })),
+                    )
+                    // Stop button
+                    .child(
+                        div()
+                            .id("stop-btn")
+                            .px_3()
+                            .py_2()
+                            .bg(rgb(theme.control))
+                            .text_color(if is_loaded { rgb(theme.text_dim) } else { rgb(theme.text_muted) })
+                            .rounded_md()
+                            .cursor(if is_loaded { CursorStyle::PointingHand } else { CursorStyle::default() })
+                            .child("⏹ Stop")
+                            .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                                this.stop_playback(cx);
+                            })),
+                    )
+                    // Loop toggle
+                    .child(
+                        div()
+                            .id("loop-btn")
+                            .px_3()
+                            .py_2()
+                            .bg(if is_looping { rgb(theme.selected) } else { rgb(theme.control) })
+                            .border_1()
+                            .border_color(if is_looping { rgb(theme.accent) } else { rgb(theme.control) })
+                            .text_color(if is_loaded { rgb(theme.text_dim) } else { rgb(theme.text_muted) })
+                            .rounded_md()
+                            .cursor_pointer()
+                            .child("🔁 Loop")
+                            .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                                this.toggle_preview_loop(cx);
+                            })),
+                    )
+                    // Time display
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(rgb(theme.text_muted))
+                            .child(format!("{:.1}s / {:.1}s", position * duration, duration)),
+                    )
+                    // Reload button
+                    .child(
+                        div()
+                            .id("reload-btn")
+                            .px_3()
+                            .py_2()
+                            .bg(rgb(theme.control))
+                            .text_color(rgb(theme.text_dim))
+                            .rounded_md()
+                            .cursor_pointer()
+                            .hover(|s| s.bg(rgb(theme.control_hover)))
+                            .child("🔄 Reload")
+                            .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                                this.reload_player(cx);
+                            })),
+                    )
+                    // Save the preview as a PNG
+                    .child(
+                        div()
+                            .id("grab-frame-btn")
+                            .px_3()
+                            .py_2()
+                            .bg(rgb(theme.control))
+                            .text_color(rgb(theme.text_dim))
+                            .rounded_md()
+                            .cursor_pointer()
+                            .hover(|s| s.bg(rgb(theme.control_hover)))
+                            .child("🖼 Grab frame")
+                            .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                                this.save_frame(None, cx);
+                            })),
+                    ),
+            )
+    }
+    
+    /// Restore / Discard pair shown while an autosave awaits a decision
+    fn render_recovery_buttons(&self, cx: &mut Context<Self>) -> AnyElement {
+        let theme = self.config.theme.theme();
+        div()
+            .flex()
+            .gap_2()
+            .mt_1()
+            .child(
+                div()
+                    .id("restore-autosave-btn")
+                    .px_3()
+                    .py_1()
+                    .bg(rgb(theme.accent))
+                    .text_color(rgb(0x000000))
+                    .text_sm()
+                    .rounded_md()
+                    .cursor_pointer()
+                    .hover(|s| s.bg(rgb(theme.accent_hover)))
+                    .child("Restore")
+                    .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                        this.restore_recovery(cx);
+                    })),
+            )
+            .child(
+                div()
+                    .id("discard-autosave-btn")
+                    .px_3()
+                    .py_1()
+                    .bg(rgb(theme.control))
+                    .text_color(rgb(theme.text_dim))
+                    .text_sm()
+                    .rounded_md()
+                    .cursor_pointer()
+                    .hover(|s| s.bg(rgb(theme.control_hover)))
+                    .child("Discard")
+                    .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                        this.discard_recovery(cx);
+                    })),
+            )
+            .into_any_element()
+    }
+    
+    /// Apply / Discard pair for agent edits awaiting confirmation
+    fn render_pending_edit_buttons(&self, cx: &mut Context<Self>) -> AnyElement {
+        let theme = self.config.theme.theme();
+        div()
+            .flex()
+            .gap_2()
+            .mt_1()
+            .child(
+                div()
+                    .id("apply-pending-btn")
+                    .px_3()
+                    .py_1()
+                    .bg(rgb(theme.accent))
+                    .text_color(rgb(0x000000))
+                    .text_sm()
+                    .rounded_md()
+                    .cursor_pointer()
+                    .hover(|s| s.bg(rgb(theme.accent_hover)))
+                    .child("Apply")
+                    .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                        this.apply_pending_edits(cx);
+                    })),
+            )
+            .child(
+                div()
+                    .id("discard-pending-btn")
+                    .px_3()
+                    .py_1()
+                    .bg(rgb(theme.control))
+                    .text_color(rgb(theme.text_dim))
+                    .text_sm()
+                    .rounded_md()
+                    .cursor_pointer()
+                    .hover(|s| s.bg(rgb(theme.control_hover)))
+                    .child("Discard")
+                    .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                        this.discard_pending_edits(cx);
+                    })),
+            )
+            .into_any_element()
+    }
+    
+    fn render_shortcuts_help(&self) -> AnyElement {
+        let theme = self.config.theme.theme();
+        if !self.help_open {
+            return div().into_any_element();
+        }
+        
+        div()
+            .flex()
+            .flex_wrap()
+            .gap_x_6()
+            .gap_y_1()
+            .p_3()
+            .bg(rgb(theme.surface))
+            .rounded_md()
+            .children(SHORTCUTS.iter().map(|(keys, action)| {
+                div()
+                    .flex()
+                    .gap_2()
+                    .text_xs()
+                    .child(div().text_color(rgb(theme.accent)).child(*keys))
+                    .child(div().text_color(rgb(theme.text_dim)).child(*action))
+            }))
+            .into_any_element()
+    }
+    
+    /// Scrollable log of past prompts and replies, with a button to forget them
+    fn render_conversation(&self, cx: &mut Context<Self>) -> AnyElement {
+        let theme = self.config.theme.theme();
+        let turns = &self.project.conversation;
+        if turns.is_empty() {
+            return div().into_any_element();
+        }
+        // The latest reply is already in the message box below
+        let shown = match turns.last() {
+            Some(last) if self.last_agent_message.as_ref() == Some(&last.agent) => &turns[..turns.len() - 1],
+            _ => &turns[..],
+        };
+        
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .child(
+                div()
+                    .flex()
+                    .justify_between()
+                    .items_center()
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(rgb(theme.text_muted))
+                            .child(format!("💬 {} message(s) of context", turns.len())),
+                    )
+                    .child(
+                        div()
+                            .id("clear-conversation")
+                            .px_2()
+                            .text_xs()
+                            .text_color(rgb(theme.text_muted))
+                            .cursor_pointer()
+                            .rounded(px(4.0))
+                            .hover(|s| s.text_color(rgb(theme.accent)).bg(rgb(theme.control)))
+                            .child("Clear conversation")
+                            .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                                this.project.conversation.clear();
+                                this.mark_dirty(cx);
+                                cx.notify();
+                            })),
+                    ),
+            )
+            .child(
+                div()
+                    .id("conversation")
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .max_h(px(240.0))
+                    .overflow_y_scroll()
+                    .children(shown.iter().map(|turn| {
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .self_end()
+                                    .px_2()
+                                    .py_1()
+                                    .bg(rgb(0x2f3f4f))
+                                    .rounded_md()
+                                    .text_sm()
+                                    .text_color(rgb(theme.text))
+                                    .child(turn.user.clone()),
+                            )
+                            .child(
+                                div()
+                                    .px_2()
+                                    .py_1()
+                                    .bg(rgb(theme.surface))
+                                    .rounded_md()
+                                    .text_sm()
+                                    .text_color(rgb(theme.text_dim))
+                                    .children(render_markdown_text(&format!("🤖 {}", turn.agent))),
                             )
-                            .into_any_element()
-                    } else {
-                        div().into_any_element()
-                    })
-                    // Clips indicator
-                    .child(if !self.project.clips.is_empty() {
-                        div()
-                            .text_xs()
-                            .text_color(rgb(0x666666))
-                            .child(format!("📁 {} clip(s) in project", self.project.clips.len()))
-                            .into_any_element()
-                    } else {
-                        div().into_any_element()
-                    })
-                    .child(self.prompt.clone()),
+                    })),
             )
+            .into_any_element()
     }
-}
-
-impl MainView {
-    fn render_video_preview(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
-        let is_loaded = self.player.is_loaded();
-        let is_playing = self.player.state() == PlayerState::Playing;
-        let duration = self.player.duration();
-        let position = self.player.get_position();
+    
+    fn render_export_dialog(&self, cx: &mut Context<Self>) -> AnyElement {
+        let theme = self.config.theme.theme();
+        if !self.export_dialog_open || self.export_job.is_some() {
+            return div().into_any_element();
+        }
+        
+        let chip = |id: SharedString, label: String, selected: bool| {
+            div()
+                .id(id)
+                .px_3()
+                .py_1()
+                .rounded_md()
+                .text_sm()
+                .cursor_pointer()
+                .bg(if selected { rgb(theme.selected) } else { rgb(theme.control) })
+                .text_color(if selected { rgb(theme.text) } else { rgb(theme.text_dim) })
+                .hover(|s| s.bg(rgb(theme.control_hover)))
+                .child(label)
+        };
+        
+        let presets = [
+            export::ExportPreset::Landscape1080p,
+            export::ExportPreset::Vertical9x16,
+            export::ExportPreset::Square1x1,
+        ];
+        let formats = [
+            export::OutputFormat::Mp4H264,
+            export::OutputFormat::WebMVp9,
+            export::OutputFormat::Gif,
+        ];
+        let resolutions = [
+            export::Resolution::P720,
+            export::Resolution::P1080,
+            export::Resolution::P1440,
+            export::Resolution::P4K,
+        ];
+        let qualities = [
+            None,
+            Some(export::Quality::Low),
+            Some(export::Quality::Medium),
+            Some(export::Quality::High),
+        ];
+        let modes = [
+            export::AspectMode::Letterbox,
+            export::AspectMode::CropToFill,
+            export::AspectMode::Stretch,
+        ];
+        let subtitle_modes = [
+            export::SubtitleMode::None,
+            export::SubtitleMode::Sidecar,
+            export::SubtitleMode::Burned,
+        ];
+        let transitions = [
+            export::Transition::Cut,
+            export::Transition::Crossfade(0.5),
+            export::Transition::Crossfade(export::Transition::DEFAULT_CROSSFADE),
+        ];
+        let encoders = [
+            export::VideoEncoder::Software,
+            export::VideoEncoder::Auto,
+            export::VideoEncoder::VideoToolbox,
+            export::VideoEncoder::Nvenc,
+            export::VideoEncoder::Vaapi,
+        ];
         
         div()
-            .flex_1()
             .flex()
             .flex_col()
-            .bg(rgb(0x0d0d0d))
-            // Video display area
+            .gap_2()
+            .p_3()
+            .bg(rgb(theme.surface))
+            .rounded_md()
+            // Frame size
             .child(
                 div()
-                    .flex_1()
                     .flex()
                     .items_center()
-                    .justify_center()
-                    .child(if is_loaded {
-                        if let Some(frame) = self.player.current_frame() {
-                            // Convert frame to image
-                            let img_buffer: Option<image::ImageBuffer<image::Rgba<u8>, Vec<u8>>> = 
-                                image::ImageBuffer::from_raw(frame.width, frame.height, frame.data.clone());
-                            
-                            if let Some(buffer) = img_buffer {
-                                let img_frame = image::Frame::new(buffer);
-                                let render_image = Arc::new(RenderImage::new(vec![img_frame]));
-                                
-                                div()
-                                    .child(
-                                        img(render_image)
-                                            .max_w(px(800.0))
-                                            .max_h(px(400.0))
-                                            .rounded_md(),
-                                    )
-                                    .into_any_element()
-                            } else {
-                                div()
-                                    .text_color(rgb(0x4fc3f7))
-                                    .child("🎬 Video ready")
-                                    .into_any_element()
-                            }
-                        } else {
-                            div()
-                                .text_color(rgb(0x4fc3f7))
-                                .child("🎬 Video loaded - press Play")
-                                .into_any_element()
-                        }
-                    } else {
-                        div()
-                            .flex()
-                            .flex_col()
-                            .items_center()
-                            .gap_4()
-                            .child(div().text_3xl().text_color(rgb(0x333333)).child("📹"))
-                            .child(div().text_color(rgb(0x555555)).child("Add video clips to preview"))
-                            .into_any_element()
-                    }),
+                    .gap_2()
+                    .child(div().w(px(60.0)).text_xs().text_color(rgb(theme.text_muted)).child("Format"))
+                    .children(presets.into_iter().enumerate().map(|(i, preset)| {
+                        chip(format!("preset-{}", i).into(), preset.label(), self.export_preset == preset)
+                            .on_click(cx.listener(move |this, _event: &ClickEvent, _window, cx| {
+                                this.export_preset = preset;
+                                this.export_aspect_mode = preset.default_aspect_mode();
+                                cx.notify();
+                            }))
+                    })),
             )
-            // Playback controls
+            // File type
             .child(
                 div()
                     .flex()
                     .items_center()
-                    .justify_center()
-                    .gap_4()
-                    .p_4()
-                    .border_t_1()
-                    .border_color(rgb(0x333333))
-                    // Play/Pause button
+                    .gap_2()
+                    .child(div().w(px(60.0)).text_xs().text_color(rgb(theme.text_muted)).child("File"))
+                    .children(formats.into_iter().enumerate().map(|(i, format)| {
+                        chip(format!("format-{}", i).into(), format.label().to_string(), self.export_format == format)
+                            .on_click(cx.listener(move |this, _event: &ClickEvent, _window, cx| {
+                                this.export_format = format;
+                                cx.notify();
+                            }))
+                    })),
+            )
+            // Scale of the frame; custom sizes from the prompt keep theirs
+            .when(!matches!(self.export_preset, export::ExportPreset::Custom { .. }), |d| d.child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(60.0)).text_xs().text_color(rgb(theme.text_muted)).child("Size"))
+                    .children(resolutions.into_iter().enumerate().map(|(i, resolution)| {
+                        chip(format!("resolution-{}", i).into(), resolution.label().to_string(), self.export_resolution == resolution)
+                            .on_click(cx.listener(move |this, _event: &ClickEvent, _window, cx| {
+                                this.export_resolution = resolution;
+                                cx.notify();
+                            }))
+                    })),
+            ))
+            // Encoding quality
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(60.0)).text_xs().text_color(rgb(theme.text_muted)).child("Quality"))
+                    .children(qualities.into_iter().enumerate().map(|(i, quality)| {
+                        let label = quality.map_or("Default", |q| q.label()).to_string();
+                        chip(format!("quality-{}", i).into(), label, self.export_quality == quality)
+                            .on_click(cx.listener(move |this, _event: &ClickEvent, _window, cx| {
+                                this.export_quality = quality;
+                                cx.notify();
+                            }))
+                    })),
+            )
+            // Aspect handling
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(60.0)).text_xs().text_color(rgb(theme.text_muted)).child("Fit"))
+                    .children(modes.into_iter().enumerate().map(|(i, mode)| {
+                        chip(format!("aspect-{}", i).into(), mode.label().to_string(), self.export_aspect_mode == mode)
+                            .on_click(cx.listener(move |this, _event: &ClickEvent, _window, cx| {
+                                this.export_aspect_mode = mode;
+                                cx.notify();
+                            }))
+                    })),
+            )
+            // Subtitles from the transcript
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(60.0)).text_xs().text_color(rgb(theme.text_muted)).child("Subtitles"))
+                    .children(subtitle_modes.into_iter().enumerate().map(|(i, mode)| {
+                        chip(format!("subtitles-{}", i).into(), mode.label().to_string(), self.export_subtitles == mode)
+                            .on_click(cx.listener(move |this, _event: &ClickEvent, _window, cx| {
+                                this.export_subtitles = mode;
+                                cx.notify();
+                            }))
+                    })),
+            )
+            // Between clips
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(60.0)).text_xs().text_color(rgb(theme.text_muted)).child("Transition"))
+                    .children(transitions.into_iter().enumerate().map(|(i, transition)| {
+                        chip(format!("transition-{}", i).into(), transition.label(), self.export_transition == transition)
+                            .on_click(cx.listener(move |this, _event: &ClickEvent, _window, cx| {
+                                this.export_transition = transition;
+                                cx.notify();
+                            }))
+                    })),
+            )
+            // Software or GPU encoding
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(60.0)).text_xs().text_color(rgb(theme.text_muted)).child("Encoder"))
+                    .children(encoders.into_iter().enumerate().map(|(i, encoder)| {
+                        chip(format!("encoder-{}", i).into(), encoder.label().to_string(), self.export_encoder == encoder)
+                            .on_click(cx.listener(move |this, _event: &ClickEvent, _window, cx| {
+                                this.export_encoder = encoder;
+                                cx.notify();
+                            }))
+                    })),
+            )
+            // Clip audio under the voiceover
+            .children(self.project.audio.is_some().then(|| {
+                let levels = [
+                    ("Full", 1.0),
+                    ("Ducked 20%", export::DUCKED_CLIP_GAIN),
+                    ("Muted", 0.0),
+                ];
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(60.0)).text_xs().text_color(rgb(theme.text_muted)).child("Clip audio"))
+                    .children(levels.into_iter().enumerate().map(|(i, (label, gain))| {
+                        chip(format!("clip-audio-{}", i).into(), label.to_string(), self.export_clip_audio_gain == gain)
+                            .on_click(cx.listener(move |this, _event: &ClickEvent, _window, cx| {
+                                this.export_clip_audio_gain = gain;
+                                // Preview the balance too
+                                this.player.set_clip_audio_gain(gain);
+                                cx.notify();
+                            }))
+                    }))
+            }))
+            // Markers as chapters
+            .children((!self.project.markers.is_empty()).then(|| {
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(60.0)).text_xs().text_color(rgb(theme.text_muted)).child("Chapters"))
+                    .children([false, true].into_iter().map(|on| {
+                        let label = if on {
+                            format!("From {} marker(s)", self.project.markers.len())
+                        } else {
+                            "None".to_string()
+                        };
+                        chip(format!("chapters-{}", on).into(), label, self.export_chapters == on)
+                            .on_click(cx.listener(move |this, _event: &ClickEvent, _window, cx| {
+                                this.export_chapters = on;
+                                cx.notify();
+                            }))
+                    }))
+            }))
+            // Whole timeline or the in/out points
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(60.0)).text_xs().text_color(rgb(theme.text_muted)).child("Range"))
                     .child(
-                        div()
-                            .id("play-pause-btn")
-                            .px_4()
-                            .py_2()
-                            .bg(if is_loaded { rgb(0x4fc3f7) } else { rgb(0x333333) })
-                            .text_color(if is_loaded { rgb(0x000000) } else { rgb(0x666666) })
-                            .font_weight(FontWeight::MEDIUM)
-                            .rounded_md()
-                            .cursor(if is_loaded { CursorStyle::PointingHand } else { CursorStyle::default() })
-                            .child(if is_playing { "⏸ Pause" } else { "▶ Play" })
+                        chip("range-all".into(), "Whole timeline".to_string(), self.export_range.is_none())
                             .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
-                                if this.player.is_loaded() {
-                                    if this.player.state() == PlayerState::Playing {
-                                        this.player.pause();
-                                    } else {
-                                        this.player.play();
-                                    }
-                                    cx.notify();
-                                }
+                                this.pick_export_range(None, cx);
                             })),
                     )
-                    // Time display
+                    .child(match self.export_range {
+                        Some((start, end)) => chip(
+                            "range-set".into(),
+                            format!("{} – {}", export::format_timestamp(start), export::format_timestamp(end)),
+                            true,
+                        )
+                        .into_any_element(),
+                        None => div()
+                            .text_xs()
+                            .text_color(rgb(theme.text_muted))
+                            .child("press I / O on the timeline")
+                            .into_any_element(),
+                    }),
+            )
+            // Loudness normalization of the final mix
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(60.0)).text_xs().text_color(rgb(theme.text_muted)).child("Loudness"))
+                    .children([false, true].into_iter().map(|normalize| {
+                        let label = if normalize {
+                            format!("Normalize ({} LUFS)", export::LOUDNESS_TARGET_LUFS)
+                        } else {
+                            "As recorded".to_string()
+                        };
+                        chip(format!("loudness-{}", normalize).into(), label, self.export_normalize_audio == normalize)
+                            .on_click(cx.listener(move |this, _event: &ClickEvent, _window, cx| {
+                                this.export_normalize_audio = normalize;
+                                cx.notify();
+                            }))
+                    })),
+            )
+            .child(
+                div()
+                    .flex()
+                    .justify_end()
                     .child(
                         div()
+                            .id("export-confirm-btn")
+                            .px_4()
+                            .py_1()
+                            .bg(rgb(0x4caf50))
                             .text_sm()
-                            .text_color(rgb(0x888888))
-                            .child(format!("{:.1}s / {:.1}s", position * duration, duration)),
-                    )
-                    // Reload button
-                    .child(
-                        div()
-                            .id("reload-btn")
-                            .px_3()
-                            .py_2()
-                            .bg(rgb(0x333333))
-                            .text_color(rgb(0xcccccc))
+                            .text_color(rgb(0xffffff))
                             .rounded_md()
                             .cursor_pointer()
-                            .hover(|s| s.bg(rgb(0x444444)))
-                            .child("🔄 Reload")
+                            .hover(|s| s.bg(rgb(0x66bb6a)))
+                            .child("Export…")
                             .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
-                                this.reload_player(cx);
+                                this.start_export(cx);
                             })),
                     ),
             )
+            .into_any_element()
+    }
+    
+    fn render_export_progress(&self, cx: &mut Context<Self>) -> AnyElement {
+        let theme = self.config.theme.theme();
+        let Some(ref job) = self.export_job else {
+            return div().into_any_element();
+        };
+        
+        let elapsed = job.started_at.elapsed().as_secs();
+        let mut timing = format!("{}:{:02}", elapsed / 60, elapsed % 60);
+        if let Some(speed) = job.progress.speed {
+            timing.push_str(&format!(" · {:.1}× realtime", speed));
+        }
+        if let Some(eta) = job.progress.eta_secs {
+            let eta = eta.round() as u64;
+            timing.push_str(&format!(" · {}:{:02} left", eta / 60, eta % 60));
+        }
+        
+        div()
+            .flex()
+            .items_center()
+            .gap_3()
+            .p_3()
+            .bg(rgb(theme.surface))
+            .rounded_md()
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(rgb(theme.text))
+                    .child(format!("{:.0}%", job.progress.fraction * 100.0)),
+            )
+            // Progress bar
+            .child(
+                div()
+                    .flex_1()
+                    .h_2()
+                    .bg(rgb(theme.control))
+                    .rounded_full()
+                    .overflow_hidden()
+                    .child(
+                        div()
+                            .h_full()
+                            .w(relative(job.progress.fraction as f32))
+                            .bg(rgb(0x4caf50))
+                            .rounded_full(),
+                    ),
+            )
+            // Elapsed time, speed and time left
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(rgb(theme.text_muted))
+                    .child(timing),
+            )
+            .child(
+                div()
+                    .id("cancel-export-btn")
+                    .px_3()
+                    .py_1()
+                    .bg(rgb(theme.control))
+                    .text_sm()
+                    .text_color(rgb(theme.text_dim))
+                    .rounded_md()
+                    .cursor_pointer()
+                    .hover(|s| s.bg(rgb(0x5c2b2b)))
+                    .child("Cancel")
+                    .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                        this.cancel_export(cx);
+                    })),
+            )
+            .into_any_element()
+    }
+    
+    fn render_auto_video_progress(&self, cx: &mut Context<Self>) -> AnyElement {
+        let theme = self.config.theme.theme();
+        let Some(ref job) = self.auto_video_job else {
+            return div().into_any_element();
+        };
+        let current = job.stages.len().saturating_sub(1);
+        
+        div()
+            .flex()
+            .items_start()
+            .gap_3()
+            .p_3()
+            .bg(rgb(theme.surface))
+            .rounded_md()
+            .child(
+                div()
+                    .flex_1()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .text_sm()
+                    .children(job.stages.iter().enumerate().map(|(i, stage)| {
+                        let (mark, color) = if i < current { ("✓", theme.text_muted) } else { ("›", theme.text) };
+                        let percent = stage
+                            .fraction()
+                            .filter(|_| i == current)
+                            .map(|f| format!(" · {:.0}%", f * 100.0))
+                            .unwrap_or_default();
+                        div()
+                            .text_color(rgb(color))
+                            .child(format!("{} {}{}", mark, stage.label(), percent))
+                    }))
+                    .when(job.stages.is_empty(), |list| {
+                        list.child(div().text_color(rgb(theme.text_muted)).child("Starting…"))
+                    }),
+            )
+            .child(
+                div()
+                    .id("cancel-auto-video-btn")
+                    .px_3()
+                    .py_1()
+                    .bg(rgb(theme.control))
+                    .text_sm()
+                    .text_color(rgb(theme.text_dim))
+                    .rounded_md()
+                    .cursor_pointer()
+                    .hover(|s| s.bg(rgb(0x5c2b2b)))
+                    .child("Cancel")
+                    .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                        this.cancel_auto_video(cx);
+                    })),
+            )
+            .into_any_element()
     }
     
     /// Reload the player with current project
@@ -1137,6 +3806,7 @@ impl MainView {
     }
     
     fn render_empty(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = self.config.theme.theme();
         div()
             .size_full()
             .flex()
@@ -1151,21 +3821,21 @@ impl MainView {
                     .child(
                         div()
                             .text_2xl()
-                            .text_color(rgb(0x333333))
+                            .text_color(rgb(theme.control))
                             .child("🎵"),
                     )
                     .child(
                         div()
-                            .text_color(rgb(0x555555))
+                            .text_color(rgb(theme.text_muted))
                             .child("Load audio to see waveform"),
                     )
                     .id("audio-drop-zone")
                     .p_8()
                     .border_2()
-                    .border_color(rgb(0x333333))
+                    .border_color(rgb(theme.control))
                     .rounded_lg()
                     .cursor_pointer()
-                    .hover(|s| s.border_color(rgb(0x4fc3f7)).bg(rgb(0x1e1e1e)))
+                    .hover(|s| s.border_color(rgb(theme.accent)).bg(rgb(theme.background)))
                     .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
                         this.open_audio_picker(cx);
                     })),
@@ -1195,6 +3865,7 @@ impl MainView {
     }
 
     fn render_loading(&self) -> impl IntoElement {
+        let theme = self.config.theme.theme();
         div()
             .size_full()
             .flex()
@@ -1207,7 +3878,7 @@ impl MainView {
                     .items_center()
                     .gap_4()
                     .child(div().text_2xl().child("⏳"))
-                    .child(div().text_lg().text_color(rgb(0x888888)).child("Loading...")),
+                    .child(div().text_lg().text_color(rgb(theme.text_muted)).child("Loading...")),
             )
     }
 }
@@ -1311,3 +3982,47 @@ fn render_markdown_line(line: &str) -> AnyElement {
     // Plain text
     div().child(line.to_string()).into_any_element()
 }
+
+/// Faded accelerator shown on a header button, e.g. "⌘S" or "Ctrl+S"
+fn shortcut_hint(key: &str) -> Div {
+    let label = if cfg!(target_os = "macos") {
+        format!("⌘{}", key)
+    } else {
+        format!("Ctrl+{}", key)
+    };
+    div().text_xs().opacity(0.6).child(label)
+}
+
+/// "Exporting 0:10 – 0:40", or the whole timeline
+fn describe_export_range(range: Option<(f64, f64)>) -> String {
+    match range {
+        Some((start, end)) => format!(
+            "✂️ Exporting {} – {}",
+            export::format_timestamp(start),
+            export::format_timestamp(end)
+        ),
+        None => "Exporting the whole timeline".to_string(),
+    }
+}
+
+/// Fresh autosave file for a project that was never saved
+fn new_untitled_autosave_path() -> Option<std::path::PathBuf> {
+    let dir = project::untitled_autosave_dir()?;
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        tracing::warn!("Failed to create {:?}: {}", dir, e);
+        return None;
+    }
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    Some(dir.join(format!("untitled-{}.{}", timestamp, Project::EXTENSION)))
+}
+
+/// Expand a leading `~` to the home directory
+fn expand_home(path: &str) -> std::path::PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => std::path::PathBuf::from(path),
+    }
+}
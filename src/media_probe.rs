@@ -0,0 +1,64 @@
+use anyhow::{Context, Result};
+use gstreamer as gst;
+use gstreamer_pbutils as gst_pbutils;
+use gstreamer_pbutils::prelude::*;
+use std::path::Path;
+
+use crate::project::MediaType;
+
+/// How long the discoverer may spend on one file
+const PROBE_TIMEOUT_SECS: u64 = 10;
+
+/// What a media file's headers say about it
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MediaInfo {
+    /// Length in seconds (none for stills)
+    pub duration: Option<f64>,
+    /// Width and height of the first video stream
+    pub dimensions: Option<(u32, u32)>,
+    /// Frames per second of the first video stream
+    pub frame_rate: Option<f64>,
+}
+
+/// Read a file's duration, size and frame rate without decoding it
+pub fn probe(path: &Path) -> Result<MediaInfo> {
+    let uri = format!("file://{}", path.canonicalize()?.display());
+    let discoverer = gst_pbutils::Discoverer::new(gst::ClockTime::from_seconds(PROBE_TIMEOUT_SECS))
+        .context("Failed to create discoverer")?;
+    let info = discoverer
+        .discover_uri(&uri)
+        .with_context(|| format!("Failed to probe {:?}", path))?;
+    
+    let duration = info
+        .duration()
+        .map(|d| d.nseconds() as f64 / 1_000_000_000.0)
+        .filter(|seconds| *seconds > 0.0);
+    let video = info.video_streams().into_iter().next();
+    let frame_rate = video
+        .as_ref()
+        .map(|v| v.framerate())
+        .filter(|fps| fps.numer() > 0 && fps.denom() > 0)
+        .map(|fps| f64::from(fps.numer()) / f64::from(fps.denom()));
+    
+    Ok(MediaInfo {
+        duration,
+        dimensions: video.as_ref().map(|v| (v.width(), v.height())),
+        frame_rate,
+    })
+}
+
+/// Make sure a clip's file can be used, returning its length (none for stills)
+pub fn check(path: &Path, media_type: &MediaType) -> Result<Option<f64>> {
+    if !path.exists() {
+        anyhow::bail!("File not found");
+    }
+    if *media_type == MediaType::Image {
+        image::image_dimensions(path).map_err(|e| anyhow::anyhow!("Not a readable image: {}", e))?;
+        return Ok(None);
+    }
+    let info = probe(path).map_err(|e| anyhow::anyhow!("Can't be decoded: {}", e.root_cause()))?;
+    if *media_type == MediaType::Video && info.dimensions.is_none() {
+        anyhow::bail!("No video stream in this file");
+    }
+    Ok(info.duration)
+}
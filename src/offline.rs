@@ -0,0 +1,176 @@
+//! Keyword parser for the most common edits, used when the model can't be reached
+
+use crate::agent::{AgentResponse, Modification};
+use crate::config::ThemeName;
+
+/// Turn a plain command into modifications without a model; None if it
+/// isn't one of the few phrasings understood here
+pub fn parse(input: &str) -> Option<AgentResponse> {
+    let input = input.trim().trim_end_matches(['.', '!']);
+    let (message, modification) = parse_command(input)?;
+    Some(AgentResponse {
+        message: format!("{} (offline parser, the model isn't reachable)", message),
+        modifications: vec![modification],
+    })
+}
+
+fn parse_command(input: &str) -> Option<(String, Modification)> {
+    if let Some(key) = strip_any(input, &["set pexels key to", "set pexels key", "set the pexels key to", "pexels key"]) {
+        let key = unquote(key)?;
+        return Some(("Setting the Pexels key".to_string(), Modification::SetPexelsKey { key: key.to_string() }));
+    }
+
+    if let Some(name) = strip_any(input, &["rename the project to", "rename project to", "rename it to", "rename to", "call it", "name it"]) {
+        let name = unquote(name)?;
+        return Some((format!("Renaming the project to \"{}\"", name), Modification::SetName { name: name.to_string() }));
+    }
+
+    if let Some(rest) = strip_any(input, &["rename"]) {
+        let (clip, name) = split_once_ci(rest, " to ")?;
+        let (old, new) = (clip_name(clip)?, unquote(name)?);
+        return Some((
+            format!("Renaming \"{}\" to \"{}\"", old, new),
+            Modification::UpdateClip { id: None, old_description: Some(old.to_string()), new_description: new.to_string() },
+        ));
+    }
+
+    if let Some(rest) = strip_any(input, &["use the", "use", "switch to the", "switch to"])
+        && rest.to_ascii_lowercase().ends_with(" theme")
+    {
+        let theme = ThemeName::parse(rest)?;
+        return Some((
+            format!("Switching to the {} theme", theme.label()),
+            Modification::SetTheme { theme: theme.label().to_string() },
+        ));
+    }
+
+    if let Some(rest) = strip_any(input, &["swap"]) {
+        let (first, second) = split_once_ci(rest, " and ").or_else(|| split_once_ci(rest, " with "))?;
+        let (clip1, clip2) = (clip_name(first)?, clip_name(second)?);
+        return Some((
+            format!("Swapping \"{}\" and \"{}\"", clip1, clip2),
+            Modification::SwapClips { clip1: clip1.to_string(), clip2: clip2.to_string() },
+        ));
+    }
+
+    if let Some(rest) = strip_any(input, &["move"]) {
+        let (clip, target) = rsplit_once_ci(rest, " to ")?;
+        let description = clip_name(clip)?;
+        let position = position(target)?;
+        return Some((
+            format!("Moving \"{}\" to {}", description, position),
+            Modification::MoveClip { description: description.to_string(), position },
+        ));
+    }
+
+    if let Some(rest) = strip_any(input, &["remove", "delete"]) {
+        let description = clip_name(rest)?;
+        return Some((
+            format!("Removing \"{}\"", description),
+            Modification::RemoveClip { id: None, description: Some(description.to_string()) },
+        ));
+    }
+
+    if let Some(rest) = strip_any(input, &["add"]) {
+        let rest = strip_any(rest, &["a clip of", "a clip showing", "a clip with", "a clip", "clip of", "clip"])
+            .unwrap_or(rest);
+        let description = unquote(rest)?;
+        return Some((
+            format!("Adding a clip: {}", description),
+            Modification::AddClip { description: description.to_string(), path: None, media_type: None },
+        ));
+    }
+
+    None
+}
+
+/// `text` after the first of `prefixes` it starts with, ignoring case;
+/// prefixes must end at a word boundary
+fn strip_any<'a>(text: &'a str, prefixes: &[&str]) -> Option<&'a str> {
+    prefixes.iter().find_map(|prefix| {
+        let head = text.get(..prefix.len())?;
+        let rest = &text[prefix.len()..];
+        (head.eq_ignore_ascii_case(prefix) && (rest.is_empty() || rest.starts_with(char::is_whitespace)))
+            .then(|| rest.trim_start())
+    })
+}
+
+fn split_once_ci<'a>(text: &'a str, separator: &str) -> Option<(&'a str, &'a str)> {
+    let at = text.to_ascii_lowercase().find(separator)?;
+    Some((&text[..at], &text[at + separator.len()..]))
+}
+
+fn rsplit_once_ci<'a>(text: &'a str, separator: &str) -> Option<(&'a str, &'a str)> {
+    let at = text.to_ascii_lowercase().rfind(separator)?;
+    Some((&text[..at], &text[at + separator.len()..]))
+}
+
+/// Text without surrounding quotes; None when nothing is left
+fn unquote(text: &str) -> Option<&str> {
+    let text = text.trim().trim_matches(['"', '\'', '“', '”']).trim();
+    (!text.is_empty()).then_some(text)
+}
+
+/// A clip reference with "the" and "clip" dropped ("the beach clip" → "beach")
+fn clip_name(text: &str) -> Option<&str> {
+    let text = text.trim();
+    let text = strip_any(text, &["the"]).unwrap_or(text);
+    let text = strip_any(text, &["clip"]).unwrap_or(text);
+    let text = match text.len().checked_sub(" clip".len()) {
+        Some(at) if text.get(at..).is_some_and(|end| end.eq_ignore_ascii_case(" clip")) => &text[..at],
+        _ => text,
+    };
+    unquote(text)
+}
+
+/// A position as `MoveClip` takes it: "first", "last" or a 1-based number
+fn position(text: &str) -> Option<String> {
+    let text = text.trim().to_ascii_lowercase();
+    let text = text.strip_prefix("the ").unwrap_or(&text);
+    match text {
+        "start" | "beginning" | "front" | "first" | "top" => Some("first".to_string()),
+        "end" | "last" | "bottom" => Some("last".to_string()),
+        _ => {
+            let number = text.strip_prefix("position ").unwrap_or(text).trim_start_matches('#');
+            number.parse::<usize>().ok().filter(|&n| n > 0).map(|n| n.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn only(input: &str) -> Modification {
+        let response = parse(input).expect("should parse");
+        assert_eq!(response.modifications.len(), 1);
+        response.modifications.into_iter().next().unwrap()
+    }
+
+    #[test]
+    fn test_parses_common_edits() {
+        assert!(matches!(only("Rename the project to \"Summer Trip\""), Modification::SetName { name } if name == "Summer Trip"));
+        assert!(matches!(
+            only("rename the beach clip to \"Sunset walk\""),
+            Modification::UpdateClip { id: None, old_description: Some(old), new_description } if old == "beach" && new_description == "Sunset walk"
+        ));
+        assert!(matches!(only("Rename clip intro to opening"), Modification::UpdateClip { old_description: Some(old), .. } if old == "intro"));
+        assert!(matches!(only("add a clip of waves at sunset"), Modification::AddClip { description, .. } if description == "waves at sunset"));
+        assert!(matches!(only("Delete the beach clip"), Modification::RemoveClip { description: Some(d), .. } if d == "beach"));
+        assert!(matches!(only("move city lights to the end"), Modification::MoveClip { description, position } if description == "city lights" && position == "last"));
+        assert!(matches!(only("move intro to position 3"), Modification::MoveClip { position, .. } if position == "3"));
+        assert!(matches!(only("swap intro and outro"), Modification::SwapClips { clip1, clip2 } if clip1 == "intro" && clip2 == "outro"));
+        assert!(matches!(only("set pexels key to abc123"), Modification::SetPexelsKey { key } if key == "abc123"));
+        assert!(matches!(only("use high contrast theme"), Modification::SetTheme { theme } if theme == "high contrast"));
+        assert!(matches!(only("Switch to the light theme"), Modification::SetTheme { theme } if theme == "light"));
+    }
+
+    #[test]
+    fn test_leaves_other_requests_alone() {
+        assert!(parse("make it more cinematic").is_none());
+        assert!(parse("remove").is_none());
+        assert!(parse("addition is fun").is_none());
+        assert!(parse("move intro somewhere").is_none());
+        assert!(parse("rename the intro").is_none());
+    }
+}
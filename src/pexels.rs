@@ -1,7 +1,44 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::project::ClipSource;
 
 const PEXELS_API_URL: &str = "https://api.pexels.com/videos/search";
+const PEXELS_PHOTO_API_URL: &str = "https://api.pexels.com/v1/search";
+
+/// Which way round search results should be
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Orientation {
+    #[default]
+    Landscape,
+    Portrait,
+    Square,
+}
+
+impl Orientation {
+    /// Orientation matching a frame size
+    pub fn of(width: u32, height: u32) -> Self {
+        match width.cmp(&height) {
+            std::cmp::Ordering::Greater => Self::Landscape,
+            std::cmp::Ordering::Less => Self::Portrait,
+            std::cmp::Ordering::Equal => Self::Square,
+        }
+    }
+    
+    /// Value of the API's `orientation` parameter
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Landscape => "landscape",
+            Self::Portrait => "portrait",
+            Self::Square => "square",
+        }
+    }
+}
 
 /// A video from Pexels
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +56,38 @@ pub struct PexelsVideo {
     pub user: String,
 }
 
+/// A photo from Pexels
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PexelsPhoto {
+    pub id: u64,
+    pub width: u32,
+    pub height: u32,
+    pub url: String,
+    /// Direct download URL (large enough for 1080p)
+    pub image_url: String,
+    /// Full-size original, for when the large rendition isn't enough
+    #[serde(default)]
+    pub original_url: String,
+    /// Short description of the photo
+    pub alt: String,
+    /// Who took it
+    pub photographer: String,
+}
+
+impl PexelsVideo {
+    /// Attribution to keep with the downloaded clip
+    pub fn source(&self) -> ClipSource {
+        ClipSource::pexels(&self.url, &self.user)
+    }
+}
+
+impl PexelsPhoto {
+    /// Attribution to keep with the downloaded still
+    pub fn source(&self) -> ClipSource {
+        ClipSource::pexels(&self.url, &self.photographer)
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct PexelsResponse {
     videos: Vec<PexelsVideoRaw>,
@@ -36,6 +105,30 @@ struct PexelsVideoRaw {
     video_files: Vec<PexelsVideoFile>,
 }
 
+#[derive(Debug, Deserialize)]
+struct PexelsPhotoResponse {
+    photos: Vec<PexelsPhotoRaw>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PexelsPhotoRaw {
+    id: u64,
+    width: u32,
+    height: u32,
+    url: String,
+    #[serde(default)]
+    alt: String,
+    photographer: String,
+    src: PexelsPhotoSrc,
+}
+
+#[derive(Debug, Deserialize)]
+struct PexelsPhotoSrc {
+    original: String,
+    /// 1880px tall, plenty for a Ken Burns pan at 1080p
+    large2x: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct PexelsUser {
     name: String,
@@ -44,41 +137,142 @@ struct PexelsUser {
 #[derive(Debug, Deserialize)]
 struct PexelsVideoFile {
     link: String,
-    quality: String,
+    /// Missing on some renditions
+    quality: Option<String>,
     width: u32,
     height: u32,
 }
 
-/// Search for videos on Pexels
-pub fn search_videos(api_key: &str, query: &str, per_page: u32) -> Result<Vec<PexelsVideo>> {
-    let client = reqwest::blocking::Client::new();
+/// Pexels searches made with one API key, remembering their results so a
+/// query repeated while the client lives doesn't use up the quota
+pub struct PexelsClient {
+    api_key: String,
+    /// Response bodies by request
+    cache: Mutex<HashMap<String, String>>,
+}
+
+impl PexelsClient {
+    pub fn new(api_key: &str) -> Self {
+        Self {
+            api_key: api_key.to_string(),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
     
-    let response = client
-        .get(PEXELS_API_URL)
-        .header("Authorization", api_key)
-        .query(&[
-            ("query", query),
-            ("per_page", &per_page.to_string()),
-            ("orientation", "landscape"),
-        ])
-        .timeout(std::time::Duration::from_secs(30))
-        .send()
-        .context("Failed to connect to Pexels API")?;
+    /// Search for videos on Pexels
+    pub fn search_videos(
+        &self,
+        query: &str,
+        per_page: u32,
+        orientation: Orientation,
+        cancel: &AtomicBool,
+    ) -> Result<Vec<PexelsVideo>, PexelsError> {
+        parse_videos(&self.search(PEXELS_API_URL, query, per_page, orientation, cancel)?)
+            .map_err(|e| PexelsError::Response(e.to_string()))
+    }
     
-    if !response.status().is_success() {
-        if response.status().as_u16() == 401 {
-            anyhow::bail!("Invalid Pexels API key");
+    /// Search for photos on Pexels
+    pub fn search_photos(
+        &self,
+        query: &str,
+        per_page: u32,
+        orientation: Orientation,
+        cancel: &AtomicBool,
+    ) -> Result<Vec<PexelsPhoto>, PexelsError> {
+        parse_photos(&self.search(PEXELS_PHOTO_API_URL, query, per_page, orientation, cancel)?)
+            .map_err(|e| PexelsError::Response(e.to_string()))
+    }
+    
+    /// Run a search and return the response body, from the cache when the
+    /// same search was made before. A rate-limited request is retried
+    /// after the wait Pexels asks for, if that's short enough and `cancel`
+    /// isn't set meanwhile.
+    fn search(
+        &self,
+        url: &str,
+        query: &str,
+        per_page: u32,
+        orientation: Orientation,
+        cancel: &AtomicBool,
+    ) -> Result<String, PexelsError> {
+        let key = format!("{}|{}|{}|{}", url, query.trim().to_lowercase(), per_page, orientation.as_str());
+        if let Some(body) = self.cached(&key) {
+            tracing::debug!("Reusing Pexels results for '{}'", query);
+            return Ok(body);
         }
-        anyhow::bail!("Pexels API error: {}", response.status());
+        
+        let client = reqwest::blocking::Client::new();
+        let mut backoff = RETRY_BACKOFF;
+        let mut retries = 0;
+        let body = loop {
+            let response = client
+                .get(url)
+                .header("Authorization", &self.api_key)
+                .query(&[
+                    ("query", query),
+                    ("per_page", &per_page.to_string()),
+                    ("orientation", orientation.as_str()),
+                ])
+                .timeout(std::time::Duration::from_secs(30))
+                .send()
+                .map_err(PexelsError::Network)?;
+            
+            let header = |name: &str| response.headers().get(name).and_then(|v| v.to_str().ok()).map(str::to_string);
+            match response.status().as_u16() {
+                401 | 403 => return Err(PexelsError::Auth),
+                429 => {
+                    let now = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map_or(0, |d| d.as_secs());
+                    let asked = retry_after(header("Retry-After").as_deref(), header("X-Ratelimit-Reset").as_deref(), now);
+                    let wait = asked.unwrap_or(backoff);
+                    if retries == RATE_LIMIT_RETRIES || wait > MAX_RATE_LIMIT_WAIT {
+                        return Err(PexelsError::RateLimited(asked));
+                    }
+                    tracing::warn!("Pexels rate limit hit, retrying '{}' in {:.0?}", query, wait);
+                    if !wait_unless_cancelled(wait, cancel) {
+                        return Err(PexelsError::Cancelled);
+                    }
+                    backoff *= 2;
+                    retries += 1;
+                }
+                _ if response.status().is_success() => {
+                    if let Some(remaining) = header("X-Ratelimit-Remaining").and_then(|r| r.parse::<u32>().ok())
+                        && remaining < 20
+                    {
+                        tracing::warn!("Only {} Pexels requests left this period", remaining);
+                    }
+                    break response.text().map_err(PexelsError::Network)?;
+                }
+                _ => return Err(PexelsError::Status(response.status())),
+            }
+        };
+        
+        self.remember(key, body.clone());
+        Ok(body)
+    }
+    
+    /// An earlier response; a poisoned cache just counts as a miss
+    fn cached(&self, key: &str) -> Option<String> {
+        self.cache.lock().ok()?.get(key).cloned()
     }
     
-    let pexels_response: PexelsResponse = response.json()
+    fn remember(&self, key: String, body: String) {
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.insert(key, body);
+        }
+    }
+}
+
+/// Turn a `videos/search` response body into videos
+fn parse_videos(body: &str) -> Result<Vec<PexelsVideo>> {
+    let pexels_response: PexelsResponse = serde_json::from_str(body)
         .context("Failed to parse Pexels response")?;
     
     let videos = pexels_response.videos.into_iter().map(|v| {
         // Find the best quality video file (prefer HD)
         let video_url = v.video_files.iter()
-            .filter(|f| f.quality == "hd" || f.quality == "sd")
+            .filter(|f| matches!(f.quality.as_deref(), Some("hd" | "sd")))
             .max_by_key(|f| f.width * f.height)
             .map(|f| f.link.clone())
             .unwrap_or_default();
@@ -98,32 +292,371 @@ pub fn search_videos(api_key: &str, query: &str, per_page: u32) -> Result<Vec<Pe
     Ok(videos)
 }
 
-/// Download a video to a local file
-pub fn download_video(video: &PexelsVideo, output_path: &std::path::Path) -> Result<()> {
-    let client = reqwest::blocking::Client::new();
+/// Why a Pexels search failed
+#[derive(Debug, thiserror::Error)]
+pub enum PexelsError {
+    /// The key is missing, wrong or revoked
+    #[error("Pexels rejected the API key")]
+    Auth,
+    /// Too many requests, even after retrying; how long until the quota
+    /// refills when Pexels said
+    #[error("Pexels' request limit is used up; try again {}", try_again(.0))]
+    RateLimited(Option<std::time::Duration>),
+    /// No answer at all (offline, DNS, timeout)
+    #[error("Failed to connect to Pexels API: {0}")]
+    Network(#[source] reqwest::Error),
+    #[error("Pexels API error: {0}")]
+    Status(reqwest::StatusCode),
+    #[error("Unexpected Pexels response: {0}")]
+    Response(String),
+    /// The cancel flag was set while waiting out the rate limit
+    #[error("Search cancelled")]
+    Cancelled,
+}
+
+fn try_again(wait: &Option<std::time::Duration>) -> String {
+    match wait {
+        Some(wait) if wait.as_secs() >= 60 => format!("in {} min", wait.as_secs().div_ceil(60)),
+        Some(wait) => format!("in {}s", wait.as_secs().max(1)),
+        None => "later".to_string(),
+    }
+}
+
+/// Retries after Pexels says "too many requests"
+const RATE_LIMIT_RETRIES: u32 = 3;
+
+/// Longest wait worth sitting through before retrying; Pexels' hourly
+/// quota can ask for far more
+const MAX_RATE_LIMIT_WAIT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// How long Pexels wants us to wait, from `Retry-After` (seconds) or else
+/// `X-Ratelimit-Reset` (when the quota refills, as a Unix time)
+fn retry_after(retry_after: Option<&str>, reset: Option<&str>, now: u64) -> Option<std::time::Duration> {
+    let seconds = |value: Option<&str>| value.and_then(|v| v.trim().parse::<u64>().ok());
+    seconds(retry_after)
+        .or_else(|| seconds(reset).map(|reset| reset.saturating_sub(now)))
+        .map(std::time::Duration::from_secs)
+}
+
+/// Turn a `v1/search` response body into photos
+fn parse_photos(body: &str) -> Result<Vec<PexelsPhoto>> {
+    let pexels_response: PexelsPhotoResponse = serde_json::from_str(body)
+        .context("Failed to parse Pexels response")?;
     
+    let photos = pexels_response.photos.into_iter().map(|p| PexelsPhoto {
+        id: p.id,
+        width: p.width,
+        height: p.height,
+        url: p.url,
+        image_url: p.src.large2x,
+        original_url: p.src.original,
+        alt: p.alt,
+        photographer: p.photographer,
+    }).collect();
+    
+    Ok(photos)
+}
+
+/// A download stopped because its cancel flag was set
+#[derive(Debug, thiserror::Error)]
+#[error("Download cancelled")]
+pub struct DownloadCancelled;
+
+/// Pexels answered a download with something other than success
+#[derive(Debug, thiserror::Error)]
+#[error("Download failed: {0}")]
+struct DownloadStatus(reqwest::StatusCode);
+
+/// Tries per file before giving up
+const DOWNLOAD_ATTEMPTS: u32 = 3;
+
+/// Wait before the first retry, doubled for each one after
+const RETRY_BACKOFF: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// The cached file for a video id, if it was fully downloaded before
+fn cached_video(dir: &Path, id: u64) -> Option<PathBuf> {
+    let path = dir.join(format!("{}.mp4", id));
+    std::fs::metadata(&path)
+        .is_ok_and(|meta| meta.len() > 0)
+        .then_some(path)
+}
+
+/// Download a video into `dir` (one file per Pexels id, shared by every
+/// project), or reuse an earlier download of the same id; returns the
+/// local file
+pub fn download_video(video: &PexelsVideo, dir: &Path, cancel: &AtomicBool) -> Result<PathBuf> {
+    if let Some(path) = cached_video(dir, video.id) {
+        tracing::info!("Reusing cached Pexels video {}: {:?}", video.id, path);
+        return Ok(path);
+    }
+    std::fs::create_dir_all(dir).context("Failed to create the media folder")?;
+    
+    let path = dir.join(format!("{}.mp4", video.id));
     tracing::info!("Downloading video from Pexels: {}", video.video_url);
+    download_with_retry(&video.video_url, &path, std::time::Duration::from_secs(300), cancel)?;
+    Ok(path)
+}
+
+/// Download a photo to a local file
+pub fn download_photo(photo: &PexelsPhoto, output_path: &Path, cancel: &AtomicBool) -> Result<()> {
+    tracing::info!("Downloading photo from Pexels: {}", photo.image_url);
+    download_with_retry(&photo.image_url, output_path, std::time::Duration::from_secs(60), cancel)
+}
+
+/// Whether trying the same download again might work: dropped connections,
+/// timeouts and server trouble, but not a missing file or a cancel
+fn is_transient(error: &anyhow::Error) -> bool {
+    if error.is::<DownloadCancelled>() {
+        return false;
+    }
+    match error.downcast_ref::<DownloadStatus>() {
+        Some(DownloadStatus(status)) => {
+            status.is_server_error() || *status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        }
+        None => true,
+    }
+}
+
+/// `download`, tried again with backoff while the failure looks transient
+fn download_with_retry(
+    url: &str,
+    output_path: &Path,
+    timeout: std::time::Duration,
+    cancel: &AtomicBool,
+) -> Result<()> {
+    let mut backoff = RETRY_BACKOFF;
+    let mut attempt = 1;
+    loop {
+        match download(url, output_path, timeout, cancel) {
+            Err(e) if attempt < DOWNLOAD_ATTEMPTS && is_transient(&e) => {
+                tracing::warn!("Download attempt {} of {} failed, retrying: {}", attempt, url, e);
+                if !wait_unless_cancelled(backoff, cancel) {
+                    return Err(DownloadCancelled.into());
+                }
+                backoff *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Sleep for `wait` in small steps so a cancel doesn't sit it out; false
+/// when `cancel` was set first
+fn wait_unless_cancelled(wait: std::time::Duration, cancel: &AtomicBool) -> bool {
+    let until = std::time::Instant::now() + wait;
+    while std::time::Instant::now() < until {
+        if cancel.load(Ordering::Relaxed) {
+            return false;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    !cancel.load(Ordering::Relaxed)
+}
+
+/// Stream `url` into `output_path`, checking `cancel` between chunks. The
+/// data goes to a `.part` file first, so a cancelled or failed download
+/// leaves nothing behind that looks complete.
+fn download(url: &str, output_path: &Path, timeout: std::time::Duration, cancel: &AtomicBool) -> Result<()> {
+    let client = reqwest::blocking::Client::new();
     
-    let response = client
-        .get(&video.video_url)
-        .timeout(std::time::Duration::from_secs(300))
+    let mut response = client
+        .get(url)
+        .timeout(timeout)
         .send()
-        .context("Failed to download video")?;
+        .context("Failed to download from Pexels")?;
     
     if !response.status().is_success() {
-        anyhow::bail!("Download failed: {}", response.status());
+        return Err(DownloadStatus(response.status()).into());
     }
     
-    let bytes = response.bytes()?;
-    std::fs::write(output_path, &bytes)?;
+    let mut partial = output_path.as_os_str().to_owned();
+    partial.push(".part");
+    let partial = PathBuf::from(partial);
+    
+    let written = (|| -> Result<usize> {
+        let mut file = std::fs::File::create(&partial)?;
+        let mut chunk = vec![0; 64 * 1024];
+        let mut written = 0;
+        loop {
+            if cancel.load(Ordering::Relaxed) {
+                return Err(DownloadCancelled.into());
+            }
+            let read = response.read(&mut chunk)?;
+            if read == 0 {
+                return Ok(written);
+            }
+            file.write_all(&chunk[..read])?;
+            written += read;
+        }
+    })();
     
-    tracing::info!("Downloaded {} bytes to {:?}", bytes.len(), output_path);
+    match written {
+        Ok(bytes) => {
+            std::fs::rename(&partial, output_path)?;
+            tracing::info!("Downloaded {} bytes to {:?}", bytes, output_path);
+            Ok(())
+        }
+        Err(e) => {
+            let _ = std::fs::remove_file(&partial);
+            Err(e)
+        }
+    }
+}
+
+/// Validate an API key by making a test request: `Ok(false)` when Pexels
+/// rejects it, an error when Pexels couldn't be asked
+pub fn validate_api_key(api_key: &str) -> Result<bool> {
+    let response = reqwest::blocking::Client::new()
+        .get(PEXELS_API_URL)
+        .header("Authorization", api_key)
+        .query(&[("query", "nature"), ("per_page", "1")])
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .context("Failed to connect to Pexels API")?;
     
-    Ok(())
+    match response.status().as_u16() {
+        401 | 403 => Ok(false),
+        _ if response.status().is_success() => Ok(true),
+        _ => anyhow::bail!("Pexels API error: {}", response.status()),
+    }
 }
 
-/// Validate an API key by making a test request
-#[allow(dead_code)]
-pub fn validate_api_key(api_key: &str) -> bool {
-    search_videos(api_key, "nature", 1).is_ok()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VIDEOS_RESPONSE: &str = r#"{
+        "page": 1,
+        "per_page": 1,
+        "videos": [{
+            "id": 1448735,
+            "width": 4096,
+            "height": 2160,
+            "duration": 32,
+            "url": "https://www.pexels.com/video/1448735/",
+            "image": "https://images.pexels.com/videos/1448735/preview.jpg",
+            "user": { "id": 574687, "name": "Ruvim Miksanskiy" },
+            "video_files": [
+                { "id": 1, "quality": "sd", "file_type": "video/mp4", "width": 640, "height": 338, "link": "https://player.vimeo.com/sd.mp4" },
+                { "id": 2, "quality": "hd", "file_type": "video/mp4", "width": 1920, "height": 1012, "link": "https://player.vimeo.com/hd.mp4" },
+                { "id": 3, "quality": null, "file_type": "video/mp4", "width": 4096, "height": 2160, "link": "https://player.vimeo.com/uhd.mp4" }
+            ]
+        }]
+    }"#;
+
+    const PHOTOS_RESPONSE: &str = r#"{
+        "page": 1,
+        "per_page": 1,
+        "total_results": 8000,
+        "photos": [{
+            "id": 2014422,
+            "width": 3024,
+            "height": 3024,
+            "url": "https://www.pexels.com/photo/2014422/",
+            "photographer": "Joey Farina",
+            "photographer_url": "https://www.pexels.com/@joey",
+            "avg_color": "#978E82",
+            "src": {
+                "original": "https://images.pexels.com/photos/2014422/original.jpeg",
+                "large2x": "https://images.pexels.com/photos/2014422/large2x.jpeg",
+                "large": "https://images.pexels.com/photos/2014422/large.jpeg",
+                "tiny": "https://images.pexels.com/photos/2014422/tiny.jpeg"
+            },
+            "liked": false,
+            "alt": "Brown rocks during golden hour"
+        }]
+    }"#;
+
+    #[test]
+    fn test_parse_videos_picks_largest_hd_file() {
+        let videos = parse_videos(VIDEOS_RESPONSE).unwrap();
+        assert_eq!(videos.len(), 1);
+        assert_eq!(videos[0].id, 1448735);
+        assert_eq!(videos[0].duration, 32);
+        assert_eq!(videos[0].user, "Ruvim Miksanskiy");
+        // The unlabelled 4K file is skipped
+        assert_eq!(videos[0].video_url, "https://player.vimeo.com/hd.mp4");
+    }
+
+    #[test]
+    fn test_only_complete_downloads_are_cached() {
+        let dir = std::env::temp_dir().join(format!("montage_pexels_cache_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("7.mp4.part"), b"half").unwrap();
+        std::fs::write(dir.join("8.mp4"), b"").unwrap();
+        std::fs::write(dir.join("9.mp4"), b"video").unwrap();
+        
+        assert_eq!(cached_video(&dir, 7), None);
+        assert_eq!(cached_video(&dir, 8), None);
+        assert_eq!(cached_video(&dir, 9), Some(dir.join("9.mp4")));
+        
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+    
+    #[test]
+    fn test_only_transient_failures_are_retried() {
+        let status = |code: u16| anyhow::Error::from(DownloadStatus(reqwest::StatusCode::from_u16(code).unwrap()));
+        assert!(is_transient(&status(503)));
+        assert!(is_transient(&status(429)));
+        assert!(!is_transient(&status(404)));
+        assert!(!is_transient(&DownloadCancelled.into()));
+        assert!(is_transient(&std::io::Error::from(std::io::ErrorKind::ConnectionReset).into()));
+    }
+    
+    #[test]
+    fn test_retry_after_reads_rate_limit_headers() {
+        let secs = std::time::Duration::from_secs;
+        assert_eq!(retry_after(Some("5"), Some("1000"), 900), Some(secs(5)));
+        assert_eq!(retry_after(None, Some("1000"), 900), Some(secs(100)));
+        assert_eq!(retry_after(None, Some("800"), 900), Some(secs(0)));
+        assert_eq!(retry_after(Some("Wed, 21 Oct 2026 07:28:00 GMT"), None, 900), None);
+        assert_eq!(retry_after(None, None, 900), None);
+        
+        let error = PexelsError::RateLimited(Some(std::time::Duration::from_secs(3600)));
+        assert!(error.to_string().ends_with("try again in 60 min"));
+    }
+    
+    #[test]
+    fn test_cache_belongs_to_its_client() {
+        let client = PexelsClient::new("key");
+        client.remember("beach".to_string(), "{}".to_string());
+        assert_eq!(client.cached("beach").as_deref(), Some("{}"));
+        assert_eq!(PexelsClient::new("key").cached("beach"), None);
+        
+        // A panic while holding the lock leaves it poisoned: a miss, not a crash
+        let _ = std::thread::scope(|scope| {
+            scope.spawn(|| {
+                let _cache = client.cache.lock().unwrap();
+                panic!("poison the cache");
+            }).join()
+        });
+        assert_eq!(client.cached("beach"), None);
+        client.remember("city".to_string(), "{}".to_string());
+    }
+    
+    #[test]
+    fn test_parse_photos() {
+        let photos = parse_photos(PHOTOS_RESPONSE).unwrap();
+        assert_eq!(photos.len(), 1);
+        let photo = &photos[0];
+        assert_eq!(photo.id, 2014422);
+        assert_eq!((photo.width, photo.height), (3024, 3024));
+        assert_eq!(photo.photographer, "Joey Farina");
+        assert_eq!(photo.alt, "Brown rocks during golden hour");
+        assert_eq!(photo.image_url, "https://images.pexels.com/photos/2014422/large2x.jpeg");
+        assert_eq!(photo.original_url, "https://images.pexels.com/photos/2014422/original.jpeg");
+    }
+
+    #[test]
+    fn test_parse_photos_without_alt_text() {
+        let body = PHOTOS_RESPONSE.replace(r#""alt": "Brown rocks during golden hour""#, r#""liked_too": true"#);
+        let photos = parse_photos(&body).unwrap();
+        assert_eq!(photos[0].alt, "");
+    }
+
+    #[test]
+    fn test_parse_error_body() {
+        assert!(parse_photos(r#"{"error": "Unauthorized"}"#).is_err());
+    }
 }
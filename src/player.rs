@@ -3,10 +3,10 @@ use gstreamer as gst;
 use gstreamer::prelude::*;
 use gstreamer_app as gst_app;
 use gstreamer_video as gst_video;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
-use crate::project::Project;
+use crate::project::{MediaType, Project};
 
 /// Frame data for display
 #[derive(Clone)]
@@ -16,6 +16,16 @@ pub struct Frame {
     pub height: u32,
 }
 
+/// Assumed until the first frame says otherwise
+const DEFAULT_FRAME_RATE: f64 = 30.0;
+
+/// A clip as the player feeds it into the pipeline
+struct PlayerClip {
+    path: PathBuf,
+    /// Seconds to hold an image on screen; None for video
+    still: Option<f64>,
+}
+
 /// Player state
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum PlayerState {
@@ -29,6 +39,8 @@ pub enum PlayerState {
 pub struct ProjectPlayer {
     /// Current frame for display
     current_frame: Arc<Mutex<Option<Frame>>>,
+    /// Frames per second of the video, as reported by the preview sink
+    frame_rate: Arc<Mutex<f64>>,
     /// GStreamer pipeline
     pipeline: Option<gst::Pipeline>,
     /// Player state
@@ -40,31 +52,75 @@ pub struct ProjectPlayer {
     /// Video dimensions
     width: u32,
     height: u32,
+    /// Media the current pipeline was built from (video clips, then audio)
+    sources: Vec<PathBuf>,
+    /// Volume of the clips' own audio under the voiceover
+    clip_audio_gain: f64,
+    /// Start over at the end of the sequence instead of stopping
+    looping: bool,
 }
 
 impl ProjectPlayer {
     pub fn new() -> Self {
         Self {
             current_frame: Arc::new(Mutex::new(None)),
+            frame_rate: Arc::new(Mutex::new(DEFAULT_FRAME_RATE)),
             pipeline: None,
             state: PlayerState::Stopped,
             duration: 0.0,
             position: 0.0,
             width: 1280,
             height: 720,
+            sources: Vec::new(),
+            clip_audio_gain: 1.0,
+            looping: false,
+        }
+    }
+    
+    /// Set how loud clip audio plays under the voiceover, live if playing
+    pub fn set_clip_audio_gain(&mut self, gain: f64) {
+        self.clip_audio_gain = gain;
+        if let Some(ref pipeline) = self.pipeline
+            && pipeline.by_name("voice_src").is_some()
+            && let Some(volume) = pipeline.by_name("clip_volume")
+        {
+            volume.set_property("volume", gain);
         }
     }
     
+    /// Media a project would feed into the player, in pipeline order
+    fn sources_for(project: &Project) -> Vec<PathBuf> {
+        project.clips
+            .iter()
+            .filter(|c| matches!(c.media_type, MediaType::Video | MediaType::Image) && c.error.is_none())
+            .map(|c| c.path.clone())
+            .chain(project.audio.as_ref().map(|a| a.path.clone()))
+            .collect()
+    }
+    
+    /// Check whether the pipeline was built from this project's current media
+    pub fn matches_project(&self, project: &Project) -> bool {
+        self.sources == Self::sources_for(project)
+    }
+    
     /// Build and load the project pipeline
     pub fn load_project(&mut self, project: &Project) -> Result<()> {
         // Clean up old pipeline
         self.stop();
+        self.sources = Self::sources_for(project);
         
-        // Get video clips
-        let video_clips: Vec<PathBuf> = project.clips
+        // Videos and stills, in timeline order; unreadable files would stall the pipeline
+        let video_clips: Vec<PlayerClip> = project.clips
             .iter()
-            .filter(|c| c.media_type == crate::project::MediaType::Video)
-            .map(|c| c.path.clone())
+            .filter(|c| c.error.is_none())
+            .filter_map(|c| match c.media_type {
+                MediaType::Video => Some(PlayerClip { path: c.path.clone(), still: None }),
+                MediaType::Image => Some(PlayerClip {
+                    path: c.path.clone(),
+                    still: Some(c.duration.unwrap_or(project.still_duration())),
+                }),
+                MediaType::Audio => None,
+            })
             .collect();
         
         if video_clips.is_empty() {
@@ -84,12 +140,14 @@ impl ProjectPlayer {
     }
     
     /// Build GStreamer pipeline for playback
-    fn build_pipeline(&mut self, video_clips: &[PathBuf], audio_track: Option<&PathBuf>) -> Result<()> {
+    fn build_pipeline(&mut self, video_clips: &[PlayerClip], audio_track: Option<&PathBuf>) -> Result<()> {
         let pipeline = gst::Pipeline::new();
         
         // For single video, simple pipeline
-        if video_clips.len() == 1 {
-            self.build_single_video_pipeline(&pipeline, &video_clips[0], audio_track)?;
+        if let [clip] = video_clips
+            && clip.still.is_none()
+        {
+            self.build_single_video_pipeline(&pipeline, &clip.path, audio_track)?;
         } else {
             // For multiple videos, use concat
             self.build_concat_pipeline(&pipeline, video_clips, audio_track)?;
@@ -145,7 +203,6 @@ impl ProjectPlayer {
         // Audio elements
         let audio_convert = gst::ElementFactory::make("audioconvert").build()?;
         let audio_resample = gst::ElementFactory::make("audioresample").build()?;
-        let audio_sink = gst::ElementFactory::make("autoaudiosink").build()?;
         
         // Add video elements
         pipeline.add_many([
@@ -154,7 +211,7 @@ impl ProjectPlayer {
         ])?;
         
         // Add audio elements  
-        pipeline.add_many([&audio_convert, &audio_resample, &audio_sink])?;
+        pipeline.add_many([&audio_convert, &audio_resample])?;
         
         // Link video chain
         gst::Element::link_many([&video_convert, &video_scale, &video_tee])?;
@@ -162,7 +219,8 @@ impl ProjectPlayer {
         preview_queue.link(preview_sink.upcast_ref::<gst::Element>())?;
         
         // Link audio chain
-        gst::Element::link_many([&audio_convert, &audio_resample, &audio_sink])?;
+        gst::Element::link_many([&audio_convert, &audio_resample])?;
+        self.add_audio_output(pipeline, &audio_resample, audio_track)?;
         
         // Handle dynamic pads from uridecodebin
         let video_convert_weak = video_convert.downgrade();
@@ -190,39 +248,7 @@ impl ProjectPlayer {
             }
         });
         
-        // If we have a separate audio track (voiceover), add it
-        if let Some(audio_path) = audio_track {
-            // TODO: Mix voiceover with video audio
-            // For now, voiceover will be used in export only
-            tracing::info!("Voiceover track: {:?} (will be used in export)", audio_path);
-        }
-        
-        // Set up frame callback
-        let frame_ref = self.current_frame.clone();
-        preview_sink.set_callbacks(
-            gst_app::AppSinkCallbacks::builder()
-                .new_sample(move |sink| {
-                    let sample = sink.pull_sample().map_err(|_| gst::FlowError::Error)?;
-                    let buffer = sample.buffer().ok_or(gst::FlowError::Error)?;
-                    let caps = sample.caps().ok_or(gst::FlowError::Error)?;
-                    
-                    let video_info = gst_video::VideoInfo::from_caps(caps)
-                        .map_err(|_| gst::FlowError::Error)?;
-                    
-                    let map = buffer.map_readable().map_err(|_| gst::FlowError::Error)?;
-                    
-                    let frame = Frame {
-                        data: map.as_slice().to_vec(),
-                        width: video_info.width(),
-                        height: video_info.height(),
-                    };
-                    
-                    *frame_ref.lock().unwrap() = Some(frame);
-                    
-                    Ok(gst::FlowSuccess::Ok)
-                })
-                .build(),
-        );
+        self.connect_preview_sink(&preview_sink);
         
         Ok(())
     }
@@ -231,8 +257,8 @@ impl ProjectPlayer {
     fn build_concat_pipeline(
         &mut self,
         pipeline: &gst::Pipeline,
-        video_clips: &[PathBuf],
-        _audio_track: Option<&PathBuf>,
+        video_clips: &[PlayerClip],
+        audio_track: Option<&PathBuf>,
     ) -> Result<()> {
         // For multiple clips, we need concat elements
         let video_concat = gst::ElementFactory::make("concat")
@@ -254,21 +280,26 @@ impl ProjectPlayer {
         
         let audio_convert = gst::ElementFactory::make("audioconvert").build()?;
         let audio_resample = gst::ElementFactory::make("audioresample").build()?;
-        let audio_sink = gst::ElementFactory::make("autoaudiosink").build()?;
         
         pipeline.add_many([
             &video_concat, &audio_concat,
             &video_convert, &video_scale, preview_sink.upcast_ref::<gst::Element>(),
-            &audio_convert, &audio_resample, &audio_sink,
+            &audio_convert, &audio_resample,
         ])?;
         
         // Link output chains
         gst::Element::link_many([&video_concat, &video_convert, &video_scale, preview_sink.upcast_ref::<gst::Element>()])?;
-        gst::Element::link_many([&audio_concat, &audio_convert, &audio_resample, &audio_sink])?;
+        gst::Element::link_many([&audio_concat, &audio_convert, &audio_resample])?;
+        self.add_audio_output(pipeline, &audio_resample, audio_track)?;
         
         // Add decoders for each clip
-        for (i, clip_path) in video_clips.iter().enumerate() {
-            let uri = format!("file://{}", clip_path.canonicalize()?.display());
+        for (i, clip) in video_clips.iter().enumerate() {
+            if let Some(seconds) = clip.still {
+                Self::add_still(pipeline, &clip.path, seconds, &video_concat, &audio_concat)?;
+                continue;
+            }
+            
+            let uri = format!("file://{}", clip.path.canonicalize()?.display());
             
             let src = gst::ElementFactory::make("uridecodebin")
                 .name(format!("src_{}", i))
@@ -277,56 +308,179 @@ impl ProjectPlayer {
             
             pipeline.add(&src)?;
             
-            let video_concat_weak = video_concat.downgrade();
+            // Claim the concat inputs now so clips play in timeline order,
+            // whichever decoder happens to find its streams first
+            let (video_sink, audio_sink) = Self::concat_inputs(&video_concat, &audio_concat)?;
+            
+            // A silent video would otherwise leave the audio concat waiting forever
             let audio_concat_weak = audio_concat.downgrade();
+            let unused_audio = audio_sink.clone();
+            src.connect_no_more_pads(move |_| {
+                if !unused_audio.is_linked()
+                    && let Some(concat) = audio_concat_weak.upgrade()
+                {
+                    concat.release_request_pad(&unused_audio);
+                }
+            });
             
             src.connect_pad_added(move |_, pad| {
                 let caps = pad.current_caps().unwrap_or_else(|| pad.query_caps(None));
                 let structure = caps.structure(0).unwrap();
                 let name = structure.name();
                 
-                if name.starts_with("video/")
-                    && let Some(concat) = video_concat_weak.upgrade()
-                    && let Some(sink_pad) = concat.request_pad_simple("sink_%u")
-                {
-                    let _ = pad.link(&sink_pad);
-                } else if name.starts_with("audio/")
-                    && let Some(concat) = audio_concat_weak.upgrade()
-                    && let Some(sink_pad) = concat.request_pad_simple("sink_%u")
-                {
-                    let _ = pad.link(&sink_pad);
+                if name.starts_with("video/") && !video_sink.is_linked() {
+                    let _ = pad.link(&video_sink);
+                } else if name.starts_with("audio/") && !audio_sink.is_linked() {
+                    let _ = pad.link(&audio_sink);
                 }
             });
         }
         
-        // Set up frame callback
-        let frame_ref = self.current_frame.clone();
+        self.connect_preview_sink(&preview_sink);
+        
+        Ok(())
+    }
+    
+    /// Feed an image into the concats as `seconds` of still frames, with
+    /// as much silence so later clips' audio stays in step
+    fn add_still(
+        pipeline: &gst::Pipeline,
+        path: &Path,
+        seconds: f64,
+        video_concat: &gst::Element,
+        audio_concat: &gst::Element,
+    ) -> Result<()> {
+        let frames = (seconds * DEFAULT_FRAME_RATE).round() as u32;
+        let video = gst::parse::bin_from_description(
+            &format!(
+                "filesrc location=\"{}\" ! decodebin ! imagefreeze num-buffers={} ! \
+                 videorate ! video/x-raw,framerate={}/1 ! videoconvert",
+                path.to_string_lossy(),
+                frames,
+                DEFAULT_FRAME_RATE as u32
+            ),
+            true,
+        )?;
+        // 10ms buffers of silence
+        let silence = gst::parse::bin_from_description(
+            &format!(
+                "audiotestsrc wave=silence samplesperbuffer=480 num-buffers={} ! \
+                 audio/x-raw,rate=48000,channels=2",
+                (seconds * 100.0).round() as u32
+            ),
+            true,
+        )?;
+        pipeline.add_many([video.upcast_ref::<gst::Element>(), silence.upcast_ref()])?;
+        
+        let (video_sink, audio_sink) = Self::concat_inputs(video_concat, audio_concat)?;
+        for (bin, sink) in [(&video, video_sink), (&silence, audio_sink)] {
+            let src = bin.static_pad("src").ok_or_else(|| anyhow::anyhow!("Still has no output"))?;
+            src.link(&sink)?;
+        }
+        Ok(())
+    }
+    
+    /// Next video and audio inputs on the concats, claimed together
+    fn concat_inputs(video_concat: &gst::Element, audio_concat: &gst::Element) -> Result<(gst::Pad, gst::Pad)> {
+        let request = |concat: &gst::Element| {
+            concat
+                .request_pad_simple("sink_%u")
+                .ok_or_else(|| anyhow::anyhow!("Concat refused another input"))
+        };
+        Ok((request(video_concat)?, request(audio_concat)?))
+    }
+    
+    /// Send the clips' audio (coming out of `clip_audio`) to the speakers,
+    /// mixed with the voiceover when there is one. Whichever runs longer
+    /// keeps playing on its own once the other ends.
+    fn add_audio_output(
+        &self,
+        pipeline: &gst::Pipeline,
+        clip_audio: &gst::Element,
+        voiceover: Option<&PathBuf>,
+    ) -> Result<()> {
+        let clip_volume = gst::ElementFactory::make("volume")
+            .name("clip_volume")
+            .property("volume", if voiceover.is_some() { self.clip_audio_gain } else { 1.0 })
+            .build()?;
+        let mixer = gst::ElementFactory::make("audiomixer").build()?;
+        let mix_convert = gst::ElementFactory::make("audioconvert").build()?;
+        let audio_sink = gst::ElementFactory::make("autoaudiosink").build()?;
+        
+        pipeline.add_many([&clip_volume, &mixer, &mix_convert, &audio_sink])?;
+        gst::Element::link_many([clip_audio, &clip_volume, &mixer, &mix_convert, &audio_sink])?;
+        
+        let Some(voiceover) = voiceover else {
+            return Ok(());
+        };
+        
+        let voice_src = gst::ElementFactory::make("uridecodebin")
+            .name("voice_src")
+            .property("uri", format!("file://{}", voiceover.canonicalize()?.display()))
+            .build()?;
+        let voice_convert = gst::ElementFactory::make("audioconvert").build()?;
+        let voice_resample = gst::ElementFactory::make("audioresample").build()?;
+        
+        pipeline.add_many([&voice_src, &voice_convert, &voice_resample])?;
+        gst::Element::link_many([&voice_convert, &voice_resample, &mixer])?;
+        
+        let voice_convert_weak = voice_convert.downgrade();
+        voice_src.connect_pad_added(move |_, pad| {
+            let caps = pad.current_caps().unwrap_or_else(|| pad.query_caps(None));
+            let is_audio = caps.structure(0).is_some_and(|s| s.name().starts_with("audio/"));
+            
+            if is_audio
+                && let Some(convert) = voice_convert_weak.upgrade()
+                && let Some(sink_pad) = convert.static_pad("sink")
+                && !sink_pad.is_linked()
+            {
+                let _ = pad.link(&sink_pad);
+            }
+        });
+        
+        tracing::info!("Voiceover mixed into preview: {:?}", voiceover);
+        Ok(())
+    }
+    
+    /// Keep the latest frame (and the frame rate) from the preview sink.
+    /// Preroll counts too, so a paused seek still updates the picture.
+    fn connect_preview_sink(&self, preview_sink: &gst_app::AppSink) {
+        let store = {
+            let frame_ref = self.current_frame.clone();
+            let frame_rate_ref = self.frame_rate.clone();
+            move |sample: gst::Sample| -> Result<gst::FlowSuccess, gst::FlowError> {
+                let buffer = sample.buffer().ok_or(gst::FlowError::Error)?;
+                let caps = sample.caps().ok_or(gst::FlowError::Error)?;
+                
+                let video_info = gst_video::VideoInfo::from_caps(caps)
+                    .map_err(|_| gst::FlowError::Error)?;
+                
+                let map = buffer.map_readable().map_err(|_| gst::FlowError::Error)?;
+                
+                let frame = Frame {
+                    data: map.as_slice().to_vec(),
+                    width: video_info.width(),
+                    height: video_info.height(),
+                };
+                
+                *frame_ref.lock().unwrap() = Some(frame);
+                
+                let fps = video_info.fps();
+                if fps.numer() > 0 && fps.denom() > 0 {
+                    *frame_rate_ref.lock().unwrap() = fps.numer() as f64 / fps.denom() as f64;
+                }
+                
+                Ok(gst::FlowSuccess::Ok)
+            }
+        };
+        let store_preroll = store.clone();
+        
         preview_sink.set_callbacks(
             gst_app::AppSinkCallbacks::builder()
-                .new_sample(move |sink| {
-                    let sample = sink.pull_sample().map_err(|_| gst::FlowError::Error)?;
-                    let buffer = sample.buffer().ok_or(gst::FlowError::Error)?;
-                    let caps = sample.caps().ok_or(gst::FlowError::Error)?;
-                    
-                    let video_info = gst_video::VideoInfo::from_caps(caps)
-                        .map_err(|_| gst::FlowError::Error)?;
-                    
-                    let map = buffer.map_readable().map_err(|_| gst::FlowError::Error)?;
-                    
-                    let frame = Frame {
-                        data: map.as_slice().to_vec(),
-                        width: video_info.width(),
-                        height: video_info.height(),
-                    };
-                    
-                    *frame_ref.lock().unwrap() = Some(frame);
-                    
-                    Ok(gst::FlowSuccess::Ok)
-                })
+                .new_sample(move |sink| store(sink.pull_sample().map_err(|_| gst::FlowError::Error)?))
+                .new_preroll(move |sink| store_preroll(sink.pull_preroll().map_err(|_| gst::FlowError::Error)?))
                 .build(),
         );
-        
-        Ok(())
     }
     
     /// Play
@@ -355,6 +509,51 @@ impl ProjectPlayer {
         *self.current_frame.lock().unwrap() = None;
     }
     
+    /// Pause and go back to the start
+    pub fn rewind(&mut self) {
+        self.pause();
+        self.seek(0.0);
+    }
+    
+    /// Whether playback starts over at the end
+    pub fn is_looping(&self) -> bool {
+        self.looping
+    }
+    
+    /// Play the sequence on repeat, or stop at its end
+    pub fn set_looping(&mut self, looping: bool) {
+        self.looping = looping;
+    }
+    
+    /// Handle pending bus messages; once the sequence ends, starts over
+    /// when looping and otherwise pauses at the start
+    pub fn poll_bus(&mut self) {
+        let Some(pipeline) = self.pipeline.clone() else {
+            return;
+        };
+        let Some(bus) = pipeline.bus() else {
+            return;
+        };
+        
+        let mut reached_end = false;
+        while let Some(msg) = bus.pop() {
+            match msg.view() {
+                gst::MessageView::Eos(..) => reached_end = true,
+                gst::MessageView::Error(err) => {
+                    tracing::error!("Player error: {}", err.error());
+                }
+                _ => {}
+            }
+        }
+        
+        if reached_end && self.looping && self.state == PlayerState::Playing {
+            // A flushing seek restarts the pipeline without leaving Playing
+            self.seek(0.0);
+        } else if reached_end {
+            self.rewind();
+        }
+    }
+    
     /// Seek to position (0.0 to 1.0)
     pub fn seek(&self, position: f64) {
         if let Some(ref pipeline) = self.pipeline {
@@ -366,6 +565,29 @@ impl ProjectPlayer {
         }
     }
     
+    /// Move by whole frames (negative goes back), landing exactly on the frame
+    pub fn step_frame(&self, delta: i32) {
+        self.step_seconds(delta as f64 / self.frame_rate());
+    }
+    
+    /// Move by a number of seconds, landing exactly there rather than on a keyframe
+    pub fn step_seconds(&self, seconds: f64) {
+        if let Some(ref pipeline) = self.pipeline
+            && self.duration > 0.0
+        {
+            let target = (self.get_position() * self.duration + seconds).clamp(0.0, self.duration);
+            let _ = pipeline.seek_simple(
+                gst::SeekFlags::FLUSH | gst::SeekFlags::ACCURATE,
+                gst::ClockTime::from_nseconds((target * 1_000_000_000.0) as u64),
+            );
+        }
+    }
+    
+    /// Frames per second of the video being played
+    pub fn frame_rate(&self) -> f64 {
+        *self.frame_rate.lock().unwrap()
+    }
+    
     /// Get current position (0.0 to 1.0)
     pub fn get_position(&self) -> f64 {
         if let Some(ref pipeline) = self.pipeline
@@ -1,7 +1,10 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
+use crate::transcription::Transcript;
+
 /// Montage project file format
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct Project {
@@ -21,8 +24,97 @@ pub struct Project {
     #[serde(default)]
     pub clips: Vec<Clip>,
     
+    /// Counter behind clip IDs, so each new clip gets the next number
+    #[serde(default)]
+    pub next_clip_id: u64,
+    
+    /// Timeline markers, ordered by time
+    #[serde(default)]
+    pub markers: Vec<Marker>,
+    
+    /// Counter behind marker IDs, like `next_clip_id`
+    #[serde(default)]
+    pub next_marker_id: u64,
+    
     /// Timeline state
     pub timeline: TimelineState,
+    
+    /// Last transcript of the project audio (cached to avoid re-running whisper)
+    #[serde(default)]
+    pub transcript: Option<Transcript>,
+    
+    /// Audio file the cached transcript was made from
+    #[serde(default)]
+    pub transcript_source: Option<TranscriptSource>,
+    
+    /// Recent exchanges with the agent, oldest first
+    #[serde(default)]
+    pub conversation: Vec<ConversationTurn>,
+    
+    /// Export dialog choices from the last export of this project
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub export: Option<ExportPreferences>,
+}
+
+/// Export dialog choices, kept as the labels the dialog shows so the file
+/// stays readable; anything missing or unknown falls back to the default
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ExportPreferences {
+    pub preset: Option<String>,
+    pub format: Option<String>,
+    pub resolution: Option<String>,
+    pub quality: Option<String>,
+    pub aspect_mode: Option<String>,
+    pub subtitles: Option<String>,
+    pub transition: Option<String>,
+    pub encoder: Option<String>,
+    pub normalize_audio: Option<bool>,
+    /// Clip audio volume under the voiceover (0 mutes it)
+    pub clip_audio_gain: Option<f64>,
+    pub chapters: Option<bool>,
+}
+
+/// One prompt and the agent's reply
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ConversationTurn {
+    /// What the user typed
+    pub user: String,
+    
+    /// What the agent answered
+    pub agent: String,
+}
+
+/// Identifies the audio behind a cached transcript, so the cache can be
+/// dropped when the file is swapped or edited
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TranscriptSource {
+    /// Audio file that was transcribed
+    pub path: PathBuf,
+    
+    /// File size in bytes when transcribed
+    pub size: u64,
+    
+    /// Modification time when transcribed (seconds since the epoch)
+    pub modified_secs: u64,
+}
+
+impl TranscriptSource {
+    /// Fingerprint the file at `path` as it is now
+    pub fn of(path: &Path) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        let modified_secs = metadata
+            .modified()
+            .ok()?
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()?
+            .as_secs();
+        Some(Self {
+            path: path.to_path_buf(),
+            size: metadata.len(),
+            modified_secs,
+        })
+    }
 }
 
 /// A media clip with description and timing
@@ -47,8 +139,165 @@ pub struct Clip {
     /// Duration of the clip (seconds)
     #[serde(default)]
     pub duration: Option<f64>,
+    
+    /// Where playback starts inside the source file (seconds)
+    #[serde(default)]
+    pub source_in: f64,
+    
+    /// Color or tag for sorting clips in the panel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<ClipLabel>,
+    
+    /// Why the file can't be used, once probing failed (not saved, so a
+    /// fixed file is retried on the next load)
+    #[serde(skip)]
+    pub error: Option<String>,
+    
+    /// Who made the footage and where it came from, for downloaded clips
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<ClipSource>,
+}
+
+/// Attribution for a clip downloaded from a stock footage site
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct ClipSource {
+    /// Site it was downloaded from (e.g., "Pexels")
+    pub provider: String,
+    /// Page of the original on that site
+    pub source_url: String,
+    pub author: String,
+    /// The terms it's used under, in a few words
+    pub license: String,
+}
+
+impl ClipSource {
+    /// A video or photo from Pexels
+    pub fn pexels(source_url: &str, author: &str) -> Self {
+        Self {
+            provider: "Pexels".to_string(),
+            source_url: source_url.to_string(),
+            author: author.to_string(),
+            license: "Pexels License (free to use, attribution appreciated)".to_string(),
+        }
+    }
+    
+    /// "Author on Provider"
+    pub fn credit(&self) -> String {
+        match (self.author.is_empty(), self.provider.is_empty()) {
+            (false, false) => format!("{} on {}", self.author, self.provider),
+            (false, true) => self.author.clone(),
+            (true, _) => self.provider.clone(),
+        }
+    }
+}
+
+/// A color label, or any tag the user picks (e.g. "b-roll")
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ClipLabel {
+    Red,
+    Yellow,
+    Green,
+    Blue,
+    Purple,
+    #[serde(untagged)]
+    Tag(String),
+}
+
+impl ClipLabel {
+    /// The color labels, in swatch order
+    pub const COLORS: [ClipLabel; 5] = [Self::Red, Self::Yellow, Self::Green, Self::Blue, Self::Purple];
+    
+    /// A color name (any case) or else a tag; None for blank text
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        if text.is_empty() {
+            return None;
+        }
+        Some(match text.to_ascii_lowercase().as_str() {
+            "red" => Self::Red,
+            "yellow" => Self::Yellow,
+            "green" => Self::Green,
+            "blue" => Self::Blue,
+            "purple" => Self::Purple,
+            _ => Self::Tag(text.to_string()),
+        })
+    }
+    
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Red => "red",
+            Self::Yellow => "yellow",
+            Self::Green => "green",
+            Self::Blue => "blue",
+            Self::Purple => "purple",
+            Self::Tag(tag) => tag,
+        }
+    }
+    
+    /// Swatch color (tags share a neutral gray)
+    pub fn rgb(&self) -> u32 {
+        match self {
+            Self::Red => 0xef5350,
+            Self::Yellow => 0xffca28,
+            Self::Green => 0x66bb6a,
+            Self::Blue => 0x42a5f5,
+            Self::Purple => 0xab47bc,
+            Self::Tag(_) => 0x9e9e9e,
+        }
+    }
+}
+
+/// A named point on the timeline
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Marker {
+    /// Tick color as "#rrggbb" (default orange when unset)
+    #[serde(default)]
+    pub color: Option<String>,
+    
+    /// Unique marker ID
+    pub id: String,
+    
+    /// What happens at this point (e.g., "cut here")
+    #[serde(alias = "description")]
+    pub label: String,
+    
+    /// Position on the timeline (seconds)
+    pub time_seconds: f64,
+}
+
+impl Clip {
+    /// Seconds a newly added image stays on screen
+    pub const DEFAULT_STILL_DURATION: f64 = 5.0;
+}
+
+impl Marker {
+    /// Parse `color` into a 0xrrggbb value, if it's a valid hex color
+    pub fn rgb(&self) -> Option<u32> {
+        let hex = self.color.as_deref()?.trim_start_matches('#');
+        if hex.len() != 6 {
+            return None;
+        }
+        u32::from_str_radix(hex, 16).ok()
+    }
+}
+
+/// Which clip a description most likely refers to
+#[derive(Debug, PartialEq)]
+pub enum ClipMatch {
+    Found(usize),
+    /// Several clips fit about equally well
+    Ambiguous(Vec<usize>),
+    NotFound,
 }
 
+/// Lowest fuzzy score that still counts as a match
+const FUZZY_THRESHOLD: f64 = 0.7;
+
+/// Fuzzy scores this close to the best are too close to call
+const FUZZY_MARGIN: f64 = 0.05;
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum MediaType {
@@ -66,13 +315,25 @@ pub struct ProjectMetadata {
     #[serde(default)]
     pub description: String,
     
-    /// Creation timestamp (ISO 8601)
+    /// Creation timestamp (RFC 3339, e.g. "2024-06-19T18:40:00Z")
     #[serde(default)]
     pub created_at: Option<String>,
     
-    /// Last modified timestamp (ISO 8601)
+    /// Last modified timestamp (RFC 3339)
     #[serde(default)]
     pub modified_at: Option<String>,
+    
+    /// Seconds a newly added image stays on screen (defaults to `Clip::DEFAULT_STILL_DURATION`)
+    #[serde(default)]
+    pub still_duration: Option<f64>,
+}
+
+impl ProjectMetadata {
+    /// When the project was last written, if the timestamp is readable
+    pub fn modified(&self) -> Option<DateTime<Utc>> {
+        let modified = DateTime::parse_from_rfc3339(self.modified_at.as_deref()?).ok()?;
+        Some(modified.with_timezone(&Utc))
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -87,6 +348,10 @@ pub struct AudioTrack {
     /// Sample rate (cached)
     #[serde(default)]
     pub sample_rate: Option<u32>,
+    
+    /// Stretches left out on export, as (start, end) seconds in order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cuts: Vec<(f64, f64)>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -120,11 +385,20 @@ fn default_zoom() -> f64 {
 
 impl Project {
     /// Current project format version
-    pub const CURRENT_VERSION: u32 = 1;
+    ///
+    /// 1: absolute media paths
+    /// 2: media paths relative to the project file when inside its folder
+    pub const CURRENT_VERSION: u32 = 2;
+    
+    /// Older agent exchanges are forgotten beyond this many
+    pub const MAX_CONVERSATION_TURNS: usize = 20;
     
     /// File extension for project files
     pub const EXTENSION: &'static str = "montage";
     
+    /// Shortest half a split may leave (about a frame)
+    pub const MIN_SPLIT_SECONDS: f64 = 0.04;
+    
     /// Create a new empty project
     pub fn new(name: impl Into<String>) -> Self {
         let now = chrono_now();
@@ -135,45 +409,163 @@ impl Project {
                 description: String::new(),
                 created_at: Some(now.clone()),
                 modified_at: Some(now),
+                still_duration: None,
             },
             audio: None,
             video: None,
             clips: Vec::new(),
+            next_clip_id: 0,
+            markers: Vec::new(),
+            next_marker_id: 0,
             timeline: TimelineState::default(),
+            transcript: None,
+            transcript_source: None,
+            conversation: Vec::new(),
+            export: None,
         }
     }
     
-    /// Load a project from a file
+    /// Load a project from a file, resolving relative media paths against its folder
     pub fn load(path: impl AsRef<Path>) -> Result<Self> {
         let path = path.as_ref();
         let content = std::fs::read_to_string(path)
             .context("Failed to read project file")?;
         
-        let project: Self = serde_json::from_str(&content)
+        let mut project: Self = serde_json::from_str(&content)
             .context("Failed to parse project file")?;
         
+        if project.version > Self::CURRENT_VERSION {
+            anyhow::bail!(
+                "Project was saved by a newer Montage (format {}, this version reads up to {})",
+                project.version,
+                Self::CURRENT_VERSION
+            );
+        }
+        
+        // Version 1 paths are all absolute, so resolving leaves them untouched
+        if let Some(dir) = project_dir(path) {
+            project.for_each_media_path(|media| {
+                if media.is_relative() {
+                    *media = dir.join(&*media);
+                }
+            });
+        }
+        project.version = Self::CURRENT_VERSION;
+        project.dedupe_clip_ids();
+        project.dedupe_marker_ids();
+        migrate_timestamp(&mut project.metadata.created_at);
+        migrate_timestamp(&mut project.metadata.modified_at);
+        
         Ok(project)
     }
     
     /// Save the project to a file
+    ///
+    /// Media inside the project's folder is stored relative to it, so the
+    /// folder can be moved or copied to another machine.
     pub fn save(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
         self.metadata.modified_at = Some(chrono_now());
+        self.version = Self::CURRENT_VERSION;
+        
+        let mut stored = self.clone();
+        if let Some(dir) = project_dir(path) {
+            let canonical_dir = dir.canonicalize().ok();
+            stored.for_each_media_path(|media| {
+                let relative = media.strip_prefix(&dir).ok().map(Path::to_path_buf).or_else(|| {
+                    // The same folder reached through a symlink, like /tmp on macOS
+                    let media = media.canonicalize().ok()?;
+                    media.strip_prefix(canonical_dir.as_ref()?).ok().map(Path::to_path_buf)
+                });
+                if let Some(relative) = relative {
+                    *media = relative;
+                }
+            });
+        }
         
-        let content = serde_json::to_string_pretty(self)
+        let content = serde_json::to_string_pretty(&stored)
             .context("Failed to serialize project")?;
         
-        std::fs::write(path, content)
+        // Write beside the target, then swap it in, so a crash mid-write
+        // leaves the previous save intact
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+        std::fs::write(&temp, content)
             .context("Failed to write project file")?;
+        std::fs::rename(&temp, path)
+            .context("Failed to replace project file")?;
         
         Ok(())
     }
     
+    /// Copy media that lives outside the project's folder into its `media`
+    /// folder and point the project at the copies, so the next save stores
+    /// them as relative paths; returns how many files were copied
+    pub fn copy_media_beside(&mut self, project_path: &Path) -> Result<usize> {
+        let dir = project_dir(project_path).context("Project has no folder")?;
+        let media_dir = dir.join("media");
+        
+        let mut outside = Vec::new();
+        self.for_each_media_path(|media| {
+            if !media.starts_with(&dir) && media.is_file() && !outside.contains(media) {
+                outside.push(media.clone());
+            }
+        });
+        if outside.is_empty() {
+            return Ok(0);
+        }
+        std::fs::create_dir_all(&media_dir).context("Failed to create the media folder")?;
+        
+        let mut copies = std::collections::HashMap::new();
+        for source in outside {
+            let target = free_name(&media_dir, &source);
+            // A hard link is instant and takes no space when on the same disk
+            if std::fs::hard_link(&source, &target).is_err() {
+                std::fs::copy(&source, &target)
+                    .with_context(|| format!("Failed to copy {}", source.display()))?;
+            }
+            copies.insert(source, target);
+        }
+        
+        let copied = copies.len();
+        self.for_each_media_path(|media| {
+            if let Some(copy) = copies.get(media) {
+                *media = copy.clone();
+            }
+        });
+        Ok(copied)
+    }
+    
+    /// Visit every media path the project references
+    fn for_each_media_path(&mut self, mut f: impl FnMut(&mut PathBuf)) {
+        if let Some(audio) = &mut self.audio {
+            f(&mut audio.path);
+        }
+        if let Some(video) = &mut self.video {
+            f(&mut video.path);
+        }
+        for clip in &mut self.clips {
+            f(&mut clip.path);
+        }
+        if let Some(source) = &mut self.transcript_source {
+            f(&mut source.path);
+        }
+    }
+    
     /// Set the audio track
+    ///
+    /// Cuts survive reloading the same file; a different file starts uncut.
     pub fn set_audio(&mut self, path: PathBuf, duration: f64, sample_rate: u32) {
+        let cuts = self.audio
+            .take()
+            .filter(|a| a.path == path)
+            .map(|a| a.cuts)
+            .unwrap_or_default();
         self.audio = Some(AudioTrack {
             path,
             duration: Some(duration),
             sample_rate: Some(sample_rate),
+            cuts,
         });
     }
     
@@ -187,18 +579,16 @@ impl Project {
         });
     }
     
+    /// Remember an exchange with the agent, dropping the oldest past the limit
+    pub fn push_conversation(&mut self, user: String, agent: String) {
+        self.conversation.push(ConversationTurn { user, agent });
+        let excess = self.conversation.len().saturating_sub(Self::MAX_CONVERSATION_TURNS);
+        self.conversation.drain(..excess);
+    }
+    
     /// Add a clip to the project
     pub fn add_clip(&mut self, description: String, path: PathBuf) -> &Clip {
-        use std::time::{SystemTime, UNIX_EPOCH};
-        
-        // Generate a simple unique ID
-        let id = format!(
-            "clip_{}",
-            SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_millis()
-        );
+        let id = self.new_clip_id();
         
         // Detect media type from extension
         let media_type = match path.extension().and_then(|e| e.to_str()) {
@@ -207,11 +597,20 @@ impl Project {
             _ => MediaType::Video, // Default to video
         };
         
-        // Calculate start time (end of last clip)
-        let start_time = self.clips
-            .last()
-            .map(|c| c.start_time + c.duration.unwrap_or(0.0))
-            .unwrap_or(0.0);
+        // Calculate start time (end of last clip); audio plays under the
+        // clips from the start instead of taking a slot of its own
+        let start_time = match media_type {
+            MediaType::Audio => 0.0,
+            _ => self.clips
+                .iter()
+                .rev()
+                .find(|c| c.media_type != MediaType::Audio)
+                .map(|c| c.start_time + c.duration.unwrap_or(0.0))
+                .unwrap_or(0.0),
+        };
+        
+        // Stills have no length of their own; video/audio fill it in when loaded
+        let duration = (media_type == MediaType::Image).then_some(self.still_duration());
         
         self.clips.push(Clip {
             id,
@@ -219,33 +618,540 @@ impl Project {
             path,
             media_type,
             start_time,
-            duration: None, // Will be filled when media is loaded
+            duration,
+            source_in: 0.0,
+            label: None,
+            error: None,
+            source: None,
         });
         
         self.clips.last().unwrap()
     }
     
+    /// Move several clips, keeping their relative order, into the gap
+    /// before `slot` (0 = front, `clips.len()` = end)
+    pub fn move_clips(&mut self, indices: &[usize], slot: usize) {
+        let mut indices: Vec<usize> = indices.iter().copied().filter(|&i| i < self.clips.len()).collect();
+        indices.sort_unstable();
+        indices.dedup();
+        
+        // Removing clips ahead of the gap shifts it left
+        let slot = slot.min(self.clips.len());
+        let slot = slot - indices.iter().filter(|&&i| i < slot).count();
+        let mut block = Vec::with_capacity(indices.len());
+        for &i in indices.iter().rev() {
+            block.push(self.clips.remove(i));
+        }
+        block.reverse();
+        self.clips.splice(slot..slot, block);
+        self.update_start_times();
+    }
+    
+    /// Take out the clip at `index`, pulling the ones after it forward
+    pub fn remove_clip(&mut self, index: usize) -> Clip {
+        let clip = self.clips.remove(index);
+        self.update_start_times();
+        clip
+    }
+    
+    /// Take out every clip whose ID is listed, leaving no gaps
+    pub fn remove_clips(&mut self, ids: &[String]) {
+        self.clips.retain(|c| !ids.contains(&c.id));
+        self.update_start_times();
+    }
+    
+    /// Move the clip at `from` so it ends up at index `to`
+    pub fn move_clip(&mut self, from: usize, to: usize) {
+        let clip = self.clips.remove(from);
+        self.clips.insert(to.min(self.clips.len()), clip);
+        self.update_start_times();
+    }
+    
+    /// Trade the places of two clips
+    pub fn swap_clips(&mut self, a: usize, b: usize) {
+        self.clips.swap(a, b);
+        self.update_start_times();
+    }
+    
+    /// Record a clip's probed length and restack the clips after it
+    pub fn set_clip_duration(&mut self, id: &str, seconds: f64) {
+        let Some(clip) = self.clips.iter_mut().find(|c| c.id == id) else {
+            return;
+        };
+        clip.duration = Some(seconds);
+        clip.error = None;
+        self.update_start_times();
+    }
+    
+    /// Mark a clip as unusable, saying why
+    pub fn set_clip_error(&mut self, id: &str, error: String) {
+        if let Some(clip) = self.clips.iter_mut().find(|c| c.id == id) {
+            clip.error = Some(error);
+        }
+    }
+    
+    /// Record where a downloaded clip came from
+    pub fn set_clip_source(&mut self, id: &str, source: ClipSource) {
+        if let Some(clip) = self.clips.iter_mut().find(|c| c.id == id) {
+            clip.source = Some(source);
+        }
+    }
+    
+    /// Plain-text credits for every downloaded clip, each source listed
+    /// once; None when no clip has a source
+    pub fn credits(&self) -> Option<String> {
+        let mut seen = std::collections::HashSet::new();
+        let entries: Vec<String> = self
+            .clips
+            .iter()
+            .filter_map(|clip| clip.source.as_ref().map(|source| (clip, source)))
+            .filter(|(_, source)| seen.insert(&source.source_url))
+            .map(|(clip, source)| {
+                let mut entry = format!("{}\n  {}", clip.description, source.credit());
+                for detail in [&source.source_url, &source.license] {
+                    if !detail.is_empty() {
+                        entry.push_str(&format!("\n  {}", detail));
+                    }
+                }
+                entry
+            })
+            .collect();
+        if entries.is_empty() {
+            return None;
+        }
+        Some(format!("Credits for {}\n\n{}\n", self.metadata.name, entries.join("\n\n")))
+    }
+    
+    /// Video and image clips whose files couldn't be read
+    pub fn broken_clips(&self) -> impl Iterator<Item = &Clip> {
+        self.clips.iter().filter(|c| c.error.is_some() && c.media_type != MediaType::Audio)
+    }
+    
+    /// The next unused clip ID ("clip_1", "clip_2", ...)
+    fn new_clip_id(&mut self) -> String {
+        loop {
+            self.next_clip_id += 1;
+            let id = format!("clip_{}", self.next_clip_id);
+            // Clips from older projects have their own IDs; skip any that match
+            if !self.clips.iter().any(|c| c.id == id) {
+                return id;
+            }
+        }
+    }
+    
+    /// Give fresh IDs to clips sharing one with an earlier clip (projects
+    /// saved when IDs came from the clock can have them)
+    fn dedupe_clip_ids(&mut self) {
+        let mut seen = std::collections::HashSet::new();
+        let mut duplicates = Vec::new();
+        for (index, clip) in self.clips.iter().enumerate() {
+            if !seen.insert(clip.id.clone()) {
+                duplicates.push(index);
+            }
+        }
+        for &index in &duplicates {
+            let id = self.new_clip_id();
+            tracing::warn!("Clip '{}' shared the ID {}; it is now {}", self.clips[index].description, self.clips[index].id, id);
+            self.clips[index].id = id;
+        }
+    }
+    
+    /// Copy the clip at `index` (trim and label included) right after it,
+    /// under a new ID and with " (copy)" after its description
+    pub fn duplicate_clip(&mut self, index: usize) -> &Clip {
+        let mut copy = self.clips[index].clone();
+        copy.id = self.new_clip_id();
+        copy.description.push_str(" (copy)");
+        self.clips.insert(index + 1, copy);
+        self.update_start_times();
+        &self.clips[index + 1]
+    }
+    
+    /// Cut the clip at `index` in two at `time` (timeline seconds), the
+    /// second half picking up where the first stops in the source file.
+    /// Returns the second half's index.
+    pub fn split_clip(&mut self, index: usize, time: f64) -> Result<usize> {
+        let clip = &self.clips[index];
+        let Some(duration) = clip.duration else {
+            anyhow::bail!("'{}' has no known length yet", clip.description);
+        };
+        let offset = time - clip.start_time;
+        if offset < Self::MIN_SPLIT_SECONDS || offset > duration - Self::MIN_SPLIT_SECONDS {
+            anyhow::bail!("{:.1}s isn't inside '{}'", time, clip.description);
+        }
+        
+        let mut second = clip.clone();
+        second.id = self.new_clip_id();
+        second.duration = Some(duration - offset);
+        // A still shows the same picture either way
+        if second.media_type != MediaType::Image {
+            second.source_in += offset;
+        }
+        self.clips[index].duration = Some(offset);
+        self.clips.insert(index + 1, second);
+        self.update_start_times();
+        Ok(index + 1)
+    }
+    
+    /// The clip on screen at `time` (timeline seconds)
+    pub fn clip_at(&self, time: f64) -> Option<usize> {
+        self.clips.iter().position(|c| {
+            c.media_type != MediaType::Audio
+                && c.duration.is_some_and(|d| time >= c.start_time && time < c.start_time + d)
+        })
+    }
+    
+    /// Lay clips end to end; a clip of unknown length takes no time, and
+    /// audio starts with the timeline rather than taking a slot
+    pub fn update_start_times(&mut self) {
+        let mut time = 0.0;
+        for clip in &mut self.clips {
+            if clip.media_type == MediaType::Audio {
+                clip.start_time = 0.0;
+                continue;
+            }
+            clip.start_time = time;
+            time += clip.duration.unwrap_or(0.0);
+        }
+    }
+    
+    /// Find a clip by exact ID, or else by description: an exact match
+    /// wins, then a position ("clip 3"), whole words, a substring, and
+    /// finally the closest fuzzy match
+    pub fn find_clip(&self, id: Option<&str>, description: Option<&str>) -> ClipMatch {
+        if let Some(id) = id {
+            return match self.clips.iter().position(|c| c.id == id) {
+                Some(index) => ClipMatch::Found(index),
+                None => ClipMatch::NotFound,
+            };
+        }
+        let Some(query) = description.map(|d| d.trim().to_lowercase()).filter(|d| !d.is_empty()) else {
+            return ClipMatch::NotFound;
+        };
+        let descriptions: Vec<String> = self.clips.iter().map(|c| c.description.to_lowercase()).collect();
+        
+        if let Some(index) = descriptions.iter().position(|d| *d == query) {
+            return ClipMatch::Found(index);
+        }
+        if let Some(number) = clip_number(&query) {
+            return match number.checked_sub(1).filter(|&i| i < self.clips.len()) {
+                Some(index) => ClipMatch::Found(index),
+                None => ClipMatch::NotFound,
+            };
+        }
+        // "intro" means "intro music" before it means "introduction"
+        let mut containing: Vec<usize> = (0..descriptions.len())
+            .filter(|&i| contains_words(&descriptions[i], &query))
+            .collect();
+        if containing.is_empty() {
+            containing = (0..descriptions.len())
+                .filter(|&i| descriptions[i].contains(&query))
+                .collect();
+        }
+        match containing.as_slice() {
+            [] => {}
+            [index] => return ClipMatch::Found(*index),
+            _ => return ClipMatch::Ambiguous(containing),
+        }
+        
+        let scores: Vec<f64> = descriptions.iter().map(|d| fuzzy_score(&query, d)).collect();
+        let best = scores.iter().copied().fold(0.0, f64::max);
+        if best < FUZZY_THRESHOLD {
+            return ClipMatch::NotFound;
+        }
+        let close: Vec<usize> = (0..scores.len())
+            .filter(|&i| scores[i] >= best - FUZZY_MARGIN)
+            .collect();
+        match close.as_slice() {
+            [index] => ClipMatch::Found(*index),
+            _ => ClipMatch::Ambiguous(close),
+        }
+    }
+    
+    /// Add a marker, keeping markers ordered by time
+    pub fn add_marker(&mut self, label: String, time_seconds: f64, color: Option<String>) -> &Marker {
+        let id = self.new_marker_id();
+        let index = self.insert_marker(Marker {
+            color,
+            id,
+            label,
+            time_seconds: time_seconds.max(0.0),
+        });
+        
+        &self.markers[index]
+    }
+    
+    fn new_marker_id(&mut self) -> String {
+        loop {
+            self.next_marker_id += 1;
+            let id = format!("marker_{}", self.next_marker_id);
+            // Markers from older projects have clock-based IDs; skip any that match
+            if !self.markers.iter().any(|m| m.id == id) {
+                return id;
+            }
+        }
+    }
+    
+    /// Give fresh IDs to markers sharing one with an earlier marker, as
+    /// several added by one agent reply could
+    fn dedupe_marker_ids(&mut self) {
+        let mut seen = std::collections::HashSet::new();
+        let mut duplicates = Vec::new();
+        for (index, marker) in self.markers.iter().enumerate() {
+            if !seen.insert(marker.id.clone()) {
+                duplicates.push(index);
+            }
+        }
+        for &index in &duplicates {
+            let id = self.new_marker_id();
+            tracing::warn!("Marker '{}' shared the ID {}; it is now {}", self.markers[index].label, self.markers[index].id, id);
+            self.markers[index].id = id;
+        }
+    }
+    
+    /// Find a marker by exact ID, or else by a case-insensitive label substring
+    pub fn find_marker(&self, id: Option<&str>, label: Option<&str>) -> Option<usize> {
+        if let Some(id) = id {
+            return self.markers.iter().position(|m| m.id == id);
+        }
+        let label = label?.to_lowercase();
+        self.markers.iter().position(|m| m.label.to_lowercase().contains(&label))
+    }
+    
+    /// Remove the marker at `index`
+    pub fn remove_marker(&mut self, index: usize) -> Marker {
+        self.markers.remove(index)
+    }
+    
+    /// Change a marker's label, time or color; a new time re-sorts it
+    pub fn update_marker(
+        &mut self,
+        index: usize,
+        label: Option<String>,
+        time_seconds: Option<f64>,
+        color: Option<String>,
+    ) -> &Marker {
+        let mut marker = self.markers.remove(index);
+        if let Some(label) = label {
+            marker.label = label;
+        }
+        if let Some(time) = time_seconds {
+            marker.time_seconds = time.max(0.0);
+        }
+        if color.is_some() {
+            marker.color = color;
+        }
+        let index = self.insert_marker(marker);
+        &self.markers[index]
+    }
+    
+    /// Insert a marker at its sorted position, returning that position
+    fn insert_marker(&mut self, marker: Marker) -> usize {
+        let index = self.markers.partition_point(|m| m.time_seconds <= marker.time_seconds);
+        self.markers.insert(index, marker);
+        index
+    }
+    
     /// Get all video clips
     #[allow(dead_code)]
     pub fn video_clips(&self) -> impl Iterator<Item = &Clip> {
         self.clips.iter().filter(|c| c.media_type == MediaType::Video)
     }
     
+    /// How long new images stay on screen
+    pub fn still_duration(&self) -> f64 {
+        self.metadata.still_duration.unwrap_or(Clip::DEFAULT_STILL_DURATION)
+    }
+    
+    /// Length of the exported video: every video and still end to end
+    /// (None while a clip's length is still unknown)
+    pub fn video_duration(&self) -> Option<f64> {
+        self.clips
+            .iter()
+            .filter(|c| matches!(c.media_type, MediaType::Video | MediaType::Image))
+            .map(|c| c.duration)
+            .sum()
+    }
+    
+    /// Audio to transcribe: the main track, or else the first audio clip
+    pub fn audio_source(&self) -> Option<&Path> {
+        self.audio
+            .as_ref()
+            .map(|a| a.path.as_path())
+            .or_else(|| self.audio_clips().next().map(|c| c.path.as_path()))
+    }
+    
+    /// Cached transcript of `audio`, unless another file was transcribed or
+    /// this one changed since
+    pub fn cached_transcript(&self, audio: &Path) -> Option<&Transcript> {
+        let transcript = self.transcript.as_ref()?;
+        match &self.transcript_source {
+            Some(source) => (TranscriptSource::of(audio).as_ref() == Some(source)).then_some(transcript),
+            // Saved before sources were recorded; trust it
+            None => Some(transcript),
+        }
+    }
+    
+    /// Cached transcript of the project audio, if it is still current
+    pub fn current_transcript(&self) -> Option<&Transcript> {
+        match self.audio_source() {
+            Some(audio) => self.cached_transcript(audio),
+            None => self.transcript.as_ref(),
+        }
+    }
+    
+    /// Cache a transcript made from `audio`
+    pub fn set_transcript(&mut self, transcript: Transcript, audio: &Path) {
+        self.transcript = Some(transcript);
+        self.transcript_source = TranscriptSource::of(audio);
+    }
+    
     /// Get all audio clips
-    #[allow(dead_code)]
     pub fn audio_clips(&self) -> impl Iterator<Item = &Clip> {
         self.clips.iter().filter(|c| c.media_type == MediaType::Audio)
     }
 }
 
+/// Sidecar file a saved project's unsaved edits are autosaved to
+pub fn autosave_path(project_path: &Path) -> PathBuf {
+    let mut path = project_path.as_os_str().to_owned();
+    path.push(".autosave");
+    PathBuf::from(path)
+}
+
+/// The sidecar autosave, if it holds edits newer than the project file
+pub fn recoverable_autosave(project_path: &Path) -> Option<PathBuf> {
+    let autosave = autosave_path(project_path);
+    let autosaved = std::fs::metadata(&autosave).and_then(|m| m.modified()).ok()?;
+    match std::fs::metadata(project_path).and_then(|m| m.modified()) {
+        Ok(saved) if saved >= autosaved => None,
+        _ => Some(autosave),
+    }
+}
+
+/// Folder autosaves of never-saved projects go to
+pub fn untitled_autosave_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".montage").join("autosave"))
+}
+
+/// Most recently written autosave of a never-saved project
+pub fn newest_untitled_autosave() -> Option<PathBuf> {
+    std::fs::read_dir(untitled_autosave_dir()?)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == Project::EXTENSION))
+        .max_by_key(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+}
+
+/// Absolute folder containing a project file
+fn project_dir(project_path: &Path) -> Option<PathBuf> {
+    let dir = project_path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    std::path::absolute(dir).ok()
+}
+
+/// `source`'s file name inside `dir`, numbered when that name is taken
+fn free_name(dir: &Path, source: &Path) -> PathBuf {
+    let name = source.file_name().map(Path::new).unwrap_or(Path::new("media"));
+    let stem = name.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = name.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+    let mut target = dir.join(name);
+    let mut n = 2;
+    while target.exists() {
+        target = dir.join(format!("{} {}{}", stem, n, extension));
+        n += 1;
+    }
+    target
+}
+
+/// The 1-based position in "clip 3" or "#3"
+fn clip_number(query: &str) -> Option<usize> {
+    let number = match query.strip_prefix("clip") {
+        Some(rest) => rest.trim_start().trim_start_matches('#'),
+        None => query.strip_prefix('#')?,
+    };
+    number.trim().parse().ok()
+}
+
+/// Whether `query`'s words appear in `description` as whole words, in order
+fn contains_words(description: &str, query: &str) -> bool {
+    let words = |text: &str| -> Vec<String> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let (description, query) = (words(description), words(query));
+    !query.is_empty() && description.windows(query.len()).any(|window| window == query)
+}
+
+/// How well `query` matches `description`, from 0 to 1: each query word
+/// is paired with its closest description word and the results averaged
+fn fuzzy_score(query: &str, description: &str) -> f64 {
+    let words: Vec<&str> = description.split_whitespace().collect();
+    let query: Vec<&str> = query.split_whitespace().collect();
+    if query.is_empty() || words.is_empty() {
+        return 0.0;
+    }
+    let total: f64 = query
+        .iter()
+        .map(|q| words.iter().map(|w| word_similarity(q, w)).fold(0.0, f64::max))
+        .sum();
+    total / query.len() as f64
+}
+
+/// 1 for the same word, falling with edit distance; a prefix ("intro" for
+/// "introduction") counts as nearly the same
+fn word_similarity(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    if a.len() >= 3 && b.starts_with(a) {
+        return 0.9;
+    }
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    
+    // Levenshtein distance, one row at a time
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitute.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    1.0 - row[b.len()] as f64 / a.len().max(b.len()) as f64
+}
+
 /// Get current timestamp in ISO 8601 format
 fn chrono_now() -> String {
-    // Simple timestamp without chrono dependency
-    use std::time::{SystemTime, UNIX_EPOCH};
-    let duration = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default();
-    format!("{}", duration.as_secs())
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Rewrite a timestamp saved as bare epoch seconds, as early projects did
+fn migrate_timestamp(timestamp: &mut Option<String>) {
+    let seconds = timestamp.as_deref().and_then(|t| t.trim().parse::<i64>().ok());
+    if let Some(time) = seconds.and_then(|s| DateTime::from_timestamp(s, 0)) {
+        *timestamp = Some(time.to_rfc3339_opts(SecondsFormat::Secs, true));
+    }
+}
+
+/// How long ago something happened, roughly: "just now", "5 min ago",
+/// "3 h ago", "2 days ago"
+pub fn time_ago(seconds: i64) -> String {
+    match seconds {
+        ..60 => "just now".to_string(),
+        60..3600 => format!("{} min ago", seconds / 60),
+        3600..86400 => format!("{} h ago", seconds / 3600),
+        86400..172800 => "yesterday".to_string(),
+        _ => format!("{} days ago", seconds / 86400),
+    }
 }
 
 #[cfg(test)]
@@ -269,4 +1175,606 @@ mod tests {
         assert_eq!(loaded.timeline.position, 30.0);
         assert!(loaded.audio.is_some());
     }
+    
+    /// Fresh scratch directory for a test
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("montage_test_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+    
+    #[test]
+    fn test_recoverable_autosave_only_when_newer() {
+        let root = scratch_dir("autosave");
+        let path = root.join("edit.montage");
+        Project::new("Edit").save(&path).unwrap();
+        assert_eq!(recoverable_autosave(&path), None);
+        
+        let autosave = autosave_path(&path);
+        assert_eq!(autosave, root.join("edit.montage.autosave"));
+        Project::new("Edit").save(&autosave).unwrap();
+        
+        // Saved before the autosave was written
+        let earlier = std::time::SystemTime::now() - std::time::Duration::from_secs(60);
+        std::fs::File::options().write(true).open(&path).unwrap().set_modified(earlier).unwrap();
+        assert_eq!(recoverable_autosave(&path), Some(autosave.clone()));
+        
+        // Saved again since
+        let later = std::time::SystemTime::now() + std::time::Duration::from_secs(60);
+        std::fs::File::options().write(true).open(&path).unwrap().set_modified(later).unwrap();
+        assert_eq!(recoverable_autosave(&path), None);
+        
+        let _ = std::fs::remove_dir_all(&root);
+    }
+    
+    #[test]
+    fn test_conversation_keeps_the_latest_turns() {
+        let mut project = Project::new("Chatty");
+        for i in 0..Project::MAX_CONVERSATION_TURNS + 3 {
+            project.push_conversation(format!("prompt {}", i), "ok".to_string());
+        }
+        
+        assert_eq!(project.conversation.len(), Project::MAX_CONVERSATION_TURNS);
+        assert_eq!(project.conversation[0].user, "prompt 3");
+        
+        let json = serde_json::to_string(&project).unwrap();
+        let loaded: Project = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.conversation, project.conversation);
+    }
+    
+    #[test]
+    fn test_transcript_cache_follows_the_audio_file() {
+        let root = scratch_dir("transcript");
+        let voice = root.join("voice.wav");
+        let other = root.join("other.wav");
+        std::fs::write(&voice, b"take one").unwrap();
+        std::fs::write(&other, b"other").unwrap();
+        
+        let mut project = Project::new("Cached");
+        let transcript = Transcript {
+            text: "hello".to_string(),
+            segments: Vec::new(),
+            language: None,
+            duration: 1.0,
+        };
+        project.set_transcript(transcript, &voice);
+        assert!(project.cached_transcript(&voice).is_some());
+        assert!(project.cached_transcript(&other).is_none());
+        
+        // Re-recorded under the same name
+        std::fs::write(&voice, b"take two, longer").unwrap();
+        assert!(project.cached_transcript(&voice).is_none());
+        
+        let _ = std::fs::remove_dir_all(&root);
+    }
+    
+    #[test]
+    fn test_media_paths_follow_moved_folder() {
+        let root = scratch_dir("moved");
+        let original = root.join("original");
+        std::fs::create_dir_all(original.join("media")).unwrap();
+        
+        let mut project = Project::new("Portable");
+        project.set_audio(original.join("voice.mp3"), 60.0, 44100);
+        project.add_clip("intro".to_string(), original.join("media").join("intro.mp4"));
+        project.add_clip("stock".to_string(), PathBuf::from("/elsewhere/stock.mp4"));
+        project.save(original.join("project.montage")).unwrap();
+        
+        // On disk, only media outside the folder stays absolute
+        let saved = std::fs::read_to_string(original.join("project.montage")).unwrap();
+        let raw: Project = serde_json::from_str(&saved).unwrap();
+        assert_eq!(raw.version, Project::CURRENT_VERSION);
+        assert_eq!(raw.audio.unwrap().path, PathBuf::from("voice.mp3"));
+        assert_eq!(raw.clips[0].path, Path::new("media").join("intro.mp4"));
+        assert_eq!(raw.clips[1].path, PathBuf::from("/elsewhere/stock.mp4"));
+        
+        let moved = root.join("moved");
+        std::fs::rename(&original, &moved).unwrap();
+        let loaded = Project::load(moved.join("project.montage")).unwrap();
+        
+        assert_eq!(loaded.audio.unwrap().path, moved.join("voice.mp3"));
+        assert_eq!(loaded.clips[0].path, moved.join("media").join("intro.mp4"));
+        assert_eq!(loaded.clips[1].path, PathBuf::from("/elsewhere/stock.mp4"));
+        
+        let _ = std::fs::remove_dir_all(&root);
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn test_media_through_symlink_saved_relative() {
+        let root = scratch_dir("symlink");
+        let real = root.join("real");
+        std::fs::create_dir_all(real.join("media")).unwrap();
+        std::fs::write(real.join("media").join("intro.mp4"), b"intro").unwrap();
+        let link = root.join("link");
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+        
+        // Media picked through the link, project saved through the real path
+        let mut project = Project::new("Linked");
+        project.add_clip("intro".to_string(), link.join("media").join("intro.mp4"));
+        project.save(real.join("project.montage")).unwrap();
+        
+        let raw: Project = serde_json::from_str(&std::fs::read_to_string(real.join("project.montage")).unwrap()).unwrap();
+        assert_eq!(raw.clips[0].path, Path::new("media").join("intro.mp4"));
+        
+        let _ = std::fs::remove_dir_all(&root);
+    }
+    
+    #[test]
+    fn test_copied_media_saved_relative() {
+        let root = scratch_dir("copy_media");
+        let project_dir = root.join("project");
+        let downloads = root.join("downloads");
+        std::fs::create_dir_all(&project_dir).unwrap();
+        std::fs::create_dir_all(downloads.join("other")).unwrap();
+        std::fs::write(downloads.join("beach.mp4"), b"beach").unwrap();
+        std::fs::write(downloads.join("other").join("beach.mp4"), b"other beach").unwrap();
+        
+        let mut project = Project::new("Copied");
+        project.add_clip("beach".to_string(), downloads.join("beach.mp4"));
+        project.add_clip("beach again".to_string(), downloads.join("beach.mp4"));
+        project.add_clip("other beach".to_string(), downloads.join("other").join("beach.mp4"));
+        project.add_clip("missing".to_string(), downloads.join("gone.mp4"));
+        
+        let path = project_dir.join("project.montage");
+        assert_eq!(project.copy_media_beside(&path).unwrap(), 2);
+        project.save(&path).unwrap();
+        
+        // Same source shares one copy, a clashing name gets a number,
+        // and files that don't exist are left alone
+        let raw: Project = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(raw.clips[0].path, Path::new("media").join("beach.mp4"));
+        assert_eq!(raw.clips[1].path, Path::new("media").join("beach.mp4"));
+        assert_eq!(raw.clips[2].path, Path::new("media").join("beach 2.mp4"));
+        assert_eq!(raw.clips[3].path, downloads.join("gone.mp4"));
+        assert_eq!(std::fs::read(project_dir.join("media").join("beach 2.mp4")).unwrap(), b"other beach");
+        
+        // Nothing left to copy the second time
+        let mut loaded = Project::load(&path).unwrap();
+        assert_eq!(loaded.copy_media_beside(&path).unwrap(), 0);
+        assert_eq!(loaded.clips[0].path, project_dir.join("media").join("beach.mp4"));
+        
+        let _ = std::fs::remove_dir_all(&root);
+    }
+    
+    #[test]
+    fn test_version_1_project_migrates() {
+        let dir = scratch_dir("v1");
+        let json = r#"{
+            "version": 1,
+            "metadata": { "name": "Old" },
+            "audio": { "path": "/music/song.mp3" },
+            "video": null,
+            "timeline": {}
+        }"#;
+        std::fs::write(dir.join("old.montage"), json).unwrap();
+        
+        let loaded = Project::load(dir.join("old.montage")).unwrap();
+        
+        assert_eq!(loaded.version, Project::CURRENT_VERSION);
+        assert_eq!(loaded.audio.unwrap().path, PathBuf::from("/music/song.mp3"));
+        
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+    
+    #[test]
+    fn test_epoch_timestamps_migrate_to_rfc3339() {
+        let dir = scratch_dir("timestamps");
+        let json = r#"{
+            "version": 2,
+            "metadata": { "name": "Old", "created_at": "1718822400", "modified_at": "1718826000" },
+            "audio": null,
+            "video": null,
+            "timeline": {}
+        }"#;
+        std::fs::write(dir.join("old.montage"), json).unwrap();
+        
+        let loaded = Project::load(dir.join("old.montage")).unwrap();
+        assert_eq!(loaded.metadata.created_at.as_deref(), Some("2024-06-19T18:40:00Z"));
+        assert_eq!(loaded.metadata.modified_at.as_deref(), Some("2024-06-19T19:40:00Z"));
+        assert_eq!(loaded.metadata.modified().unwrap().timestamp(), 1718826000);
+        
+        // New saves write RFC 3339 and read back unchanged
+        let mut project = Project::new("New");
+        project.save(dir.join("new.montage")).unwrap();
+        let loaded = Project::load(dir.join("new.montage")).unwrap();
+        assert_eq!(loaded.metadata.modified_at, project.metadata.modified_at);
+        assert!(loaded.metadata.modified().is_some());
+        assert!(DateTime::parse_from_rfc3339(loaded.metadata.created_at.as_deref().unwrap()).is_ok());
+        
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+    
+    #[test]
+    fn test_time_ago() {
+        assert_eq!(time_ago(-5), "just now");
+        assert_eq!(time_ago(59), "just now");
+        assert_eq!(time_ago(5 * 60 + 10), "5 min ago");
+        assert_eq!(time_ago(3 * 3600), "3 h ago");
+        assert_eq!(time_ago(30 * 3600), "yesterday");
+        assert_eq!(time_ago(3 * 86400), "3 days ago");
+    }
+    
+    #[test]
+    fn test_move_clips_as_block() {
+        let mut project = Project::new("Block");
+        for name in ["a", "b", "c", "d", "e"] {
+            project.add_clip(name.to_string(), PathBuf::from(format!("{}.mp4", name)));
+        }
+        let order = |p: &Project| p.clips.iter().map(|c| c.description.clone()).collect::<Vec<_>>().join("");
+        
+        // b and d to the end
+        project.move_clips(&[3, 1], 5);
+        assert_eq!(order(&project), "acebd");
+        
+        // e and b to the front
+        project.move_clips(&[2, 3], 0);
+        assert_eq!(order(&project), "ebacd");
+        
+        // Into a gap inside the block's own span
+        project.move_clips(&[0, 2], 2);
+        assert_eq!(order(&project), "beacd");
+    }
+    
+    #[test]
+    fn test_probed_duration_moves_later_clips() {
+        let mut project = Project::new("Durations");
+        for name in ["a", "b", "c"] {
+            project.add_clip(name.to_string(), PathBuf::from(format!("{}.mp4", name)));
+        }
+        let first = project.clips[0].id.clone();
+        let second = project.clips[1].id.clone();
+        
+        project.set_clip_duration(&first, 12.0);
+        project.set_clip_duration(&second, 3.5);
+        
+        let starts: Vec<f64> = project.clips.iter().map(|c| c.start_time).collect();
+        assert_eq!(starts, vec![0.0, 12.0, 15.5]);
+    }
+    
+    #[test]
+    fn test_video_duration_needs_every_clip() {
+        let mut project = Project::new("Length");
+        project.add_clip("still".to_string(), PathBuf::from("title.png"));
+        project.add_clip("music".to_string(), PathBuf::from("song.mp3"));
+        project.add_clip("shot".to_string(), PathBuf::from("shot.mp4"));
+        assert_eq!(project.video_duration(), None);
+        
+        project.clips[2].duration = Some(20.0);
+        assert_eq!(project.video_duration(), Some(Clip::DEFAULT_STILL_DURATION + 20.0));
+    }
+    
+    #[test]
+    fn test_new_stills_use_project_still_duration() {
+        let mut project = Project::new("Stills");
+        project.add_clip("before".to_string(), PathBuf::from("a.png"));
+        project.metadata.still_duration = Some(2.5);
+        project.add_clip("after".to_string(), PathBuf::from("b.png"));
+        
+        let durations: Vec<_> = project.clips.iter().map(|c| c.duration).collect();
+        assert_eq!(durations, vec![Some(Clip::DEFAULT_STILL_DURATION), Some(2.5)]);
+        assert_eq!(project.clips[1].start_time, Clip::DEFAULT_STILL_DURATION);
+    }
+    
+    #[test]
+    fn test_hundred_clips_get_distinct_ids() {
+        let mut project = Project::new("Burst");
+        for i in 0..100 {
+            project.add_clip(format!("clip {}", i), PathBuf::from(format!("/clips/{}.mp4", i)));
+        }
+        project.duplicate_clip(3);
+        project.duplicate_clip(3);
+        let ids: std::collections::HashSet<&str> = project.clips.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids.len(), 102);
+        
+        // The counter survives a save, so reopened projects keep counting
+        let json = serde_json::to_string(&project).unwrap();
+        let mut loaded: Project = serde_json::from_str(&json).unwrap();
+        let id = loaded.add_clip("more".to_string(), PathBuf::from("/clips/more.mp4")).id.clone();
+        assert!(!ids.contains(id.as_str()));
+    }
+    
+    #[test]
+    fn test_colliding_ids_reassigned_on_load() {
+        // As saved when two files were dropped within the same millisecond
+        let mut project = Project::new("Old");
+        project.add_clip("beach".to_string(), PathBuf::from("/clips/beach.mp4"));
+        project.add_clip("city".to_string(), PathBuf::from("/clips/city.mp4"));
+        project.add_clip("forest".to_string(), PathBuf::from("/clips/forest.mp4"));
+        for clip in &mut project.clips {
+            clip.id = "clip_1700000000000".to_string();
+        }
+        project.next_clip_id = 0;
+        
+        let dir = scratch_dir("dedupe");
+        let path = dir.join("old.montage");
+        std::fs::write(&path, serde_json::to_string(&project).unwrap()).unwrap();
+        let loaded = Project::load(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        
+        // The first keeps its ID; each clip can now be picked out on its own
+        assert_eq!(loaded.clips[0].id, "clip_1700000000000");
+        for (index, clip) in loaded.clips.iter().enumerate() {
+            assert_eq!(loaded.find_clip(Some(&clip.id), None), ClipMatch::Found(index));
+        }
+    }
+    
+    #[test]
+    fn test_marker_ids_unique_and_reassigned_on_load() {
+        let mut project = Project::new("Markers");
+        for i in 0..20 {
+            project.add_marker(format!("note {}", i), f64::from(i), None);
+        }
+        let ids: std::collections::HashSet<&str> = project.markers.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids.len(), 20);
+        
+        // As saved when a burst of markers shared the same millisecond
+        for marker in &mut project.markers {
+            marker.id = "marker_1700000000000".to_string();
+        }
+        project.next_marker_id = 0;
+        let dir = scratch_dir("marker_dedupe");
+        let path = dir.join("old.montage");
+        std::fs::write(&path, serde_json::to_string(&project).unwrap()).unwrap();
+        let mut loaded = Project::load(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        
+        assert_eq!(loaded.markers[0].id, "marker_1700000000000");
+        for (index, marker) in loaded.markers.iter().enumerate() {
+            assert_eq!(loaded.find_marker(Some(&marker.id), None), Some(index));
+        }
+        let removed = loaded.remove_marker(loaded.find_marker(Some("marker_3"), None).unwrap());
+        assert_eq!(removed.id, "marker_3");
+        assert_eq!(loaded.markers.len(), 19);
+    }
+    
+    #[test]
+    fn test_duplicate_clip_follows_original() {
+        let mut project = Project::new("Cutaways");
+        project.add_clip("intro".to_string(), PathBuf::from("/clips/intro.mp4"));
+        project.add_clip("beach".to_string(), PathBuf::from("/clips/beach.mp4"));
+        project.add_clip("outro".to_string(), PathBuf::from("/clips/outro.mp4"));
+        for (clip, seconds) in project.clips.iter_mut().zip([4.0, 6.0, 3.0]) {
+            clip.duration = Some(seconds);
+        }
+        project.clips[1].source_in = 2.5;
+        project.clips[1].label = Some(ClipLabel::Green);
+        project.update_start_times();
+        
+        let copy_id = project.duplicate_clip(1).id.clone();
+        
+        let names: Vec<&str> = project.clips.iter().map(|c| c.description.as_str()).collect();
+        assert_eq!(names, ["intro", "beach", "beach (copy)", "outro"]);
+        let copy = &project.clips[2];
+        assert_eq!(copy.id, copy_id);
+        assert_ne!(copy.id, project.clips[1].id);
+        assert_eq!(copy.source_in, 2.5);
+        assert_eq!(copy.label, Some(ClipLabel::Green));
+        assert_eq!(copy.start_time, 10.0);
+        assert_eq!(project.clips[3].start_time, 16.0);
+    }
+    
+    #[test]
+    fn test_no_gaps_after_removing_or_moving() {
+        let mut project = Project::new("Ripple");
+        for name in ["a", "b", "c", "d"] {
+            project.add_clip(name.to_string(), PathBuf::from(format!("/clips/{}.mp4", name)));
+        }
+        for (clip, seconds) in project.clips.iter_mut().zip([2.0, 3.0, 4.0, 5.0]) {
+            clip.duration = Some(seconds);
+        }
+        project.update_start_times();
+        let starts = |project: &Project| project.clips.iter().map(|c| c.start_time).collect::<Vec<_>>();
+        
+        project.remove_clip(1);
+        assert_eq!(starts(&project), [0.0, 2.0, 6.0]);
+        
+        project.move_clip(2, 0);
+        assert_eq!(starts(&project), [0.0, 5.0, 7.0]);
+        
+        project.swap_clips(0, 2);
+        assert_eq!(starts(&project), [0.0, 4.0, 6.0]);
+        
+        let id = project.clips[1].id.clone();
+        project.remove_clips(&[id]);
+        assert_eq!(starts(&project), [0.0, 4.0]);
+        
+        // A voiceover first, as auto-video adds it, leaves the clips where they were
+        let mut project = Project::new("Voiceover");
+        project.add_clip("voiceover".to_string(), PathBuf::from("/audio/voice.mp3"));
+        project.clips[0].duration = Some(30.0);
+        for name in ["a", "b", "c"] {
+            project.add_clip(name.to_string(), PathBuf::from(format!("/clips/{}.mp4", name)));
+        }
+        for (clip, seconds) in project.clips[1..].iter_mut().zip([2.0, 3.0, 4.0]) {
+            clip.duration = Some(seconds);
+        }
+        project.update_start_times();
+        assert_eq!(starts(&project), [0.0, 0.0, 2.0, 5.0]);
+        assert_eq!(project.clip_at(1.0), Some(1));
+        
+        project.remove_clip(1);
+        assert_eq!(starts(&project), [0.0, 0.0, 3.0]);
+        
+        project.move_clip(0, 2);
+        assert_eq!(starts(&project), [0.0, 3.0, 0.0]);
+    }
+    
+    #[test]
+    fn test_split_clip_at_time() {
+        let mut project = Project::new("Split");
+        project.add_clip("intro".to_string(), PathBuf::from("/clips/intro.mp4"));
+        project.add_clip("beach".to_string(), PathBuf::from("/clips/beach.mp4"));
+        for (clip, seconds) in project.clips.iter_mut().zip([4.0, 10.0]) {
+            clip.duration = Some(seconds);
+        }
+        project.clips[1].source_in = 1.0;
+        project.update_start_times();
+        
+        assert_eq!(project.clip_at(7.0), Some(1));
+        let second = project.split_clip(1, 7.0).unwrap();
+        assert_eq!(second, 2);
+        
+        let (first, second) = (&project.clips[1], &project.clips[2]);
+        assert_eq!((first.source_in, first.duration), (1.0, Some(3.0)));
+        assert_eq!((second.source_in, second.duration), (4.0, Some(7.0)));
+        assert_eq!(second.start_time, 7.0);
+        assert_eq!(second.path, first.path);
+        assert_ne!(second.id, first.id);
+        
+        // Not inside the clip, or too close to an edge
+        assert!(project.split_clip(0, 5.0).is_err());
+        assert!(project.split_clip(0, 0.01).is_err());
+    }
+    
+    #[test]
+    fn test_clip_labels_roundtrip() {
+        let mut project = Project::new("Labels");
+        project.add_clip("beach".to_string(), PathBuf::from("/clips/beach.mp4"));
+        project.add_clip("intro".to_string(), PathBuf::from("/clips/intro.mp4"));
+        project.add_clip("outro".to_string(), PathBuf::from("/clips/outro.mp4"));
+        project.clips[0].label = ClipLabel::parse("Yellow");
+        project.clips[1].label = ClipLabel::parse("b-roll");
+        
+        let json = serde_json::to_string(&project).unwrap();
+        assert!(json.contains("\"label\":\"yellow\""));
+        assert!(json.contains("\"label\":\"b-roll\""));
+        
+        let loaded: Project = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.clips[0].label, Some(ClipLabel::Yellow));
+        assert_eq!(loaded.clips[1].label, Some(ClipLabel::Tag("b-roll".to_string())));
+        assert_eq!(loaded.clips[2].label, None);
+        assert_eq!(ClipLabel::parse("  "), None);
+    }
+    
+    #[test]
+    fn test_clip_errors_not_saved() {
+        let mut project = Project::new("Broken");
+        let id = project.add_clip("corrupt".to_string(), PathBuf::from("/clips/corrupt.mp4")).id.clone();
+        project.set_clip_error(&id, "Can't be decoded".to_string());
+        assert_eq!(project.broken_clips().count(), 1);
+        
+        let loaded: Project = serde_json::from_str(&serde_json::to_string(&project).unwrap()).unwrap();
+        assert_eq!(loaded.broken_clips().count(), 0);
+        
+        // A later successful probe clears it
+        project.set_clip_duration(&id, 3.0);
+        assert_eq!(project.clips[0].error, None);
+    }
+    
+    #[test]
+    fn test_credits_list_each_source_once() {
+        let mut project = Project::new("Trip");
+        assert_eq!(project.credits(), None);
+        
+        let beach = project.add_clip("beach".to_string(), PathBuf::from("/tmp/1.mp4")).id.clone();
+        let again = project.add_clip("beach again".to_string(), PathBuf::from("/tmp/1.mp4")).id.clone();
+        project.add_clip("my own".to_string(), PathBuf::from("/clips/me.mp4"));
+        let source = ClipSource::pexels("https://www.pexels.com/video/1/", "Jane Doe");
+        project.set_clip_source(&beach, source.clone());
+        project.set_clip_source(&again, source);
+        
+        let credits = project.credits().unwrap();
+        assert!(credits.starts_with("Credits for Trip"));
+        assert_eq!(credits.matches("Jane Doe on Pexels").count(), 1);
+        assert!(credits.contains("https://www.pexels.com/video/1/"));
+        assert!(!credits.contains("my own"));
+        
+        // Saved with the project; older projects without it still load
+        let loaded: Project = serde_json::from_str(&serde_json::to_string(&project).unwrap()).unwrap();
+        assert_eq!(loaded.clips[0].source.as_ref().map(|s| s.author.as_str()), Some("Jane Doe"));
+        assert_eq!(loaded.clips[2].source, None);
+    }
+    
+    #[test]
+    fn test_export_preferences_roundtrip() {
+        let mut project = Project::new("Export");
+        let json = serde_json::to_string(&project).unwrap();
+        assert!(!json.contains("\"export\""));
+        
+        project.export = Some(ExportPreferences {
+            preset: Some("Vertical 9:16".to_string()),
+            clip_audio_gain: Some(0.2),
+            ..Default::default()
+        });
+        let json = serde_json::to_string(&project).unwrap();
+        let loaded: Project = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.export, project.export);
+        
+        // Hand-written settings may leave fields out
+        let partial: ExportPreferences = serde_json::from_str(r#"{"format": "webm"}"#).unwrap();
+        assert_eq!(partial.format.as_deref(), Some("webm"));
+        assert_eq!(partial.preset, None);
+    }
+    
+    #[test]
+    fn test_find_clip_prefers_exact_then_substring_then_fuzzy() {
+        let mut project = Project::new("Matching");
+        for name in ["beach at sunset", "city skyline", "drone shot 1", "drone shot 2", "intro"] {
+            project.add_clip(name.to_string(), PathBuf::from(format!("{}.mp4", name)));
+        }
+        let find = |d: &str| project.find_clip(None, Some(d));
+        
+        assert_eq!(find("Intro"), ClipMatch::Found(4));
+        assert_eq!(find("skyline"), ClipMatch::Found(1));
+        assert_eq!(find("drone shot"), ClipMatch::Ambiguous(vec![2, 3]));
+        assert_eq!(find("clip 2"), ClipMatch::Found(1));
+        assert_eq!(find("#5"), ClipMatch::Found(4));
+        assert_eq!(find("clip 9"), ClipMatch::NotFound);
+        // Typos
+        assert_eq!(find("beech sunset"), ClipMatch::Found(0));
+        assert_eq!(find("citty skylin"), ClipMatch::Found(1));
+        assert_eq!(find("waterfall"), ClipMatch::NotFound);
+        
+        project.clips[3].id = "clip_drone_2".to_string();
+        assert_eq!(project.find_clip(Some("clip_drone_2"), Some("beach")), ClipMatch::Found(3));
+    }
+    
+    #[test]
+    fn test_find_clip_prefers_whole_words() {
+        let mut project = Project::new("Words");
+        for name in ["introduction to the park", "intro music", "drone shot 1"] {
+            project.add_clip(name.to_string(), PathBuf::from(format!("{}.mp4", name)));
+        }
+        
+        assert_eq!(project.find_clip(None, Some("intro")), ClipMatch::Found(1));
+        assert_eq!(project.find_clip(None, Some("introduction")), ClipMatch::Found(0));
+        assert_eq!(project.find_clip(None, Some("shot")), ClipMatch::Found(2));
+        // Only a substring left, still found
+        assert_eq!(project.find_clip(None, Some("introduc")), ClipMatch::Found(0));
+        // A clip literally named like a position wins over the position
+        project.add_clip("clip 1".to_string(), PathBuf::from("clip.mp4"));
+        assert_eq!(project.find_clip(None, Some("clip 1")), ClipMatch::Found(3));
+    }
+    
+    #[test]
+    fn test_markers_stay_sorted() {
+        let mut project = Project::new("Markers");
+        project.add_marker("outro".to_string(), 90.0, None);
+        project.add_marker("intro".to_string(), 5.0, Some("#ff0000".to_string()));
+        project.add_marker("drop".to_string(), 42.0, None);
+        
+        let labels: Vec<&str> = project.markers.iter().map(|m| m.label.as_str()).collect();
+        assert_eq!(labels, vec!["intro", "drop", "outro"]);
+        assert_eq!(project.markers[0].rgb(), Some(0xff0000));
+        
+        let index = project.find_marker(None, Some("DROP")).unwrap();
+        project.update_marker(index, None, Some(120.0), None);
+        assert_eq!(project.markers.last().unwrap().label, "drop");
+        
+        let index = project.find_marker(None, Some("intro")).unwrap();
+        project.remove_marker(index);
+        assert_eq!(project.markers.len(), 2);
+        assert!(project.find_marker(None, Some("intro")).is_none());
+    }
+    
+    #[test]
+    fn test_marker_loads_legacy_description() {
+        let json = r#"{"description": "cut here", "id": "marker_1", "time_seconds": 3.5}"#;
+        let marker: Marker = serde_json::from_str(json).unwrap();
+        
+        assert_eq!(marker.label, "cut here");
+        assert!(marker.color.is_none());
+    }
 }
@@ -1,8 +1,16 @@
 use gpui::*;
+use gpui::prelude::FluentBuilder;
 use std::path::PathBuf;
 
+use crate::text_buffer::TextBuffer;
+
+/// Most submissions kept for up-arrow recall
+pub const HISTORY_LIMIT: usize = 100;
+
 /// Events emitted by the prompt input
 pub enum PromptEvent {
+    /// User cancelled the command being processed
+    Cancel,
     /// User submitted a command with optional file attachments
     Submit {
         text: String,
@@ -14,14 +22,24 @@ impl EventEmitter<PromptEvent> for PromptInput {}
 
 /// Prompt input component for agentic interactions
 pub struct PromptInput {
-    /// Current input text
-    text: String,
+    /// Current input text and cursor
+    buffer: TextBuffer,
     /// Attached files (via @ or drag-drop)
     attachments: Vec<Attachment>,
+    /// Unsent text put aside while browsing history
+    draft: String,
     /// Focus handle for keyboard input
     focus_handle: FocusHandle,
+    /// Earlier submissions, oldest first
+    history: Vec<String>,
+    /// History entry in the input, if browsing
+    history_index: Option<usize>,
     /// Whether we're processing a command
     processing: bool,
+    /// Whether the reply has started arriving
+    streaming: bool,
+    /// Text of the last submission, restored if it gets cancelled
+    submitted_text: String,
     /// Animation frame for thinking dots
     thinking_frame: usize,
 }
@@ -36,20 +54,29 @@ impl PromptInput {
     pub fn new(cx: &mut Context<Self>) -> Self {
         Self {
             attachments: Vec::new(),
+            buffer: TextBuffer::new(),
+            draft: String::new(),
             focus_handle: cx.focus_handle(),
-            text: String::new(),
+            history: Vec::new(),
+            history_index: None,
             processing: false,
+            streaming: false,
+            submitted_text: String::new(),
             thinking_frame: 0,
         }
     }
     
     /// Get the current thinking dots animation
     fn thinking_text(&self) -> &'static str {
-        match self.thinking_frame % 4 {
-            0 => "Thinking",
-            1 => "Thinking.",
-            2 => "Thinking..",
-            _ => "Thinking...",
+        match (self.streaming, self.thinking_frame % 4) {
+            (false, 0) => "Thinking",
+            (false, 1) => "Thinking.",
+            (false, 2) => "Thinking..",
+            (false, _) => "Thinking...",
+            (true, 0) => "Replying",
+            (true, 1) => "Replying.",
+            (true, 2) => "Replying..",
+            (true, _) => "Replying...",
         }
     }
     
@@ -72,40 +99,223 @@ impl PromptInput {
 
     /// Clear the input
     pub fn clear(&mut self) {
-        self.text.clear();
+        self.buffer.clear();
         self.attachments.clear();
+        self.history_index = None;
+    }
+    
+    /// Seed up-arrow recall with earlier submissions (oldest first)
+    pub fn set_history(&mut self, history: Vec<String>) {
+        self.history = history;
+        self.history_index = None;
+    }
+    
+    /// Show the submission before the one in the input; false if there's none
+    fn recall_previous(&mut self) -> bool {
+        let index = match self.history_index {
+            None if self.history.is_empty() => return false,
+            None => {
+                self.draft = self.buffer.text().to_string();
+                self.history.len() - 1
+            }
+            Some(0) => return false,
+            Some(index) => index - 1,
+        };
+        self.history_index = Some(index);
+        self.buffer.set_text(self.history[index].clone());
+        true
+    }
+    
+    /// Show the submission after the one in the input, then the draft
+    fn recall_next(&mut self) -> bool {
+        let Some(index) = self.history_index else {
+            return false;
+        };
+        match self.history.get(index + 1) {
+            Some(entry) => {
+                self.history_index = Some(index + 1);
+                self.buffer.set_text(entry.clone());
+            }
+            None => self.restore_draft(),
+        }
+        true
+    }
+    
+    /// Stop browsing history and bring back what was being typed
+    fn restore_draft(&mut self) {
+        self.history_index = None;
+        self.buffer.set_text(std::mem::take(&mut self.draft));
     }
     
     /// Set processing state
     pub fn set_processing(&mut self, processing: bool) {
         self.processing = processing;
+        self.streaming = false;
         if processing {
             self.thinking_frame = 0;
         }
     }
     
+    /// Note that the reply has started arriving
+    pub fn set_streaming(&mut self) {
+        self.streaming = self.processing;
+    }
+    
     /// Check if currently processing
     pub fn is_processing(&self) -> bool {
         self.processing
     }
 
     fn submit(&mut self, cx: &mut Context<Self>) {
-        if self.text.trim().is_empty() && self.attachments.is_empty() {
+        if self.buffer.text().trim().is_empty() && self.attachments.is_empty() {
             return;
         }
         
-        if self.processing {
-            return;
-        }
-
-        let text = self.text.clone();
+        // Submitting while processing replaces the pending command
+        let text = self.buffer.text().to_string();
         let attachments = self.attachments.iter().map(|a| a.path.clone()).collect();
         
+        self.submitted_text = text.clone();
+        if !text.trim().is_empty() && self.history.last() != Some(&text) {
+            self.history.push(text.clone());
+            let excess = self.history.len().saturating_sub(HISTORY_LIMIT);
+            self.history.drain(..excess);
+        }
         cx.emit(PromptEvent::Submit { text, attachments });
         self.clear();
         cx.notify();
     }
 
+    /// Abort the pending command and put its text back in the input
+    fn cancel(&mut self, cx: &mut Context<Self>) {
+        if !self.processing {
+            return;
+        }
+        
+        self.set_processing(false);
+        if self.buffer.is_empty() {
+            self.buffer.set_text(std::mem::take(&mut self.submitted_text));
+        }
+        cx.emit(PromptEvent::Cancel);
+        cx.notify();
+    }
+
+    fn handle_key_down(&mut self, event: &KeyDownEvent, cx: &mut Context<Self>) {
+        let key = event.keystroke.key.as_str();
+        let modifiers = &event.keystroke.modifiers;
+        let command = modifiers.control || modifiers.platform;
+        
+        match key {
+            // Ctrl+V / Cmd+V pastes at the cursor
+            "v" if command => {
+                if let Some(text) = cx.read_from_clipboard()
+                    .and_then(|item| item.text().map(|s| s.to_string()))
+                {
+                    self.buffer.insert(&text);
+                }
+            }
+            // Ctrl+A / Cmd+A selects everything (the next edit replaces it)
+            "a" if command => self.buffer.select_all(),
+            "backspace" if modifiers.alt => self.buffer.delete_word_backward(),
+            "backspace" => self.buffer.backspace(),
+            "delete" => self.buffer.delete(),
+            // Up/down past the first/last line walk through earlier submissions
+            "down" => {
+                if !(self.buffer.on_last_line() && self.recall_next()) {
+                    self.buffer.move_down();
+                }
+            }
+            "end" if modifiers.shift => self.buffer.select_end(),
+            "end" => self.buffer.move_end(),
+            // Shift+Enter starts a new line; plain Enter submits
+            "enter" if modifiers.shift => self.buffer.insert("\n"),
+            "enter" => {
+                self.submit(cx);
+                return;
+            }
+            "escape" if self.history_index.is_some() => self.restore_draft(),
+            "escape" if self.processing && self.buffer.is_empty() => {
+                self.cancel(cx);
+                return;
+            }
+            "escape" => self.buffer.clear(),
+            "home" if modifiers.shift => self.buffer.select_home(),
+            "home" => self.buffer.move_home(),
+            "left" if modifiers.platform && modifiers.shift => self.buffer.select_home(),
+            "left" if modifiers.platform => self.buffer.move_home(),
+            "left" if modifiers.shift => self.buffer.select_left(),
+            "left" => self.buffer.move_left(),
+            "right" if modifiers.platform && modifiers.shift => self.buffer.select_end(),
+            "right" if modifiers.platform => self.buffer.move_end(),
+            "right" if modifiers.shift => self.buffer.select_right(),
+            "right" => self.buffer.move_right(),
+            "up" => {
+                if !(self.buffer.on_first_line() && self.recall_previous()) {
+                    self.buffer.move_up();
+                }
+            }
+            _ => {
+                // Skip if modifier keys are held (except shift)
+                if command || modifiers.alt {
+                    return;
+                }
+                let Some(ch) = &event.keystroke.key_char else {
+                    return;
+                };
+                self.buffer.insert(ch);
+            }
+        }
+        cx.notify();
+    }
+    
+    /// Render the text with the caret at the cursor (or the selection highlighted)
+    fn render_text(&self, placeholder: &str, is_focused: bool) -> AnyElement {
+        let caret = if is_focused { "│" } else { "" };
+        
+        if self.buffer.is_empty() {
+            return div()
+                .text_color(rgb(0x666666))
+                .child(format!("{}{}", placeholder, caret))
+                .into_any_element();
+        }
+        
+        let text = self.buffer.text();
+        let selection = self.buffer.selection();
+        let cursor = self.buffer.cursor();
+        
+        let mut line_start = 0;
+        let lines = text.split('\n').map(|line| {
+            let (start, end) = (line_start, line_start + line.len());
+            line_start = end + 1;
+            
+            let row = div().flex().min_h(px(20.0));
+            match selection {
+                // Highlight the part of the selection on this line
+                Some((sel_start, sel_end)) if sel_start <= end && sel_end >= start => {
+                    let from = sel_start.max(start) - start;
+                    let to = sel_end.min(end) - start;
+                    row.child(line[..from].to_string())
+                        .child(div().bg(rgb(0x2f5f7f)).child(line[from..to].to_string()))
+                        .child(line[to..].to_string())
+                }
+                None if (start..=end).contains(&cursor) => {
+                    let at = cursor - start;
+                    row.child(line[..at].to_string())
+                        .child(div().text_color(rgb(0x4fc3f7)).child(caret))
+                        .child(line[at..].to_string())
+                }
+                _ => row.child(line.to_string()),
+            }
+        });
+        
+        div()
+            .flex()
+            .flex_col()
+            .text_color(rgb(0xffffff))
+            .children(lines)
+            .into_any_element()
+    }
+
     fn render_attachment(&self, attachment: &Attachment, index: usize, cx: &mut Context<Self>) -> impl IntoElement {
         let idx = index;
         div()
@@ -187,7 +397,7 @@ impl Render for PromptInput {
         let placeholder = if self.processing {
             self.thinking_text()
         } else {
-            "Type a command... (e.g., 'this is the intro' or 'cut at 0:30')"
+            "Type a command... (e.g., 'this is the intro' or 'cut at 0:30'; Shift+Enter for a new line)"
         };
 
         div()
@@ -210,56 +420,14 @@ impl Render for PromptInput {
             .child(
                 div()
                     .id("prompt-input-row")
+                    .key_context("TextInput")
                     .track_focus(&self.focus_handle)
                     .on_click(cx.listener(|this, _event: &ClickEvent, window, cx| {
                         this.focus_handle.focus(window, cx);
                         cx.notify();
                     }))
                     .on_key_down(cx.listener(|this, event: &KeyDownEvent, _window, cx| {
-                        let key = &event.keystroke.key;
-                        let modifiers = &event.keystroke.modifiers;
-                        
-                        // Handle Ctrl+V / Cmd+V for paste
-                        if key == "v" && (modifiers.control || modifiers.platform) {
-                            if let Some(text) = cx.read_from_clipboard()
-                                .and_then(|item| item.text().map(|s| s.to_string()))
-                            {
-                                this.text.push_str(&text);
-                                cx.notify();
-                            }
-                            return;
-                        }
-                        
-                        // Handle Ctrl+A / Cmd+A for select all (clear and ready for new input)
-                        if key == "a" && (modifiers.control || modifiers.platform) {
-                            // Select all = we'll just highlight behavior, but for now skip
-                            return;
-                        }
-                        
-                        match key.as_str() {
-                            "enter" => {
-                                this.submit(cx);
-                            }
-                            "backspace" => {
-                                this.text.pop();
-                                cx.notify();
-                            }
-                            "escape" => {
-                                this.text.clear();
-                                cx.notify();
-                            }
-                            _ => {
-                                // Handle regular character input via key_char
-                                // Skip if modifier keys are held (except shift)
-                                if modifiers.control || modifiers.platform || modifiers.alt {
-                                    return;
-                                }
-                                if let Some(ch) = &event.keystroke.key_char {
-                                    this.text.push_str(ch);
-                                    cx.notify();
-                                }
-                            }
-                        }
+                        this.handle_key_down(event, cx);
                     }))
                     .flex()
                     .items_center()
@@ -289,16 +457,24 @@ impl Render for PromptInput {
                         div()
                             .flex_1()
                             .min_h(px(20.0))
-                            .child(
-                                div()
-                                    .text_color(if self.text.is_empty() { rgb(0x666666) } else { rgb(0xffffff) })
-                                    .child(if self.text.is_empty() {
-                                        format!("{}{}", placeholder, if is_focused { "│" } else { "" })
-                                    } else {
-                                        format!("{}{}", self.text.clone(), if is_focused { "│" } else { "" })
-                                    }),
-                            ),
+                            .child(self.render_text(placeholder, is_focused)),
                     )
+                    // Cancel button (while processing)
+                    .when(self.processing, |row| {
+                        row.child(
+                            div()
+                                .id("cancel-btn")
+                                .px_2()
+                                .py_1()
+                                .text_color(rgb(0x888888))
+                                .cursor_pointer()
+                                .hover(|s| s.text_color(rgb(0xff6b6b)))
+                                .child("×")
+                                .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                                    this.cancel(cx);
+                                })),
+                        )
+                    })
                     // Submit button
                     .child(
                         div()
@@ -311,7 +487,11 @@ impl Render for PromptInput {
                             .rounded_md()
                             .cursor_pointer()
                             .hover(|s| if self.processing { s } else { s.bg(rgb(0x81d4fa)) })
-                            .child(if self.processing { "⏳" } else { "→" })
+                            .child(match (self.processing, self.streaming) {
+                                (true, true) => "✍",
+                                (true, false) => "⏳",
+                                (false, _) => "→",
+                            })
                             .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
                                 this.submit(cx);
                             })),
@@ -1,44 +1,73 @@
+use serde::Deserialize;
+
+use crate::agent::{AgentBackend, AgentBackendKind, OllamaEndpoint};
+use crate::pexels;
+use crate::transcription::TranscribeOptions;
+
 /// Status of required services
 #[derive(Debug, Clone)]
 pub struct ServiceStatus {
-    pub ollama: OllamaStatus,
+    /// Which service the agent talks to
+    pub backend: AgentBackendKind,
+    pub agent: AgentStatus,
     pub whisper: WhisperStatus,
     pub pexels: PexelsStatus,
 }
 
 #[derive(Debug, Clone)]
-pub enum OllamaStatus {
+pub enum AgentStatus {
+    Checking,
     Ready(String), // model name
+    Cold(String),  // installed but not loaded yet, so the first reply is slow
     NotRunning,
-    NoModel,
+    NoModel(String), // configured model the server doesn't have
+    Rejected,        // API key refused
 }
 
 #[derive(Debug, Clone)]
 pub enum WhisperStatus {
-    Available(String), // which whisper
+    Checking,
+    Available(String), // which whisper, and with which model
     NotInstalled,
 }
 
 #[derive(Debug, Clone)]
 pub enum PexelsStatus {
-    Configured,
+    Checking,
+    Valid,
+    Invalid,
     NotConfigured,
+    Unreachable(String), // why the key couldn't be checked
 }
 
 impl ServiceStatus {
-    /// Check all services
-    pub fn check(pexels_key: &Option<String>) -> Self {
+    /// Check all services (blocks on HTTP and process spawns)
+    pub fn check(pexels_key: &Option<String>, backend: &dyn AgentBackend, whisper: &TranscribeOptions) -> Self {
         Self {
-            ollama: check_ollama(),
-            whisper: check_whisper(),
-            pexels: if pexels_key.as_ref().is_some_and(|k| !k.is_empty()) {
-                PexelsStatus::Configured
-            } else {
-                PexelsStatus::NotConfigured
-            },
+            backend: backend.kind(),
+            agent: backend.check(),
+            whisper: check_whisper(whisper),
+            pexels: check_pexels(pexels_key),
+        }
+    }
+    
+    /// Placeholder shown while `check` runs in the background
+    pub fn checking(pexels_key: &Option<String>, backend: AgentBackendKind) -> Self {
+        Self {
+            backend,
+            agent: AgentStatus::Checking,
+            whisper: WhisperStatus::Checking,
+            pexels: PexelsStatus::checking(pexels_key),
         }
     }
     
+    /// Whether a check is still under way
+    pub fn is_checking(&self) -> bool {
+        matches!(self.agent, AgentStatus::Checking)
+            || matches!(self.whisper, WhisperStatus::Checking)
+            || matches!(self.pexels, PexelsStatus::Checking)
+    }
+    
     /// Generate a greeting message based on status
     pub fn greeting_message(&self) -> String {
         let mut lines = vec![
@@ -48,23 +77,46 @@ impl ServiceStatus {
             String::new(),
         ];
         
-        // Ollama status
-        match &self.ollama {
-            OllamaStatus::Ready(model) => {
-                lines.push(format!("✅ **Ollama**: Ready ({})", model));
+        // Agent backend status
+        let name = self.backend.label();
+        match (&self.agent, self.backend) {
+            (AgentStatus::Checking, _) => {
+                lines.push(format!("⏳ **{}**: Checking…", name));
             }
-            OllamaStatus::NotRunning => {
+            (AgentStatus::Ready(model), _) => {
+                lines.push(format!("✅ **{}**: Ready ({})", name, model));
+            }
+            (AgentStatus::Cold(model), _) => {
+                lines.push(format!("✅ **{}**: Ready ({}, not loaded yet)", name, model));
+                lines.push("   → The first reply loads the model and may take a while".to_string());
+            }
+            (AgentStatus::NotRunning, AgentBackendKind::Ollama) => {
                 lines.push("❌ **Ollama**: Not running".to_string());
                 lines.push("   → Run `ollama serve` in a terminal".to_string());
             }
-            OllamaStatus::NoModel => {
-                lines.push("⚠️ **Ollama**: Running but no model".to_string());
-                lines.push("   → Run `ollama pull qwen2.5:3b`".to_string());
+            (AgentStatus::NotRunning, AgentBackendKind::OpenAiCompatible) => {
+                lines.push(format!("❌ **{}**: Not reachable", name));
+                lines.push("   → Check `openai_base_url` in the config file".to_string());
+            }
+            (AgentStatus::NoModel(model), AgentBackendKind::Ollama) => {
+                lines.push(format!("⚠️ **Ollama**: Running but `{}` is not installed", model));
+                lines.push(format!("   → Run `ollama pull {}`", model));
+            }
+            (AgentStatus::NoModel(model), AgentBackendKind::OpenAiCompatible) => {
+                lines.push(format!("⚠️ **{}**: No model called `{}`", name, model));
+                lines.push("   → Say: \"use model MODEL_NAME\"".to_string());
+            }
+            (AgentStatus::Rejected, _) => {
+                lines.push(format!("❌ **{}**: API key rejected", name));
+                lines.push("   → Check `openai_api_key` in the config file".to_string());
             }
         }
         
         // Whisper status
         match &self.whisper {
+            WhisperStatus::Checking => {
+                lines.push("⏳ **Whisper**: Checking…".to_string());
+            }
             WhisperStatus::Available(which) => {
                 lines.push(format!("✅ **Whisper**: Available ({})", which));
             }
@@ -76,8 +128,18 @@ impl ServiceStatus {
         
         // Pexels status
         match &self.pexels {
-            PexelsStatus::Configured => {
-                lines.push("✅ **Pexels API**: Configured".to_string());
+            PexelsStatus::Checking => {
+                lines.push("⏳ **Pexels API**: Checking key…".to_string());
+            }
+            PexelsStatus::Valid => {
+                lines.push("✅ **Pexels API**: Key valid".to_string());
+            }
+            PexelsStatus::Invalid => {
+                lines.push("❌ **Pexels API**: Key rejected".to_string());
+                lines.push("   → Say: \"set pexels key YOUR_API_KEY\" with a key from pexels.com/api".to_string());
+            }
+            PexelsStatus::Unreachable(reason) => {
+                lines.push(format!("⚠️ **Pexels API**: Couldn't check the key ({})", reason));
             }
             PexelsStatus::NotConfigured => {
                 lines.push("⚠️ **Pexels API**: Not configured (optional)".to_string());
@@ -89,55 +151,150 @@ impl ServiceStatus {
         lines.push(String::new());
         
         // Ready state
-        if matches!(self.ollama, OllamaStatus::Ready(_)) {
+        if self.agent.is_ready() {
             lines.push("🎬 **Ready to edit!** Drag & drop videos or type a command.".to_string());
+        } else if self.is_checking() {
+            lines.push("You can already drag & drop videos while the checks finish.".to_string());
         } else {
-            lines.push("⏳ **Setup needed**: Please start Ollama to use AI features.".to_string());
+            lines.push(format!("⏳ **Setup needed**: {} must be working to use AI features.", name));
+            lines.push("   → Once it is, click any status badge to check again".to_string());
         }
         
         lines.join("\n")
     }
     
-    /// Get quick status indicators for the UI
-    pub fn status_indicators(&self) -> Vec<(String, bool)> {
+    /// Get quick status indicators for the UI: `None` while still checking
+    pub fn status_indicators(&self) -> Vec<(String, Option<bool>)> {
+        let agent = match self.agent {
+            AgentStatus::Checking => None,
+            ref status => Some(status.is_ready()),
+        };
+        let whisper = match self.whisper {
+            WhisperStatus::Checking => None,
+            ref status => Some(matches!(status, WhisperStatus::Available(_))),
+        };
+        let pexels = match self.pexels {
+            PexelsStatus::Checking => None,
+            ref status => Some(matches!(status, PexelsStatus::Valid)),
+        };
         vec![
-            ("Ollama".to_string(), matches!(self.ollama, OllamaStatus::Ready(_))),
-            ("Whisper".to_string(), matches!(self.whisper, WhisperStatus::Available(_))),
-            ("Pexels".to_string(), matches!(self.pexels, PexelsStatus::Configured)),
+            (self.backend.label().to_string(), agent),
+            ("Whisper".to_string(), whisper),
+            ("Pexels".to_string(), pexels),
         ]
     }
 }
 
-/// Check if Ollama is running and has the model
-fn check_ollama() -> OllamaStatus {
+impl AgentStatus {
+    /// Whether requests can be sent, loaded or not
+    pub fn is_ready(&self) -> bool {
+        matches!(self, Self::Ready(_) | Self::Cold(_))
+    }
+}
+
+impl PexelsStatus {
+    /// Placeholder while a key is validated (nothing to check without one)
+    pub fn checking(pexels_key: &Option<String>) -> Self {
+        if pexels_key.as_ref().is_some_and(|k| !k.is_empty()) {
+            Self::Checking
+        } else {
+            Self::NotConfigured
+        }
+    }
+    
+    /// One line for the agent message after a key was set
+    pub fn summary(&self) -> String {
+        match self {
+            Self::Checking => "⏳ Checking Pexels key…".to_string(),
+            Self::Valid => "✓ Pexels key valid".to_string(),
+            Self::Invalid => "✗ Invalid Pexels key".to_string(),
+            Self::NotConfigured => "✗ No Pexels key set".to_string(),
+            Self::Unreachable(reason) => format!("⚠ Couldn't check the Pexels key: {}", reason),
+        }
+    }
+}
+
+/// Ask Pexels whether the key works (blocks on HTTP)
+pub fn check_pexels(pexels_key: &Option<String>) -> PexelsStatus {
+    let Some(key) = pexels_key.as_deref().filter(|k| !k.is_empty()) else {
+        return PexelsStatus::NotConfigured;
+    };
+    match pexels::validate_api_key(key) {
+        Ok(true) => PexelsStatus::Valid,
+        Ok(false) => PexelsStatus::Invalid,
+        Err(e) => PexelsStatus::Unreachable(e.to_string()),
+    }
+}
+
+/// Model list from `/api/tags` (installed) or `/api/ps` (loaded)
+#[derive(Deserialize)]
+pub struct OllamaTags {
+    #[serde(default)]
+    models: Vec<OllamaModel>,
+}
+
+impl OllamaTags {
+    /// Whether the configured model is in the list
+    pub fn has_model(&self, configured: &str) -> bool {
+        self.models.iter().any(|m| is_same_model(&m.name, configured))
+    }
+}
+
+#[derive(Deserialize)]
+struct OllamaModel {
+    name: String,
+}
+
+/// Check whether a model listed by Ollama is the one we're configured to use
+/// (`ollama pull llama3.1` is listed as `llama3.1:latest`)
+fn is_same_model(listed: &str, configured: &str) -> bool {
+    listed == configured
+        || (!configured.contains(':') && listed == format!("{}:latest", configured))
+}
+
+/// Check if Ollama is running and has the configured model, and whether
+/// it's already loaded
+pub fn check_ollama(ollama: &OllamaEndpoint) -> AgentStatus {
     let client = reqwest::blocking::Client::new();
     
     // Check if Ollama is running
     let response = client
-        .get("http://localhost:11434/api/tags")
+        .get(ollama.tags_url())
         .timeout(std::time::Duration::from_secs(2))
         .send();
     
     match response {
         Ok(resp) if resp.status().is_success() => {
             // Check if our model is available
-            if let Ok(body) = resp.text() {
-                if body.contains("qwen2.5") {
-                    return OllamaStatus::Ready("qwen2.5:3b".to_string());
-                } else if body.contains("llama") {
-                    return OllamaStatus::Ready("llama".to_string());
-                }
-            }
-            OllamaStatus::NoModel
+            let has_model = resp
+                .json::<OllamaTags>()
+                .is_ok_and(|tags| tags.has_model(&ollama.model));
+            if !has_model {
+                return AgentStatus::NoModel(ollama.model.clone());
+            }
+            
+            let loaded = client
+                .get(ollama.ps_url())
+                .timeout(std::time::Duration::from_secs(2))
+                .send()
+                .and_then(|resp| resp.json::<OllamaTags>())
+                .is_ok_and(|loaded| loaded.has_model(&ollama.model));
+            if loaded {
+                AgentStatus::Ready(ollama.model.clone())
+            } else {
+                AgentStatus::Cold(ollama.model.clone())
+            }
         }
-        _ => OllamaStatus::NotRunning,
+        _ => AgentStatus::NotRunning,
     }
 }
 
-/// Check if Whisper is installed
-fn check_whisper() -> WhisperStatus {
+/// Check if Whisper is installed, naming the model it will run
+fn check_whisper(options: &TranscribeOptions) -> WhisperStatus {
     use std::process::Command;
     
+    let available = |which: &str| WhisperStatus::Available(format!("{}, {}", which, options.model_label()));
+    
     // Try whisper.cpp
     if Command::new("whisper-cpp")
         .arg("--help")
@@ -145,7 +302,7 @@ fn check_whisper() -> WhisperStatus {
         .map(|o| o.status.success())
         .unwrap_or(false)
     {
-        return WhisperStatus::Available("whisper.cpp".to_string());
+        return available("whisper.cpp");
     }
     
     // Try main (whisper.cpp alternate name)
@@ -155,7 +312,7 @@ fn check_whisper() -> WhisperStatus {
         .map(|o| !o.stderr.is_empty() || !o.stdout.is_empty())
         .unwrap_or(false)
     {
-        return WhisperStatus::Available("whisper.cpp".to_string());
+        return available("whisper.cpp");
     }
     
     // Try Python whisper
@@ -165,7 +322,7 @@ fn check_whisper() -> WhisperStatus {
         .map(|o| o.status.success())
         .unwrap_or(false)
     {
-        return WhisperStatus::Available("openai-whisper".to_string());
+        return available("openai-whisper");
     }
     
     WhisperStatus::NotInstalled
@@ -0,0 +1,422 @@
+/// Editable text with a cursor and an optional selection
+///
+/// Positions are byte offsets that always sit on a char boundary. Lines are
+/// separated by `\n`; home, end, up and down work within them.
+#[derive(Clone, Debug, Default)]
+pub struct TextBuffer {
+    /// Cursor position
+    cursor: usize,
+    /// Other end of the selection (the cursor is the moving end)
+    selection_anchor: Option<usize>,
+    /// Buffer contents
+    text: String,
+}
+
+impl TextBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Delete the character before the cursor (or the selection)
+    pub fn backspace(&mut self) {
+        if self.delete_selection() {
+            return;
+        }
+        if let Some(prev) = self.prev_boundary(self.cursor) {
+            self.text.replace_range(prev..self.cursor, "");
+            self.cursor = prev;
+        }
+    }
+
+    /// Remove all text
+    pub fn clear(&mut self) {
+        self.cursor = 0;
+        self.selection_anchor = None;
+        self.text.clear();
+    }
+
+    /// Cursor position in bytes
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Delete the character after the cursor (or the selection)
+    pub fn delete(&mut self) {
+        if self.delete_selection() {
+            return;
+        }
+        if let Some(next) = self.next_boundary(self.cursor) {
+            self.text.replace_range(self.cursor..next, "");
+        }
+    }
+
+    /// Delete the word before the cursor, along with any spaces after it
+    pub fn delete_word_backward(&mut self) {
+        if self.delete_selection() {
+            return;
+        }
+
+        let before = &self.text[..self.cursor];
+        let trimmed = before.trim_end();
+        let word_start = trimmed
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map(|(i, c)| i + c.len_utf8())
+            .unwrap_or(0);
+
+        self.text.replace_range(word_start..self.cursor, "");
+        self.cursor = word_start;
+    }
+
+    /// Insert text at the cursor, replacing the selection if there is one
+    pub fn insert(&mut self, text: &str) {
+        self.delete_selection();
+        self.text.insert_str(self.cursor, text);
+        self.cursor += text.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Whether the cursor is on the first line
+    pub fn on_first_line(&self) -> bool {
+        self.line_start(self.cursor) == 0
+    }
+
+    /// Whether the cursor is on the last line
+    pub fn on_last_line(&self) -> bool {
+        self.line_end(self.cursor) == self.text.len()
+    }
+
+    /// Move the cursor to the next line, keeping its column where it can
+    pub fn move_down(&mut self) {
+        self.selection_anchor = None;
+        let line_end = self.line_end(self.cursor);
+        if line_end == self.text.len() {
+            self.cursor = line_end;
+            return;
+        }
+        let column = self.column();
+        self.cursor = self.column_in_line(line_end + 1, column);
+    }
+
+    /// Move the cursor to the end of its line
+    pub fn move_end(&mut self) {
+        self.selection_anchor = None;
+        self.cursor = self.line_end(self.cursor);
+    }
+
+    /// Move the cursor to the start of its line
+    pub fn move_home(&mut self) {
+        self.selection_anchor = None;
+        self.cursor = self.line_start(self.cursor);
+    }
+
+    /// Move the cursor one character left (or to the start of the selection)
+    pub fn move_left(&mut self) {
+        if let Some((start, _)) = self.selection() {
+            self.selection_anchor = None;
+            self.cursor = start;
+            return;
+        }
+        if let Some(prev) = self.prev_boundary(self.cursor) {
+            self.cursor = prev;
+        }
+    }
+
+    /// Move the cursor one character right (or to the end of the selection)
+    pub fn move_right(&mut self) {
+        if let Some((_, end)) = self.selection() {
+            self.selection_anchor = None;
+            self.cursor = end;
+            return;
+        }
+        if let Some(next) = self.next_boundary(self.cursor) {
+            self.cursor = next;
+        }
+    }
+
+    /// Move the cursor to the previous line, keeping its column where it can
+    pub fn move_up(&mut self) {
+        self.selection_anchor = None;
+        let line_start = self.line_start(self.cursor);
+        if line_start == 0 {
+            self.cursor = 0;
+            return;
+        }
+        let column = self.column();
+        self.cursor = self.column_in_line(self.line_start(line_start - 1), column);
+    }
+
+    /// Select the whole text
+    pub fn select_all(&mut self) {
+        if self.text.is_empty() {
+            return;
+        }
+        self.selection_anchor = Some(0);
+        self.cursor = self.text.len();
+    }
+
+    /// Selected byte range, ordered, if anything is selected
+    pub fn selection(&self) -> Option<(usize, usize)> {
+        let anchor = self.selection_anchor?;
+        if anchor == self.cursor {
+            return None;
+        }
+        Some((anchor.min(self.cursor), anchor.max(self.cursor)))
+    }
+
+    /// Grow the selection to the end of the line
+    pub fn select_end(&mut self) {
+        self.extend_to(self.line_end(self.cursor));
+    }
+
+    /// Grow the selection to the start of the line
+    pub fn select_home(&mut self) {
+        self.extend_to(self.line_start(self.cursor));
+    }
+
+    /// Grow the selection one character left
+    pub fn select_left(&mut self) {
+        if let Some(prev) = self.prev_boundary(self.cursor) {
+            self.extend_to(prev);
+        }
+    }
+
+    /// Grow the selection one character right
+    pub fn select_right(&mut self) {
+        if let Some(next) = self.next_boundary(self.cursor) {
+            self.extend_to(next);
+        }
+    }
+
+    /// Replace the whole text and put the cursor at the end
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.cursor = self.text.len();
+        self.selection_anchor = None;
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Remove the selected text; returns whether anything was selected
+    fn delete_selection(&mut self) -> bool {
+        let Some((start, end)) = self.selection() else {
+            self.selection_anchor = None;
+            return false;
+        };
+        self.text.replace_range(start..end, "");
+        self.cursor = start;
+        self.selection_anchor = None;
+        true
+    }
+
+    /// Cursor's column, in chars from the start of its line
+    fn column(&self) -> usize {
+        self.text[self.line_start(self.cursor)..self.cursor].chars().count()
+    }
+
+    /// Offset `column` chars into the line starting at `start`, or that line's end
+    fn column_in_line(&self, start: usize, column: usize) -> usize {
+        let end = self.line_end(start);
+        self.text[start..end]
+            .char_indices()
+            .nth(column)
+            .map(|(i, _)| start + i)
+            .unwrap_or(end)
+    }
+
+    /// Move the cursor, leaving the anchor where the selection started
+    fn extend_to(&mut self, index: usize) {
+        self.selection_anchor.get_or_insert(self.cursor);
+        self.cursor = index;
+    }
+
+    fn line_end(&self, index: usize) -> usize {
+        self.text[index..].find('\n').map(|i| index + i).unwrap_or(self.text.len())
+    }
+
+    fn line_start(&self, index: usize) -> usize {
+        self.text[..index].rfind('\n').map(|i| i + 1).unwrap_or(0)
+    }
+
+    fn next_boundary(&self, index: usize) -> Option<usize> {
+        self.text[index..].chars().next().map(|c| index + c.len_utf8())
+    }
+
+    fn prev_boundary(&self, index: usize) -> Option<usize> {
+        self.text[..index].chars().next_back().map(|c| index - c.len_utf8())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buffer(text: &str) -> TextBuffer {
+        let mut buffer = TextBuffer::new();
+        buffer.set_text(text);
+        buffer
+    }
+
+    #[test]
+    fn test_insert_in_the_middle() {
+        let mut buffer = buffer("helo");
+        buffer.move_left();
+        buffer.move_left();
+        buffer.insert("l");
+
+        assert_eq!(buffer.text(), "hello");
+        assert_eq!(buffer.cursor(), 3);
+    }
+
+    #[test]
+    fn test_backspace_and_delete_around_cursor() {
+        let mut buffer = buffer("abc");
+        buffer.move_left();
+        buffer.backspace();
+        assert_eq!(buffer.text(), "ac");
+
+        buffer.delete();
+        assert_eq!(buffer.text(), "a");
+
+        buffer.move_home();
+        buffer.backspace();
+        assert_eq!(buffer.text(), "a");
+    }
+
+    #[test]
+    fn test_multibyte_characters() {
+        let mut buffer = buffer("café");
+        buffer.move_left();
+        assert_eq!(buffer.cursor(), 3);
+
+        buffer.move_right();
+        buffer.backspace();
+        assert_eq!(buffer.text(), "caf");
+    }
+
+    #[test]
+    fn test_home_and_end() {
+        let mut buffer = buffer("cut here");
+        buffer.move_home();
+        buffer.insert(">");
+        buffer.move_end();
+        buffer.insert("<");
+
+        assert_eq!(buffer.text(), ">cut here<");
+    }
+
+    #[test]
+    fn test_select_all_then_delete() {
+        let mut buffer = buffer("remove me");
+        buffer.select_all();
+        assert_eq!(buffer.selection(), Some((0, 9)));
+
+        buffer.backspace();
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.selection(), None);
+    }
+
+    #[test]
+    fn test_typing_replaces_selection() {
+        let mut buffer = buffer("old text");
+        buffer.select_all();
+        buffer.insert("new");
+
+        assert_eq!(buffer.text(), "new");
+        assert_eq!(buffer.cursor(), 3);
+    }
+
+    #[test]
+    fn test_arrow_collapses_selection() {
+        let mut buffer = buffer("abc");
+        buffer.select_all();
+        buffer.move_left();
+
+        assert_eq!(buffer.cursor(), 0);
+        assert_eq!(buffer.selection(), None);
+    }
+
+    #[test]
+    fn test_delete_word_backward() {
+        let mut buffer = buffer("move the intro  ");
+        buffer.delete_word_backward();
+        assert_eq!(buffer.text(), "move the ");
+
+        buffer.delete_word_backward();
+        assert_eq!(buffer.text(), "move ");
+
+        buffer.delete_word_backward();
+        buffer.delete_word_backward();
+        assert_eq!(buffer.text(), "");
+    }
+
+    #[test]
+    fn test_paste_at_cursor() {
+        let mut buffer = buffer("set key ");
+        buffer.move_home();
+        buffer.insert("pexels: ");
+
+        assert_eq!(buffer.text(), "pexels: set key ");
+    }
+
+    #[test]
+    fn test_up_and_down_keep_the_column() {
+        let mut buffer = buffer("first line\nab\nthird line");
+        buffer.move_up();
+        assert_eq!(buffer.cursor(), 13); // end of "ab"
+
+        buffer.move_up();
+        assert_eq!(buffer.cursor(), 2);
+
+        buffer.move_down();
+        buffer.move_down();
+        assert_eq!(buffer.cursor(), 16);
+
+        buffer.move_down();
+        assert_eq!(buffer.cursor(), buffer.text().len());
+    }
+
+    #[test]
+    fn test_first_and_last_line() {
+        let mut buffer = buffer("one\ntwo");
+        assert!(buffer.on_last_line());
+        assert!(!buffer.on_first_line());
+
+        buffer.move_up();
+        assert!(buffer.on_first_line());
+        assert!(!buffer.on_last_line());
+
+        assert!(TextBuffer::new().on_first_line());
+    }
+
+    #[test]
+    fn test_home_and_end_stay_on_the_line() {
+        let mut buffer = buffer("one\ntwo");
+        buffer.move_home();
+        assert_eq!(buffer.cursor(), 4);
+
+        buffer.move_up();
+        buffer.move_end();
+        assert_eq!(buffer.cursor(), 3);
+    }
+
+    #[test]
+    fn test_shift_selection() {
+        let mut buffer = buffer("hello world");
+        buffer.select_left();
+        buffer.select_left();
+        assert_eq!(buffer.selection(), Some((9, 11)));
+
+        buffer.select_home();
+        assert_eq!(buffer.selection(), Some((0, 11)));
+
+        buffer.insert("bye");
+        assert_eq!(buffer.text(), "bye");
+    }
+}
@@ -0,0 +1,108 @@
+use anyhow::{Context, Result};
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+use gstreamer_video as gst_video;
+use image::{ImageBuffer, Rgba};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// Where in the source the frame is taken (seconds)
+const FRAME_AT_SECONDS: f64 = 1.0;
+
+/// Thumbnail size in pixels
+pub const WIDTH: u32 = 160;
+pub const HEIGHT: u32 = 90;
+
+/// Cached thumbnail location for a video file
+///
+/// The name hashes the path and modification time, so a file that changes
+/// on disk gets a fresh thumbnail.
+pub fn cache_path(source: &Path) -> Option<PathBuf> {
+    let modified = std::fs::metadata(source).ok()?.modified().ok()?;
+    let mut hasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+    modified.hash(&mut hasher);
+    
+    let dir = dirs::home_dir()?.join(".montage").join("thumbnails");
+    Some(dir.join(format!("{:016x}.png", hasher.finish())))
+}
+
+/// Return the thumbnail for a video, extracting it if it isn't cached yet
+///
+/// Runs a GStreamer pipeline, so call it off the UI thread.
+pub fn get_or_create(source: &Path) -> Result<PathBuf> {
+    let output = cache_path(source).context("No thumbnail location for this file")?;
+    if output.exists() {
+        return Ok(output);
+    }
+    
+    if let Some(dir) = output.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    extract(source, &output)?;
+    Ok(output)
+}
+
+/// Grab the frame at `FRAME_AT_SECONDS`, letterboxed to `WIDTH`x`HEIGHT`, and save it as a PNG
+///
+/// Clips shorter than that keep their first frame.
+fn extract(path: &Path, output: &Path) -> Result<()> {
+    gst::init()?;
+    
+    let uri = format!("file://{}", path.canonicalize()?.display());
+    let pipeline_str = format!(
+        "uridecodebin uri=\"{}\" ! videoconvert ! videoscale add-borders=true ! \
+         video/x-raw,format=RGBA,width={},height={},pixel-aspect-ratio=1/1 ! \
+         appsink name=sink max-buffers=1 drop=true",
+        uri, WIDTH, HEIGHT
+    );
+    
+    let pipeline = gst::parse::launch(&pipeline_str)
+        .context("Failed to create thumbnail pipeline")?
+        .downcast::<gst::Pipeline>()
+        .map_err(|_| anyhow::anyhow!("Not a pipeline"))?;
+    let sink = pipeline
+        .by_name("sink")
+        .and_then(|e| e.downcast::<gst_app::AppSink>().ok())
+        .context("Thumbnail pipeline has no appsink")?;
+    
+    // Preroll, then seek so the preroll buffer is the frame we want
+    pipeline.set_state(gst::State::Paused)?;
+    let (state_result, _, _) = pipeline.state(gst::ClockTime::from_seconds(5));
+    let frame = state_result
+        .map_err(|_| anyhow::anyhow!("Video did not preroll"))
+        .and_then(|_| {
+            let _ = pipeline.seek_simple(
+                gst::SeekFlags::FLUSH | gst::SeekFlags::KEY_UNIT,
+                gst::ClockTime::from_mseconds((FRAME_AT_SECONDS * 1000.0) as u64),
+            );
+            let _ = pipeline.state(gst::ClockTime::from_seconds(5));
+            sink.try_pull_preroll(gst::ClockTime::from_seconds(5))
+                .context("No frame decoded")
+        });
+    let _ = pipeline.set_state(gst::State::Null);
+    let sample = frame?;
+    
+    let buffer = sample.buffer().context("Sample has no buffer")?;
+    let caps = sample.caps().context("Sample has no caps")?;
+    let info = gst_video::VideoInfo::from_caps(caps)?;
+    let map = buffer.map_readable()?;
+    
+    // Rows may be padded; copy only the visible pixels
+    let row_bytes = info.width() as usize * 4;
+    let stride = info.stride()[0] as usize;
+    let data: Vec<u8> = map
+        .as_slice()
+        .chunks(stride)
+        .take(info.height() as usize)
+        .flat_map(|row| &row[..row_bytes])
+        .copied()
+        .collect();
+    
+    let image: ImageBuffer<Rgba<u8>, Vec<u8>> = ImageBuffer::from_raw(info.width(), info.height(), data)
+        .context("Frame size does not match its caps")?;
+    image.save(output).context("Failed to save thumbnail")?;
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 /// A segment of transcribed audio with timing
@@ -12,6 +12,17 @@ pub struct TranscriptSegment {
     pub end: f64,
     /// Transcribed text
     pub text: String,
+    /// Per-word timing, when the transcriber gave it
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<Word>,
+}
+
+/// One word of a segment with its own timing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Word {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
 }
 
 /// Full transcript with segments
@@ -27,21 +38,346 @@ pub struct Transcript {
     pub duration: f64,
 }
 
+impl Transcript {
+    /// The part between `start` and `end` seconds, retimed to begin at zero;
+    /// segments crossing either edge are clipped to it
+    pub fn trimmed(&self, start: f64, end: f64) -> Transcript {
+        let segments: Vec<TranscriptSegment> = self.segments
+            .iter()
+            .filter(|s| s.end > start && s.start < end)
+            .map(|s| TranscriptSegment {
+                start: s.start.max(start) - start,
+                end: s.end.min(end) - start,
+                text: s.text.clone(),
+                words: s.words
+                    .iter()
+                    .filter(|w| w.end > start && w.start < end)
+                    .map(|w| Word {
+                        start: w.start.max(start) - start,
+                        end: w.end.min(end) - start,
+                        text: w.text.clone(),
+                    })
+                    .collect(),
+            })
+            .collect();
+        Transcript {
+            text: segments.iter().map(|s| s.text.trim()).collect::<Vec<_>>().join(" "),
+            segments,
+            language: self.language.clone(),
+            duration: (end.min(self.duration) - start).max(0.0),
+        }
+    }
+    
+    /// The same transcript with every time passed through `at`, as when
+    /// stretches of the audio are cut; segments left with no length are dropped
+    pub fn retimed(&self, at: impl Fn(f64) -> f64) -> Transcript {
+        let segments: Vec<TranscriptSegment> = self.segments
+            .iter()
+            .map(|s| TranscriptSegment {
+                start: at(s.start),
+                end: at(s.end),
+                text: s.text.clone(),
+                words: s.words
+                    .iter()
+                    .map(|w| Word { start: at(w.start), end: at(w.end), text: w.text.clone() })
+                    .filter(|w| w.end > w.start)
+                    .collect(),
+            })
+            .filter(|s| s.end > s.start)
+            .collect();
+        Transcript {
+            text: segments.iter().map(|s| s.text.trim()).collect::<Vec<_>>().join(" "),
+            segments,
+            language: self.language.clone(),
+            duration: at(self.duration),
+        }
+    }
+    
+    /// The same transcript with no segment longer than `max_seconds`,
+    /// long ones split between words
+    pub fn resegmented(&self, max_seconds: f64) -> Transcript {
+        Transcript {
+            segments: self.segments
+                .iter()
+                .flat_map(|s| split_segment(s, max_seconds))
+                .collect(),
+            ..self.clone()
+        }
+    }
+}
+
+/// Split a segment into pieces of at most `max_seconds` (unless a single
+/// word runs longer). Without word timing, each word's time is guessed
+/// from its share of the text.
+fn split_segment(segment: &TranscriptSegment, max_seconds: f64) -> Vec<TranscriptSegment> {
+    if segment.end - segment.start <= max_seconds {
+        return vec![segment.clone()];
+    }
+    let words = if segment.words.is_empty() {
+        estimate_words(segment)
+    } else {
+        segment.words.clone()
+    };
+    
+    let mut pieces: Vec<Vec<Word>> = Vec::new();
+    let mut piece_start = segment.start;
+    for word in words {
+        match pieces.last_mut() {
+            Some(piece) if word.end - piece_start <= max_seconds => piece.push(word),
+            Some(_) => {
+                piece_start = word.start;
+                pieces.push(vec![word]);
+            }
+            None => pieces.push(vec![word]),
+        }
+    }
+    if pieces.len() < 2 {
+        return vec![segment.clone()];
+    }
+    
+    // Pieces run on into each other so the segment's span stays covered
+    let starts: Vec<f64> = std::iter::once(segment.start)
+        .chain(pieces[1..].iter().map(|p| p[0].start))
+        .collect();
+    pieces
+        .into_iter()
+        .enumerate()
+        .map(|(i, words)| TranscriptSegment {
+            start: starts[i],
+            end: starts.get(i + 1).copied().unwrap_or(segment.end),
+            text: words.iter().map(|w| w.text.trim()).collect::<Vec<_>>().join(" "),
+            words,
+        })
+        .collect()
+}
+
+/// Spread a segment's time over its words by length
+fn estimate_words(segment: &TranscriptSegment) -> Vec<Word> {
+    let words: Vec<&str> = segment.text.split_whitespace().collect();
+    let letters: usize = words.iter().map(|w| w.chars().count() + 1).sum();
+    let per_letter = (segment.end - segment.start) / letters.max(1) as f64;
+    
+    let mut time = segment.start;
+    words
+        .into_iter()
+        .map(|text| {
+            let start = time;
+            time += (text.chars().count() + 1) as f64 * per_letter;
+            Word { start, end: time, text: text.to_string() }
+        })
+        .collect()
+}
+
+/// Shortest time a subtitle stays on screen when a segment has no duration
+const MIN_CUE_SECONDS: f64 = 0.5;
+
+/// Longest subtitle line before wrapping (the usual broadcast limit)
+const MAX_LINE_CHARS: usize = 42;
+
+/// Subtitle file formats the transcript can be written as
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SubtitleFormat {
+    Srt,
+    Vtt,
+}
+
+impl SubtitleFormat {
+    /// File extension, without the dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Srt => "srt",
+            Self::Vtt => "vtt",
+        }
+    }
+    
+    /// Parse a user-provided format name ("srt", "VTT", "webvtt"...)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "srt" => Some(Self::Srt),
+            "vtt" | "webvtt" => Some(Self::Vtt),
+            _ => None,
+        }
+    }
+    
+    /// Render a transcript in this format
+    pub fn render(&self, transcript: &Transcript) -> String {
+        match self {
+            Self::Srt => to_srt(transcript),
+            Self::Vtt => to_vtt(transcript),
+        }
+    }
+}
+
+/// Render a transcript as SubRip subtitles
+pub fn to_srt(transcript: &Transcript) -> String {
+    let mut out = String::new();
+    for (i, (start, end, text)) in cues(transcript).into_iter().enumerate() {
+        out.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            i + 1,
+            format_timestamp(start, ','),
+            format_timestamp(end, ','),
+            wrap_text(text, MAX_LINE_CHARS),
+        ));
+    }
+    out
+}
+
+/// Render a transcript as WebVTT subtitles
+pub fn to_vtt(transcript: &Transcript) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+    for (start, end, text) in cues(transcript) {
+        out.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            format_timestamp(start, '.'),
+            format_timestamp(end, '.'),
+            wrap_text(text, MAX_LINE_CHARS),
+        ));
+    }
+    out
+}
+
+/// One "[m:ss - m:ss] text" line per segment, for showing in the app
+pub fn to_lines(transcript: &Transcript) -> Vec<String> {
+    let clock = |seconds: f64| {
+        let seconds = seconds.max(0.0).round() as u64;
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    };
+    transcript
+        .segments
+        .iter()
+        .filter(|s| !s.text.trim().is_empty())
+        .map(|s| format!("[{} - {}] {}", clock(s.start), clock(s.end), s.text.trim()))
+        .collect()
+}
+
+/// Turn segments into displayable cues: empty segments are dropped,
+/// zero-length ones get a minimum duration and overlapping ones are cut
+/// where the next segment starts
+fn cues(transcript: &Transcript) -> Vec<(f64, f64, &str)> {
+    let segments: Vec<&TranscriptSegment> = transcript.segments
+        .iter()
+        .filter(|s| !s.text.trim().is_empty())
+        .collect();
+    
+    segments
+        .iter()
+        .enumerate()
+        .map(|(i, segment)| {
+            let start = segment.start.max(0.0);
+            let mut end = segment.end;
+            if end <= start {
+                end = start + MIN_CUE_SECONDS;
+            }
+            if let Some(next) = segments.get(i + 1)
+                && next.start > start
+            {
+                end = end.min(next.start);
+            }
+            (start, end, segment.text.trim())
+        })
+        .collect()
+}
+
+/// Break text into lines of at most `max_chars` characters at word boundaries
+/// (a single longer word gets a line of its own)
+fn wrap_text(text: &str, max_chars: usize) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > max_chars {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    
+    lines.join("\n")
+}
+
+/// Format seconds as `HH:MM:SS<sep>mmm` (SRT uses ',', VTT uses '.')
+fn format_timestamp(seconds: f64, separator: char) -> String {
+    let total_ms = (seconds.max(0.0) * 1000.0).round() as u64;
+    let hours = total_ms / 3_600_000;
+    let minutes = (total_ms / 60_000) % 60;
+    let secs = (total_ms / 1000) % 60;
+    let millis = total_ms % 1000;
+    format!("{:02}:{:02}:{:02}{}{:03}", hours, minutes, secs, separator, millis)
+}
+
+/// Which whisper model to run and how the transcript should be cut up
+#[derive(Debug, Clone, Default)]
+pub struct TranscribeOptions {
+    /// Split segments longer than this many seconds between words
+    pub max_segment_seconds: Option<f64>,
+    /// Ask whisper for per-word timing, so splits land on real word edges
+    pub word_timestamps: bool,
+    /// Model name ("small", "medium.en"...); `base` when unset
+    pub model: Option<String>,
+    /// whisper.cpp model file, used instead of looking `model` up
+    pub model_path: Option<PathBuf>,
+    /// Spoken language ("fr", "French"...); whisper detects it when unset
+    pub language: Option<String>,
+}
+
+/// Where whisper.cpp builds usually install their ggml models
+const WHISPER_CPP_MODEL_DIR: &str = "/usr/local/share/whisper";
+
+impl TranscribeOptions {
+    /// Model name to load. The English-only default can't transcribe
+    /// anything else, so another language gets the multilingual one.
+    fn model_name(&self, english_default: bool) -> String {
+        self.model.clone().unwrap_or_else(|| {
+            let english = self.language.as_deref().is_none_or(|l| {
+                matches!(l.to_lowercase().as_str(), "en" | "english")
+            });
+            if english && english_default { "base.en" } else { "base" }.to_string()
+        })
+    }
+    
+    /// whisper.cpp's `-m` argument: the configured file, or the ggml file
+    /// for the model name
+    fn whisper_cpp_model(&self) -> PathBuf {
+        self.model_path.clone().unwrap_or_else(|| {
+            Path::new(WHISPER_CPP_MODEL_DIR).join(format!("ggml-{}.bin", self.model_name(true)))
+        })
+    }
+    
+    /// Model shown in the service status
+    pub fn model_label(&self) -> String {
+        match &self.model_path {
+            Some(path) => path.file_name().unwrap_or(path.as_os_str()).to_string_lossy().into_owned(),
+            None => self.model_name(false),
+        }
+    }
+}
+
+/// Roughly how many characters of speech whisper.cpp hears per second,
+/// to turn a segment length into its `-ml` character limit
+const SPOKEN_CHARS_PER_SECOND: f64 = 15.0;
+
 /// Transcribe an audio file using Whisper
 /// 
 /// Tries multiple methods:
 /// 1. whisper-cpp CLI if installed
 /// 2. Ollama with whisper model (if available)
 /// 3. Python whisper as fallback
-pub fn transcribe(audio_path: &Path) -> Result<Transcript> {
-    // Try whisper.cpp first (fastest)
-    if let Ok(transcript) = transcribe_with_whisper_cpp(audio_path) {
-        return Ok(transcript);
-    }
+pub fn transcribe(audio_path: &Path, options: &TranscribeOptions) -> Result<Transcript> {
+    // Try whisper.cpp first (fastest), then insanely-fast-whisper or whisper CLI
+    let transcript = transcribe_with_whisper_cpp(audio_path, options)
+        .or_else(|_| transcribe_with_whisper_cli(audio_path, options));
     
-    // Try insanely-fast-whisper or whisper CLI
-    if let Ok(transcript) = transcribe_with_whisper_cli(audio_path) {
-        return Ok(transcript);
+    if let Ok(transcript) = transcript {
+        return Ok(match options.max_segment_seconds {
+            Some(max) => transcript.resegmented(max),
+            None => transcript,
+        });
     }
     
     anyhow::bail!(
@@ -53,25 +389,41 @@ pub fn transcribe(audio_path: &Path) -> Result<Transcript> {
 }
 
 /// Transcribe using whisper.cpp CLI
-fn transcribe_with_whisper_cpp(audio_path: &Path) -> Result<Transcript> {
+fn transcribe_with_whisper_cpp(audio_path: &Path, options: &TranscribeOptions) -> Result<Transcript> {
+    // Have whisper.cpp keep segments short itself, breaking only between words
+    let max_len = options.max_segment_seconds
+        .map(|max| ((max * SPOKEN_CHARS_PER_SECOND).round() as u32).to_string());
+    let mut extra_args: Vec<&str> = match &max_len {
+        Some(chars) => vec!["-ml", chars, "-sow"],
+        None => Vec::new(),
+    };
+    if let Some(language) = &options.language {
+        extra_args.extend(["-l", language.as_str()]);
+    }
+    let model = options.whisper_cpp_model();
+    
     // whisper.cpp outputs JSON with -oj flag
     let output = Command::new("whisper-cpp")
+        .arg("-m")
+        .arg(&model)
         .args([
-            "-m", "base.en",  // or path to model
             "-f", &audio_path.to_string_lossy(),
             "-oj",  // output JSON
             "--print-progress", "false",
         ])
+        .args(&extra_args)
         .output();
     
     // Also try "main" binary name (common whisper.cpp build name)
     let output = output.or_else(|_| {
         Command::new("main")
+            .arg("-m")
+            .arg(&model)
             .args([
-                "-m", "/usr/local/share/whisper/ggml-base.en.bin",
                 "-f", &audio_path.to_string_lossy(),
                 "-oj",
             ])
+            .args(&extra_args)
             .output()
     })?;
     
@@ -85,7 +437,7 @@ fn transcribe_with_whisper_cpp(audio_path: &Path) -> Result<Transcript> {
 }
 
 /// Transcribe using Python whisper CLI
-fn transcribe_with_whisper_cli(audio_path: &Path) -> Result<Transcript> {
+fn transcribe_with_whisper_cli(audio_path: &Path, options: &TranscribeOptions) -> Result<Transcript> {
     // Create temp dir for output
     let temp_dir = std::env::temp_dir().join("montage_whisper");
     std::fs::create_dir_all(&temp_dir)?;
@@ -93,10 +445,16 @@ fn transcribe_with_whisper_cli(audio_path: &Path) -> Result<Transcript> {
     let output = Command::new("whisper")
         .args([
             &audio_path.to_string_lossy(),
-            "--model", "base",
+            "--model", &options.model_name(false),
             "--output_format", "json",
             "--output_dir", &temp_dir.to_string_lossy(),
         ])
+        .args(if options.word_timestamps {
+            &["--word_timestamps", "True"][..]
+        } else {
+            &[]
+        })
+        .args(options.language.iter().flat_map(|language| ["--language", language.as_str()]))
         .output()?;
     
     if !output.status.success() {
@@ -133,6 +491,15 @@ fn parse_whisper_json(json_str: &str) -> Result<Transcript> {
         start: f64,
         end: f64,
         text: String,
+        #[serde(default)]
+        words: Vec<WhisperWord>,
+    }
+    
+    #[derive(Deserialize)]
+    struct WhisperWord {
+        start: f64,
+        end: f64,
+        word: String,
     }
     
     let output: WhisperOutput = serde_json::from_str(json_str)
@@ -148,6 +515,11 @@ fn parse_whisper_json(json_str: &str) -> Result<Transcript> {
             start: s.start,
             end: s.end,
             text: s.text.trim().to_string(),
+            words: s.words.into_iter().map(|w| Word {
+                start: w.start,
+                end: w.end,
+                text: w.word.trim().to_string(),
+            }).collect(),
         }).collect(),
         language: output.language,
         duration,
@@ -168,3 +540,150 @@ pub fn is_available() -> bool {
         .map(|o| o.status.success())
         .unwrap_or(false)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn transcript(segments: &[(f64, f64, &str)]) -> Transcript {
+        Transcript {
+            text: String::new(),
+            segments: segments
+                .iter()
+                .map(|&(start, end, text)| TranscriptSegment {
+                    start,
+                    end,
+                    text: text.to_string(),
+                    words: Vec::new(),
+                })
+                .collect(),
+            language: None,
+            duration: segments.last().map(|s| s.1).unwrap_or(0.0),
+        }
+    }
+    
+    #[test]
+    fn test_timestamp_formatting() {
+        assert_eq!(format_timestamp(0.0, ','), "00:00:00,000");
+        assert_eq!(format_timestamp(61.5, ','), "00:01:01,500");
+        assert_eq!(format_timestamp(3723.0456, '.'), "01:02:03.046");
+        assert_eq!(format_timestamp(-1.0, ','), "00:00:00,000");
+    }
+    
+    #[test]
+    fn test_transcript_lines() {
+        let lines = to_lines(&transcript(&[(0.0, 4.2, " Welcome back "), (4.2, 4.2, " "), (65.0, 70.6, "Next")]));
+        assert_eq!(lines, vec!["[0:00 - 0:04] Welcome back", "[1:05 - 1:11] Next"]);
+    }
+    
+    #[test]
+    fn test_trimmed_transcript_starts_at_zero() {
+        let trimmed = transcript(&[(0.0, 4.0, "Intro"), (4.0, 12.0, "Middle"), (12.0, 20.0, "End")]).trimmed(10.0, 15.0);
+        let times: Vec<(f64, f64)> = trimmed.segments.iter().map(|s| (s.start, s.end)).collect();
+        
+        assert_eq!(times, vec![(0.0, 2.0), (2.0, 5.0)]);
+        assert_eq!(trimmed.text, "Middle End");
+        assert_eq!(trimmed.duration, 5.0);
+    }
+    
+    #[test]
+    fn test_retimed_transcript_drops_empty_segments() {
+        // As if 4s to 6s of the audio were cut
+        let cut = |t: f64| if t < 4.0 { t } else { (t - 2.0).max(4.0) };
+        let retimed = transcript(&[(0.0, 4.0, "Before"), (4.0, 6.0, "Gone"), (5.0, 9.0, "After")]).retimed(cut);
+        let times: Vec<(f64, f64)> = retimed.segments.iter().map(|s| (s.start, s.end)).collect();
+        
+        assert_eq!(times, vec![(0.0, 4.0), (4.0, 7.0)]);
+        assert_eq!(retimed.text, "Before After");
+        assert_eq!(retimed.duration, 7.0);
+    }
+    
+    #[test]
+    fn test_srt_output() {
+        let srt = to_srt(&transcript(&[(0.0, 1.5, " Hello "), (1.5, 3.0, "world")]));
+        
+        assert_eq!(
+            srt,
+            "1\n00:00:00,000 --> 00:00:01,500\nHello\n\n\
+             2\n00:00:01,500 --> 00:00:03,000\nworld\n\n"
+        );
+    }
+    
+    #[test]
+    fn test_vtt_output() {
+        let vtt = to_vtt(&transcript(&[(2.0, 4.25, "Hi")]));
+        
+        assert_eq!(vtt, "WEBVTT\n\n00:00:02.000 --> 00:00:04.250\nHi\n\n");
+    }
+    
+    #[test]
+    fn test_zero_duration_segment_gets_minimum_length() {
+        let cues = cues(&transcript(&[(5.0, 5.0, "blink")]));
+        
+        assert_eq!(cues, vec![(5.0, 5.0 + MIN_CUE_SECONDS, "blink")]);
+    }
+    
+    #[test]
+    fn test_overlapping_segments_are_trimmed() {
+        let cues = cues(&transcript(&[(0.0, 4.0, "first"), (3.0, 6.0, "second")]));
+        
+        assert_eq!(cues, vec![(0.0, 3.0, "first"), (3.0, 6.0, "second")]);
+    }
+    
+    #[test]
+    fn test_long_lines_wrap_at_word_boundaries() {
+        let text = "this subtitle is much too long to fit on a single line of video";
+        let wrapped = wrap_text(text, MAX_LINE_CHARS);
+        
+        assert_eq!(
+            wrapped,
+            "this subtitle is much too long to fit on a\nsingle line of video"
+        );
+        assert!(wrapped.lines().all(|l| l.chars().count() <= MAX_LINE_CHARS));
+    }
+    
+    #[test]
+    fn test_empty_segments_are_skipped() {
+        let srt = to_srt(&transcript(&[(0.0, 1.0, "  "), (1.0, 2.0, "kept")]));
+        
+        assert!(srt.starts_with("1\n00:00:01,000"));
+        assert!(!srt.contains("2\n"));
+    }
+    
+    #[test]
+    fn test_long_segment_splits_at_word_boundaries() {
+        let mut long = transcript(&[(0.0, 20.0, "one two three four five six seven eight nine ten")]);
+        long.segments[0].words = long.segments[0].text
+            .split(' ')
+            .enumerate()
+            .map(|(i, text)| Word {
+                start: i as f64 * 2.0,
+                end: i as f64 * 2.0 + 2.0,
+                text: text.to_string(),
+            })
+            .collect();
+        
+        let split = long.resegmented(6.0);
+        let pieces: Vec<(f64, f64, &str)> = split.segments
+            .iter()
+            .map(|s| (s.start, s.end, s.text.as_str()))
+            .collect();
+        assert_eq!(pieces, vec![
+            (0.0, 6.0, "one two three"),
+            (6.0, 12.0, "four five six"),
+            (12.0, 18.0, "seven eight nine"),
+            (18.0, 20.0, "ten"),
+        ]);
+        assert_eq!(split.segments[1].words.len(), 3);
+    }
+    
+    #[test]
+    fn test_resegmenting_without_word_timing_estimates_it() {
+        let split = transcript(&[(0.0, 2.0, "short"), (2.0, 14.0, "aaa bbb ccc ddd")]).resegmented(5.0);
+        
+        let texts: Vec<&str> = split.segments.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["short", "aaa", "bbb", "ccc", "ddd"]);
+        assert_eq!(split.segments[1].start, 2.0);
+        assert_eq!(split.segments.last().unwrap().end, 14.0);
+    }
+}
@@ -0,0 +1,61 @@
+use std::collections::VecDeque;
+
+use crate::project::Project;
+
+/// A project snapshot taken right before an edit
+struct UndoEntry {
+    /// What the edit did (shown to the user on undo/redo)
+    label: String,
+    /// Project as it was before the edit
+    project: Project,
+}
+
+/// Bounded undo/redo history of whole-project snapshots
+pub struct UndoStack {
+    redo: Vec<UndoEntry>,
+    undo: VecDeque<UndoEntry>,
+}
+
+impl UndoStack {
+    /// Oldest snapshots are dropped beyond this many entries
+    const MAX_ENTRIES: usize = 50;
+
+    pub fn new() -> Self {
+        Self {
+            redo: Vec::new(),
+            undo: VecDeque::new(),
+        }
+    }
+
+    /// Snapshot the project before an edit; clears anything that could be redone
+    pub fn record(&mut self, label: impl Into<String>, project: &Project) {
+        self.redo.clear();
+        self.undo.push_back(UndoEntry {
+            label: label.into(),
+            project: project.clone(),
+        });
+        while self.undo.len() > Self::MAX_ENTRIES {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Re-apply the last undone edit: returns the project to restore and its label
+    pub fn redo(&mut self, current: &Project) -> Option<(Project, String)> {
+        let entry = self.redo.pop()?;
+        self.undo.push_back(UndoEntry {
+            label: entry.label.clone(),
+            project: current.clone(),
+        });
+        Some((entry.project, entry.label))
+    }
+
+    /// Revert the last edit: returns the project to restore and its label
+    pub fn undo(&mut self, current: &Project) -> Option<(Project, String)> {
+        let entry = self.undo.pop_back()?;
+        self.redo.push(UndoEntry {
+            label: entry.label.clone(),
+            project: current.clone(),
+        });
+        Some((entry.project, entry.label))
+    }
+}
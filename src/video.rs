@@ -19,6 +19,18 @@ pub struct VideoFrame {
 
 #[allow(dead_code)]
 impl VideoFrame {
+    /// Decode a still image into a frame
+    pub fn from_image(path: &Path) -> Result<Self> {
+        let image = image::open(path)
+            .with_context(|| format!("Failed to open image {:?}", path))?
+            .into_rgba8();
+        Ok(Self {
+            height: image.height(),
+            width: image.width(),
+            data: image.into_raw(),
+        })
+    }
+    
     /// Convert frame data to an image::Frame for gpui rendering
     pub fn to_image_frame(&self) -> Option<image::Frame> {
         let img_buffer: ImageBuffer<Rgba<u8>, Vec<u8>> = 
@@ -26,6 +38,19 @@ impl VideoFrame {
         Some(image::Frame::new(img_buffer))
     }
     
+    /// Write the frame to disk as a PNG, creating the folder if needed
+    pub fn save_png(&self, path: &Path) -> Result<()> {
+        let buffer: ImageBuffer<Rgba<u8>, Vec<u8>> =
+            ImageBuffer::from_raw(self.width, self.height, self.data.clone())
+                .context("Frame data doesn't match its size")?;
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        buffer
+            .save_with_format(path, image::ImageFormat::Png)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+    
     /// Create a RenderImage for use with gpui's img() element
     pub fn to_render_image(&self) -> Option<Arc<RenderImage>> {
         let frame = self.to_image_frame()?;
@@ -165,6 +190,28 @@ impl VideoPlayer {
         Ok(())
     }
 
+    /// Start playing; the appsink keeps `current_frame` up to date
+    pub fn play(&self) {
+        if let Some(ref pipeline) = self.pipeline {
+            let _ = pipeline.set_state(gst::State::Playing);
+        }
+    }
+
+    /// Pause on the current frame
+    pub fn pause(&self) {
+        if let Some(ref pipeline) = self.pipeline {
+            let _ = pipeline.set_state(gst::State::Paused);
+        }
+    }
+
+    /// Whether the pipeline is playing (or about to be)
+    pub fn is_playing(&self) -> bool {
+        self.pipeline.as_ref().is_some_and(|pipeline| {
+            let (_, current, pending) = pipeline.state(gst::ClockTime::ZERO);
+            current == gst::State::Playing || pending == gst::State::Playing
+        })
+    }
+
     /// Seek to a normalized position (0.0 to 1.0)
     pub fn seek(&self, position: f64) {
         if let Some(ref pipeline) = self.pipeline {
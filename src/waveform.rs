@@ -1,62 +1,468 @@
 use gpui::*;
+use std::path::Path;
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use crate::audio::AudioData;
+use crate::audio::{self, AudioData, AudioPlayer};
+use crate::config::Theme;
+use crate::project::{Clip, Marker, MediaType};
+
+/// Default tick color for markers without one
+const MARKER_COLOR: u32 = 0xffb74d;
+
+/// Clip lane colors, cycled in timeline order
+const CLIP_COLORS: [u32; 4] = [0x5c6bc0, 0x26a69a, 0x8e24aa, 0xef6c00];
+
+/// Width of a clip whose length isn't known yet
+const UNKNOWN_CLIP_WIDTH: f32 = 48.0;
+
+/// A video clip or still as drawn in the clip lane
+#[derive(Clone)]
+struct ClipBlock {
+    id: String,
+    label: SharedString,
+    /// Seconds into the timeline
+    start: f64,
+    /// Seconds on screen (None until probed)
+    duration: Option<f64>,
+}
+
+/// A marker as drawn on the waveform
+#[derive(Clone)]
+struct MarkerTick {
+    color: u32,
+    label: SharedString,
+    /// Position (0.0 to 1.0)
+    position: f64,
+}
+
+/// A quiet stretch as shaded on the waveform
+#[derive(Clone)]
+struct SilenceBand {
+    /// Start and end (0.0 to 1.0)
+    start: f64,
+    end: f64,
+    /// Left out of the export
+    cut: bool,
+}
+
+/// Hover text for a marker tick
+struct MarkerTooltip {
+    text: SharedString,
+    theme: Theme,
+}
+
+impl Render for MarkerTooltip {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .px_2()
+            .py_1()
+            .bg(rgb(self.theme.background))
+            .border_1()
+            .border_color(rgb(self.theme.control_hover))
+            .rounded_md()
+            .text_xs()
+            .text_color(rgb(self.theme.text))
+            .child(self.text.clone())
+    }
+}
+
+/// Deepest zoom, in pixels per second
+const MAX_ZOOM: f64 = 1000.0;
+
+/// Zoom change per `+`/`-` press or cmd+scroll notch
+const ZOOM_STEP: f64 = 1.25;
+
+/// Shortest gap between seeks while dragging the playhead (~30 Hz)
+const SCRUB_INTERVAL: Duration = Duration::from_millis(33);
+
+/// Zoom clamped between showing the whole file and `MAX_ZOOM`
+fn clamp_zoom(zoom: f64, width: f32, duration: f64) -> f64 {
+    let fit = if duration > 0.0 { f64::from(width) / duration } else { 0.0 };
+    zoom.clamp(fit, MAX_ZOOM.max(fit))
+}
 
 /// Waveform visualization component with playhead
 pub struct Waveform {
     audio: AudioData,
     /// Cached bounds for click calculation
     bounds: Arc<Mutex<Option<Bounds<Pixels>>>>,
+    /// Video clips and stills in the lane under the waveform
+    clips: Vec<ClipBlock>,
+    /// Focus handle so `+`/`-` can zoom
+    focus_handle: FocusHandle,
+    /// Markers to draw, positioned 0.0 to 1.0
+    markers: Vec<MarkerTick>,
     /// Current playhead position (0.0 to 1.0)
     position: f64,
+    /// When the last seek went out, while the mouse is held down on the waveform
+    scrubbing: Option<Instant>,
+    /// Export in/out points, positioned 0.0 to 1.0
+    range: Option<(f64, f64)>,
+    /// Clip selected in the clips panel
+    selected_clip: Option<String>,
+    /// Seconds scrolled off the left edge
+    scroll: f64,
+    /// Quiet stretches found in the audio, and those already cut
+    silences: Vec<SilenceBand>,
+    /// Draw left above the center line and right below it (stereo files only)
+    split_channels: bool,
+    theme: Theme,
+    /// Pixels per second, before clamping to the panel width
+    zoom: f64,
 }
 
 impl Waveform {
-    pub fn new(audio: AudioData) -> Self {
+    pub fn new(audio: AudioData, zoom: f64, cx: &mut Context<Self>) -> Self {
         Self {
             audio,
             bounds: Arc::new(Mutex::new(None)),
+            clips: Vec::new(),
+            focus_handle: cx.focus_handle(),
+            markers: Vec::new(),
             position: 0.0,
+            scrubbing: None,
+            range: None,
+            selected_clip: None,
+            scroll: 0.0,
+            silences: Vec::new(),
+            split_channels: false,
+            theme: Theme::DARK,
+            zoom,
         }
     }
 
+    fn set_markers(&mut self, markers: Vec<MarkerTick>) {
+        self.markers = markers;
+    }
+
+    fn set_range(&mut self, range: Option<(f64, f64)>) {
+        self.range = range;
+    }
+
+    fn set_clips(&mut self, clips: Vec<ClipBlock>) {
+        self.clips = clips;
+    }
+
+    fn set_silences(&mut self, silences: Vec<SilenceBand>) {
+        self.silences = silences;
+    }
+
+    /// Move the playhead, scrolling to keep it in view
     pub fn set_position(&mut self, position: f64) {
+        // The mouse owns the playhead while dragging; playback ticks would pull it back
+        if self.scrubbing.is_some() {
+            return;
+        }
         self.position = position.clamp(0.0, 1.0);
+        
+        let (start, end) = self.visible_range();
+        let time = self.position * self.audio.duration;
+        if time < start || time > end {
+            // Page so the playhead lands near the left edge
+            self.scroll = time - (end - start) * 0.1;
+            self.clamp_scroll();
+        }
+    }
+
+    /// Normalized position under window x, clamped to the file
+    fn position_at(&self, x: Pixels) -> Option<f64> {
+        let bounds = (*self.bounds.lock().unwrap())?;
+        let zoom = self.effective_zoom();
+        if zoom <= 0.0 || self.audio.duration <= 0.0 {
+            return None;
+        }
+        let offset: f32 = (x - bounds.origin.x).into();
+        let time = self.scroll + f64::from(offset) / zoom;
+        Some((time / self.audio.duration).clamp(0.0, 1.0))
+    }
+    
+    /// Follow the mouse while it's held down, seeking at most every `SCRUB_INTERVAL`
+    fn scrub_to(&mut self, x: Pixels, cx: &mut Context<Self>) {
+        let Some(last_seek) = self.scrubbing else {
+            return;
+        };
+        let Some(position) = self.position_at(x) else {
+            return;
+        };
+        self.position = position;
+        if last_seek.elapsed() >= SCRUB_INTERVAL {
+            self.scrubbing = Some(Instant::now());
+            cx.emit(WaveformEvent::Seek(position));
+        }
+        cx.notify();
+    }
+    
+    /// Mouse released: land exactly where the drag stopped
+    fn end_scrub(&mut self, cx: &mut Context<Self>) {
+        if self.scrubbing.take().is_some() {
+            cx.emit(WaveformEvent::Seek(self.position));
+            cx.notify();
+        }
+    }
+
+    /// Switch between the mixed-down and the left/right view
+    fn toggle_channels(&mut self, cx: &mut Context<Self>) {
+        if self.audio.stereo.is_some() {
+            self.split_channels = !self.split_channels;
+            cx.notify();
+        }
+    }
+
+    /// Panel width from the last paint (0 before the first)
+    fn width(&self) -> f32 {
+        let bounds = *self.bounds.lock().unwrap();
+        bounds.map(|b| b.size.width.into()).unwrap_or(0.0)
+    }
+
+    fn effective_zoom(&self) -> f64 {
+        clamp_zoom(self.zoom, self.width(), self.audio.duration)
+    }
+
+    /// First and last second on screen
+    fn visible_range(&self) -> (f64, f64) {
+        let zoom = self.effective_zoom();
+        if zoom <= 0.0 {
+            return (0.0, self.audio.duration);
+        }
+        let end = self.scroll + f64::from(self.width()) / zoom;
+        (self.scroll, end.min(self.audio.duration))
+    }
+
+    fn clamp_scroll(&mut self) {
+        let zoom = self.effective_zoom();
+        let max_scroll = if zoom > 0.0 {
+            (self.audio.duration - f64::from(self.width()) / zoom).max(0.0)
+        } else {
+            0.0
+        };
+        self.scroll = self.scroll.clamp(0.0, max_scroll);
+    }
+
+    fn scroll_by(&mut self, pixels: f32, cx: &mut Context<Self>) {
+        let zoom = self.effective_zoom();
+        if zoom > 0.0 {
+            self.scroll += f64::from(pixels) / zoom;
+            self.clamp_scroll();
+            cx.notify();
+        }
+    }
+
+    /// Zoom keeping the time under `anchor_x` (window x, default: playhead) in place
+    fn zoom_by(&mut self, factor: f64, anchor_x: Option<Pixels>, cx: &mut Context<Self>) {
+        let Some(bounds) = *self.bounds.lock().unwrap() else {
+            return;
+        };
+        let width: f32 = bounds.size.width.into();
+        let old_zoom = self.effective_zoom();
+        if old_zoom <= 0.0 {
+            return;
+        }
+        
+        let anchor = match anchor_x {
+            Some(x) => f64::from(f32::from(x) - f32::from(bounds.origin.x)),
+            None => (self.position * self.audio.duration - self.scroll) * old_zoom,
+        };
+        let anchor = anchor.clamp(0.0, f64::from(width));
+        let anchor_time = self.scroll + anchor / old_zoom;
+        
+        self.zoom = clamp_zoom(old_zoom * factor, width, self.audio.duration);
+        self.scroll = anchor_time - anchor / self.zoom;
+        self.clamp_scroll();
+        cx.emit(WaveformEvent::Zoom(self.zoom));
+        cx.notify();
+    }
+}
+
+impl Focusable for Waveform {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
     }
 }
 
 impl Render for Waveform {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let samples = self.audio.samples.clone();
+        let peaks = self.audio.peaks.clone();
+        let sides = self.audio.stereo.clone().filter(|_| self.split_channels);
+        let has_stereo = self.audio.stereo.is_some();
+        let split_channels = self.split_channels;
+        let duration = self.audio.duration;
         let position = self.position;
+        let range = self.range;
+        let silences = self.silences.clone();
+        let scroll = self.scroll;
+        let requested_zoom = self.zoom;
         let bounds_for_paint = self.bounds.clone();
-        let bounds_for_click = self.bounds.clone();
+        let scrubbing = self.scrubbing.is_some();
+        let scrub_target = cx.entity().downgrade();
+        let theme = self.theme;
+        
+        let width = self.width();
+        let zoom = self.effective_zoom();
+        let (visible_start, visible_end) = self.visible_range();
 
-        div()
+        // Marker ticks sit above the canvas; clicking one seeks exactly to it
+        let marker_ticks: Vec<AnyElement> = self.markers
+            .iter()
+            .enumerate()
+            .filter_map(|(i, marker)| {
+                let position = marker.position;
+                let x = ((position * duration - scroll) * zoom) as f32;
+                if x < 0.0 || (width > 0.0 && x > width) {
+                    return None;
+                }
+                let label = marker.label.clone();
+                Some(div()
+                    .id(("marker", i))
+                    .absolute()
+                    .top_0()
+                    .left(px(x))
+                    .h_full()
+                    .w(px(3.0))
+                    .bg(rgb(marker.color))
+                    .cursor_pointer()
+                    .hover(|s| s.bg(rgb(0xffe0b2)))
+                    .tooltip(move |_window, cx| {
+                        let text = label.clone();
+                        cx.new(|_| MarkerTooltip { text, theme }).into()
+                    })
+                    .on_mouse_down(MouseButton::Left, cx.listener(move |this, _event: &MouseDownEvent, _window, cx| {
+                        cx.stop_propagation();
+                        this.position = position;
+                        cx.notify();
+                        cx.emit(WaveformEvent::Seek(position));
+                    }))
+                    .into_any_element())
+            })
+            .collect();
+
+        // One block per clip under the waveform, sharing its scroll and zoom
+        let playhead_time = position * duration;
+        let clip_blocks: Vec<AnyElement> = self.clips
+            .iter()
+            .enumerate()
+            .filter_map(|(i, clip)| {
+                let left = ((clip.start - scroll) * zoom) as f32;
+                let block_width = clip.duration
+                    .map_or(UNKNOWN_CLIP_WIDTH, |d| (d * zoom) as f32)
+                    .max(2.0);
+                if left + block_width < 0.0 || (width > 0.0 && left > width) {
+                    return None;
+                }
+                let playing = clip.duration
+                    .is_some_and(|d| (clip.start..clip.start + d).contains(&playhead_time));
+                let selected = self.selected_clip.as_deref() == Some(clip.id.as_str());
+                let color = CLIP_COLORS[i % CLIP_COLORS.len()];
+                let id = clip.id.clone();
+                let start = clip.start;
+                
+                Some(div()
+                    .id(("clip-block", i))
+                    .absolute()
+                    .top_0()
+                    .left(px(left))
+                    .w(px(block_width))
+                    .h_full()
+                    .px_1()
+                    .overflow_hidden()
+                    .rounded_sm()
+                    .border_1()
+                    .border_color(if selected {
+                        rgb(theme.playhead)
+                    } else if playing {
+                        rgb(theme.accent)
+                    } else {
+                        rgb(theme.background)
+                    })
+                    .bg(if clip.duration.is_some() { rgb(color) } else { rgb(theme.control) })
+                    .opacity(if playing || selected { 1.0 } else { 0.75 })
+                    .text_xs()
+                    .text_color(rgb(0xffffff))
+                    .cursor_pointer()
+                    // Unknown length: striped until it's probed
+                    .when(clip.duration.is_none(), |block| {
+                        block.child(
+                            canvas(|_, _, _| {}, move |bounds, _, window, _| {
+                                let mut x = 0.0_f32;
+                                let width: f32 = bounds.size.width.into();
+                                while x < width {
+                                    window.paint_quad(fill(
+                                        Bounds {
+                                            origin: point(bounds.origin.x + px(x), bounds.origin.y),
+                                            size: size(px(2.0), bounds.size.height),
+                                        },
+                                        rgb(color),
+                                    ));
+                                    x += 6.0;
+                                }
+                            })
+                            .absolute()
+                            .size_full(),
+                        )
+                    })
+                    .child(div().relative().truncate().child(clip.label.clone()))
+                    .on_mouse_down(MouseButton::Left, cx.listener(move |this, event: &MouseDownEvent, _window, cx| {
+                        cx.stop_propagation();
+                        this.selected_clip = Some(id.clone());
+                        cx.emit(WaveformEvent::SelectClip(id.clone()));
+                        // Double-click jumps to where the clip starts
+                        if event.click_count == 2 && this.audio.duration > 0.0 {
+                            this.position = (start / this.audio.duration).clamp(0.0, 1.0);
+                            cx.emit(WaveformEvent::Seek(this.position));
+                        }
+                        cx.notify();
+                    }))
+                    .into_any_element())
+            })
+            .collect();
+
+        let waveform = div()
             .id("waveform")
+            .track_focus(&self.focus_handle)
+            .relative()
             .w_full()
             .h_32()
-            .bg(rgb(0x2a2a2a))
+            .bg(rgb(theme.surface))
             .rounded_md()
             .overflow_hidden()
             .cursor_pointer()
-            .on_mouse_down(MouseButton::Left, cx.listener(move |this, event: &MouseDownEvent, _window, cx| {
-                // Get cached bounds and calculate relative position
-                if let Some(bounds) = *bounds_for_click.lock().unwrap() {
-                    let click_x: f32 = event.position.x.into();
-                    let origin_x: f32 = bounds.origin.x.into();
-                    let width: f32 = bounds.size.width.into();
-                    
-                    let relative_x = click_x - origin_x;
-                    let normalized = (relative_x / width).clamp(0.0, 1.0) as f64;
-                    
+            .on_mouse_down(MouseButton::Left, cx.listener(move |this, event: &MouseDownEvent, window, cx| {
+                this.focus_handle.focus(window, cx);
+                
+                // Seek to the click, then keep following the mouse until it's released
+                if let Some(normalized) = this.position_at(event.position.x) {
                     this.position = normalized;
+                    this.scrubbing = Some(Instant::now());
                     cx.notify();
                     cx.emit(WaveformEvent::Seek(normalized));
                 }
             }))
+            .on_scroll_wheel(cx.listener(|this, event: &ScrollWheelEvent, window, cx| {
+                let delta = event.delta.pixel_delta(window.line_height());
+                let (dx, dy): (f32, f32) = (delta.x.into(), delta.y.into());
+                
+                if event.modifiers.platform || event.modifiers.control {
+                    // cmd+scroll zooms around the pointer
+                    if dy != 0.0 {
+                        let factor = if dy > 0.0 { ZOOM_STEP } else { 1.0 / ZOOM_STEP };
+                        this.zoom_by(factor, Some(event.position.x), cx);
+                    }
+                } else {
+                    // Plain mouse wheels only scroll vertically; treat that as sideways
+                    let delta = if dx.abs() >= dy.abs() { dx } else { dy };
+                    this.scroll_by(-delta, cx);
+                }
+                cx.stop_propagation();
+            }))
+            .on_key_down(cx.listener(|this, event: &KeyDownEvent, _window, cx| {
+                match event.keystroke.key.as_str() {
+                    "+" | "=" => this.zoom_by(ZOOM_STEP, None, cx),
+                    "-" => this.zoom_by(1.0 / ZOOM_STEP, None, cx),
+                    "c" => this.toggle_channels(cx),
+                    _ => return,
+                }
+                cx.stop_propagation();
+            }))
             .child(
                 canvas(
                     move |bounds, _window, _cx| {
@@ -71,37 +477,87 @@ impl Render for Waveform {
                         let origin_x: f32 = bounds.origin.x.into();
                         let origin_y: f32 = bounds.origin.y.into();
 
-                        let sample_count = samples.len();
-                        if sample_count == 0 || width <= 0.0 {
+                        if duration <= 0.0 || width <= 0.0 {
                             return;
                         }
+                        let zoom = clamp_zoom(requested_zoom, width, duration);
 
                         let bar_width = 2.0_f32;
                         let bar_gap = 1.0_f32;
                         let bar_step = bar_width + bar_gap;
                         let num_bars = (width / bar_step) as usize;
+                        let bar_seconds = f64::from(bar_step) / zoom;
+
+                        let waveform_color = rgb(theme.waveform);
+                        let played_color = rgb(theme.waveform_played);
+
+                        // Export range as a band behind the bars, with solid edges
+                        if let Some((range_start, range_end)) = range {
+                            let x = |normalized: f64| ((normalized * duration - scroll) * zoom) as f32;
+                            let left = x(range_start).max(0.0);
+                            let right = x(range_end).min(width);
+                            if right > left {
+                                window.paint_quad(fill(
+                                    Bounds {
+                                        origin: point(px(origin_x + left), px(origin_y)),
+                                        size: size(px(right - left), px(height)),
+                                    },
+                                    rgba(0xffb74d33),
+                                ));
+                            }
+                            for edge in [x(range_start), x(range_end)] {
+                                if (0.0..=width).contains(&edge) {
+                                    window.paint_quad(fill(
+                                        Bounds {
+                                            origin: point(px(origin_x + edge - 1.0), px(origin_y)),
+                                            size: size(px(2.0), px(height)),
+                                        },
+                                        rgb(0xffb74d),
+                                    ));
+                                }
+                            }
+                        }
 
-                        let waveform_color = rgb(0x4fc3f7);
-                        let played_color = rgb(0x81d4fa);
-                        let playhead_x = position as f32 * width;
+                        // Silences behind the bars, darker once they're cut
+                        for band in &silences {
+                            let left = (((band.start * duration - scroll) * zoom) as f32).max(0.0);
+                            let right = (((band.end * duration - scroll) * zoom) as f32).min(width);
+                            if right > left {
+                                let alpha = if band.cut { 0x55 } else { 0x22 };
+                                window.paint_quad(fill(
+                                    Bounds {
+                                        origin: point(px(origin_x + left), px(origin_y)),
+                                        size: size(px(right - left), px(height)),
+                                    },
+                                    rgba((theme.text_muted << 8) | alpha),
+                                ));
+                            }
+                        }
 
-                        // Draw waveform bars
+                        // Draw waveform bars for the visible stretch only
                         for i in 0..num_bars {
                             let x = i as f32 * bar_step;
-                            let sample_idx = ((x / width) * sample_count as f32) as usize;
-                            let sample_idx = sample_idx.min(sample_count - 1);
-
-                            let range_start = sample_idx.saturating_sub(2);
-                            let range_end = (sample_idx + 3).min(sample_count);
-                            let avg_sample: f32 = samples[range_start..range_end]
-                                .iter()
-                                .sum::<f32>()
-                                / (range_end - range_start) as f32;
+                            let start = scroll + f64::from(x) / zoom;
+                            if start >= duration {
+                                break;
+                            }
 
-                            let bar_height = (avg_sample * max_amplitude).max(1.0);
+                            // Left grows up from the center line and right down, or both ways mixed
+                            let (above, below) = match &sides {
+                                Some([left, right]) => (
+                                    left.max_in(start, start + bar_seconds),
+                                    right.max_in(start, start + bar_seconds),
+                                ),
+                                None => {
+                                    let peak = peaks.max_in(start, start + bar_seconds);
+                                    (peak, peak)
+                                }
+                            };
+                            let above = (above * max_amplitude).max(1.0);
+                            let below = (below * max_amplitude).max(1.0);
 
                             // Color bars before playhead differently
-                            let color = if x < playhead_x {
+                            let color = if start < playhead_time {
                                 played_color
                             } else {
                                 waveform_color
@@ -110,56 +566,163 @@ impl Render for Waveform {
                             let bar_bounds = Bounds {
                                 origin: point(
                                     px(origin_x + x),
-                                    px(origin_y + center_y - bar_height),
+                                    px(origin_y + center_y - above),
                                 ),
-                                size: size(px(bar_width), px(bar_height * 2.0)),
+                                size: size(px(bar_width), px(above + below)),
                             };
 
                             window.paint_quad(fill(bar_bounds, color));
                         }
 
-                        // Draw playhead line
-                        let playhead_bounds = Bounds {
-                            origin: point(px(origin_x + playhead_x - 1.0), px(origin_y)),
-                            size: size(px(2.0), px(height)),
-                        };
-                        window.paint_quad(fill(playhead_bounds, rgb(0xffffff)));
+                        // Draw playhead line (when scrolled into view)
+                        let playhead_x = ((playhead_time - scroll) * zoom) as f32;
+                        if (0.0..=width).contains(&playhead_x) {
+                            let playhead_bounds = Bounds {
+                                origin: point(px(origin_x + playhead_x - 1.0), px(origin_y)),
+                                size: size(px(2.0), px(height)),
+                            };
+                            window.paint_quad(fill(playhead_bounds, rgb(theme.playhead)));
+                        }
+
+                        // Listen window-wide while dragging, so the drag survives leaving the panel
+                        if scrubbing {
+                            let target = scrub_target.clone();
+                            window.on_mouse_event(move |event: &MouseMoveEvent, phase, _window, cx| {
+                                if phase == DispatchPhase::Bubble {
+                                    let _ = target.update(cx, |this, cx| {
+                                        if event.pressed_button == Some(MouseButton::Left) {
+                                            this.scrub_to(event.position.x, cx);
+                                        } else {
+                                            this.end_scrub(cx);
+                                        }
+                                    });
+                                }
+                            });
+                            let target = scrub_target.clone();
+                            window.on_mouse_event(move |event: &MouseUpEvent, phase, _window, cx| {
+                                if phase == DispatchPhase::Bubble && event.button == MouseButton::Left {
+                                    let _ = target.update(cx, |this, cx| this.end_scrub(cx));
+                                }
+                            });
+                        }
                     },
                 )
                 .size_full(),
             )
+            .children(marker_ticks);
+
+        div()
+            .w_full()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(waveform)
+            // Clip lane
+            .when(!self.clips.is_empty(), |lane| {
+                lane.child(
+                    div()
+                        .id("clip-lane")
+                        .relative()
+                        .w_full()
+                        .h_8()
+                        .bg(rgb(theme.surface))
+                        .rounded_md()
+                        .overflow_hidden()
+                        .children(clip_blocks),
+                )
+            })
+            // Time range on screen, below the waveform
+            .child(
+                div()
+                    .w_full()
+                    .flex()
+                    .justify_between()
+                    .text_xs()
+                    .text_color(rgb(theme.text_muted))
+                    .child(format_duration(visible_start))
+                    .child(
+                        div()
+                            .flex()
+                            .gap_2()
+                            .child(if visible_end - visible_start < duration {
+                                "scroll to pan · ⌘ scroll or +/− to zoom"
+                            } else {
+                                "⌘ scroll or +/− to zoom"
+                            })
+                            // Mono files have nothing to split
+                            .when(has_stereo, |d| {
+                                d.child(
+                                    div()
+                                        .id("channels-toggle")
+                                        .cursor_pointer()
+                                        .text_color(if split_channels { rgb(theme.accent) } else { rgb(theme.text_muted) })
+                                        .hover(|s| s.text_color(rgb(theme.accent_hover)))
+                                        .child(if split_channels { "L/R (c)" } else { "mixed (c)" })
+                                        .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
+                                            this.toggle_channels(cx);
+                                        })),
+                                )
+                            }),
+                    )
+                    .child(format_duration(visible_end)),
+            )
     }
 }
 
 /// Events emitted by Waveform
 pub enum WaveformEvent {
     Seek(f64),
+    /// A block in the clip lane was clicked
+    SelectClip(String),
+    /// Zoom changed (pixels per second)
+    Zoom(f64),
 }
 
 impl EventEmitter<WaveformEvent> for Waveform {}
 
 /// Events emitted by Timeline
 pub enum TimelineEvent {
+    /// The timeline started playing its audio
+    PlaybackStarted,
     /// Position changed (normalized 0.0 to 1.0)
     PositionChanged(f64),
+    /// Playback moved the playhead (seconds); nothing needs to seek
+    PlayheadMoved(f64),
+    /// A clip was picked in the clip lane
+    ClipSelected(String),
+    /// Waveform zoom changed (pixels per second)
+    ZoomChanged(f64),
+    /// The silences button was clicked
+    CutSilences,
 }
 
 impl EventEmitter<TimelineEvent> for Timeline {}
 
 /// Timeline component with waveform, controls, and time display
 pub struct Timeline {
+    /// Plays the audio file (None if GStreamer couldn't open it)
+    audio_player: Option<AudioPlayer>,
     duration: f64,
     /// Whether audio is playing
     playing: bool,
     /// Current position in seconds
     position: f64,
+    /// Quiet stretches found with the default settings (seconds)
+    silences: Vec<(f64, f64)>,
+    /// Stretches the project leaves out on export (seconds)
+    cuts: Vec<(f64, f64)>,
+    theme: Theme,
     waveform: Entity<Waveform>,
 }
 
 impl Timeline {
-    pub fn new(audio: AudioData, cx: &mut Context<Self>) -> Self {
+    pub fn new(audio: AudioData, path: &Path, zoom: f64, cx: &mut Context<Self>) -> Self {
         let duration = audio.duration;
-        let waveform = cx.new(|_cx| Waveform::new(audio));
+        let silences = audio::detect_silence(&audio, audio::DEFAULT_SILENCE_DB, audio::DEFAULT_MIN_SILENCE_SECONDS);
+        let waveform = cx.new(|cx| Waveform::new(audio, zoom, cx));
+        let audio_player = AudioPlayer::new(path)
+            .inspect_err(|e| tracing::warn!("Audio playback unavailable: {}", e))
+            .ok();
 
         // Subscribe to waveform events
         cx.subscribe(&waveform, |this, _waveform, event: &WaveformEvent, cx| match event {
@@ -167,22 +730,173 @@ impl Timeline {
                 this.seek(*position, cx);
                 cx.notify();
             }
+            WaveformEvent::SelectClip(id) => cx.emit(TimelineEvent::ClipSelected(id.clone())),
+            WaveformEvent::Zoom(zoom) => cx.emit(TimelineEvent::ZoomChanged(*zoom)),
         })
         .detach();
 
         Self {
+            audio_player,
             duration,
             playing: false,
             position: 0.0,
+            silences,
+            cuts: Vec::new(),
+            theme: Theme::DARK,
             waveform,
         }
     }
 
+    /// Pause audio playback (e.g. because the project preview started playing)
+    pub fn pause(&mut self, cx: &mut Context<Self>) {
+        if let Some(ref player) = self.audio_player {
+            player.pause();
+        }
+        self.playing = false;
+        cx.notify();
+    }
+
+    /// Show the project's markers on the waveform
+    pub fn set_markers(&mut self, markers: &[Marker], cx: &mut Context<Self>) {
+        let markers = if self.duration > 0.0 {
+            markers
+                .iter()
+                .map(|m| MarkerTick {
+                    color: m.rgb().unwrap_or(MARKER_COLOR),
+                    label: format!("{} · {}", format_duration(m.time_seconds), m.label).into(),
+                    position: (m.time_seconds / self.duration).clamp(0.0, 1.0),
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        self.waveform.update(cx, |waveform, cx| {
+            waveform.set_markers(markers);
+            cx.notify();
+        });
+    }
+
+    /// Highlight the export range, in seconds (None clears it)
+    pub fn set_range(&mut self, range: Option<(f64, f64)>, cx: &mut Context<Self>) {
+        let range = range
+            .filter(|_| self.duration > 0.0)
+            .map(|(start, end)| (start / self.duration, end / self.duration));
+        self.waveform.update(cx, |waveform, cx| {
+            waveform.set_range(range);
+            cx.notify();
+        });
+    }
+
+    /// Lay the project's video clips and stills out in the clip lane
+    pub fn set_clips(&mut self, clips: &[Clip], cx: &mut Context<Self>) {
+        let clips = clips
+            .iter()
+            .filter(|c| matches!(c.media_type, MediaType::Video | MediaType::Image))
+            .map(|c| ClipBlock {
+                id: c.id.clone(),
+                label: c.description.clone().into(),
+                start: c.start_time,
+                duration: c.duration,
+            })
+            .collect();
+        self.waveform.update(cx, |waveform, cx| {
+            waveform.set_clips(clips);
+            cx.notify();
+        });
+    }
+    
+    /// Quiet stretches last found in the audio (seconds)
+    pub fn silences(&self) -> &[(f64, f64)] {
+        &self.silences
+    }
+
+    /// Look for silences again with other settings
+    pub fn detect_silence(&mut self, threshold_db: f64, min_duration: f64, cx: &mut Context<Self>) -> Vec<(f64, f64)> {
+        self.silences = audio::detect_silence(&self.waveform.read(cx).audio, threshold_db, min_duration);
+        self.sync_silences(cx);
+        self.silences.clone()
+    }
+
+    /// Shade the stretches the project cuts from the export (seconds)
+    pub fn set_cuts(&mut self, cuts: &[(f64, f64)], cx: &mut Context<Self>) {
+        self.cuts = cuts.to_vec();
+        self.sync_silences(cx);
+    }
+
+    fn sync_silences(&mut self, cx: &mut Context<Self>) {
+        let bands = if self.duration > 0.0 {
+            let band = |&(start, end): &(f64, f64), cut| SilenceBand {
+                start: start / self.duration,
+                end: end / self.duration,
+                cut,
+            };
+            self.cuts
+                .iter()
+                .map(|c| band(c, true))
+                .chain(self.silences.iter().filter(|s| !self.cuts.contains(s)).map(|s| band(s, false)))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        self.waveform.update(cx, |waveform, cx| {
+            waveform.set_silences(bands);
+            cx.notify();
+        });
+        cx.notify();
+    }
+    
+    /// Recolor the controls and waveform
+    pub fn set_theme(&mut self, theme: Theme, cx: &mut Context<Self>) {
+        self.theme = theme;
+        self.waveform.update(cx, |waveform, cx| {
+            waveform.theme = theme;
+            cx.notify();
+        });
+        cx.notify();
+    }
+    
+    /// Outline the clip selected elsewhere (None clears it)
+    pub fn set_selected_clip(&mut self, id: Option<String>, cx: &mut Context<Self>) {
+        self.waveform.update(cx, |waveform, cx| {
+            waveform.selected_clip = id;
+            cx.notify();
+        });
+    }
+
+    /// Playhead position in seconds
+    pub fn time(&self) -> f64 {
+        self.position
+    }
+
+    /// Length of the timeline's audio in seconds
+    pub fn duration(&self) -> f64 {
+        self.duration
+    }
+
+    /// Move the playhead without emitting a seek (used when the player drives it)
+    pub fn set_time(&mut self, seconds: f64, cx: &mut Context<Self>) {
+        self.position = seconds.clamp(0.0, self.duration);
+        let normalized = if self.duration > 0.0 {
+            self.position / self.duration
+        } else {
+            0.0
+        };
+        self.waveform.update(cx, |waveform, cx| {
+            waveform.set_position(normalized);
+            cx.notify();
+        });
+        cx.notify();
+    }
+
     fn seek(&mut self, normalized_position: f64, cx: &mut Context<Self>) {
         self.position = normalized_position * self.duration;
+        if let Some(ref player) = self.audio_player {
+            player.seek(self.position);
+        }
         cx.emit(TimelineEvent::PositionChanged(normalized_position));
     }
 
+    /// Follow the audio pipeline's position while playing
     fn start_playback_timer(&mut self, cx: &mut Context<Self>) {
         cx.spawn(async move |this, cx| {
             loop {
@@ -195,23 +909,22 @@ impl Timeline {
                         if !this.playing {
                             return false;
                         }
+                        let Some(ref player) = this.audio_player else {
+                            return false;
+                        };
 
-                        this.position += 0.05; // 50ms increment
-                        if this.position >= this.duration {
-                            this.position = 0.0;
+                        if player.take_eos() {
+                            player.pause();
+                            player.seek(0.0);
                             this.playing = false;
-                            cx.notify();
+                            this.set_time(0.0, cx);
                             return false;
                         }
 
-                        // Update waveform position
-                        let normalized = this.position / this.duration;
-                        this.waveform.update(cx, |waveform, cx| {
-                            waveform.set_position(normalized);
-                            cx.notify();
-                        });
-
-                        cx.notify();
+                        if let Some(seconds) = player.position() {
+                            this.set_time(seconds, cx);
+                            cx.emit(TimelineEvent::PlayheadMoved(seconds));
+                        }
                         true
                     })
                     .unwrap_or(false);
@@ -225,9 +938,18 @@ impl Timeline {
     }
 
     fn toggle_playback(&mut self, cx: &mut Context<Self>) {
+        let Some(ref player) = self.audio_player else {
+            return;
+        };
+
         self.playing = !self.playing;
         if self.playing {
+            player.seek(self.position);
+            player.play();
             self.start_playback_timer(cx);
+            cx.emit(TimelineEvent::PlaybackStarted);
+        } else {
+            player.pause();
         }
         cx.notify();
     }
@@ -238,6 +960,8 @@ impl Render for Timeline {
         let current_time = format_duration(self.position);
         let duration_str = format_duration(self.duration);
         let is_playing = self.playing;
+        let theme = self.theme;
+        let uncut = self.silences.iter().filter(|s| !self.cuts.contains(s)).count();
 
         div()
             .w_full()
@@ -259,11 +983,11 @@ impl Render for Timeline {
                             .flex()
                             .items_center()
                             .justify_center()
-                            .bg(rgb(0x4fc3f7))
+                            .bg(rgb(theme.accent))
                             .rounded_full()
                             .cursor_pointer()
-                            .hover(|s| s.bg(rgb(0x81d4fa)))
-                            .active(|s| s.bg(rgb(0x29b6f6)))
+                            .hover(|s| s.bg(rgb(theme.accent_hover)))
+                            .active(|s| s.bg(rgb(theme.accent)))
                             .child(if is_playing { "⏸" } else { "▶" })
                             .on_click(cx.listener(|this, _event: &ClickEvent, _window, cx| {
                                 this.toggle_playback(cx);
@@ -275,21 +999,29 @@ impl Render for Timeline {
                             .text_sm()
                             .font_weight(FontWeight::MEDIUM)
                             .child(format!("{} / {}", current_time, duration_str)),
-                    ),
+                    )
+                    // Dead air in the voiceover, shaded on the waveform
+                    .when(uncut > 0, |d| {
+                        d.child(
+                            div()
+                                .id("cut-silences")
+                                .px_2()
+                                .py_1()
+                                .rounded_md()
+                                .bg(rgb(theme.control))
+                                .text_xs()
+                                .text_color(rgb(theme.text_dim))
+                                .cursor_pointer()
+                                .hover(|s| s.bg(rgb(theme.control_hover)))
+                                .child(format!("✂ Cut {} silence{}", uncut, if uncut == 1 { "" } else { "s" }))
+                                .on_click(cx.listener(|_this, _event: &ClickEvent, _window, cx| {
+                                    cx.emit(TimelineEvent::CutSilences);
+                                })),
+                        )
+                    }),
             )
-            // Waveform
+            // Waveform (with its visible time range)
             .child(self.waveform.clone())
-            // Time markers below waveform
-            .child(
-                div()
-                    .w_full()
-                    .flex()
-                    .justify_between()
-                    .text_xs()
-                    .text_color(rgb(0x666666))
-                    .child("0:00")
-                    .child(duration_str),
-            )
     }
 }
 